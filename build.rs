@@ -0,0 +1,7 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    // Код gRPC-сервиса генерируется из proto только с функцией grpc
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/mnem_to_entropy.proto")
+        .expect("не удалось сгенерировать код из proto/mnem_to_entropy.proto");
+}
@@ -0,0 +1,107 @@
+#ifndef MNEM_TO_ENTROPY_H
+#define MNEM_TO_ENTROPY_H
+
+/* Сгенерировано cbindgen из src/ffi.rs; не редактировать вручную. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Код результата [`mnem_decode`].
+ */
+typedef enum MnemStatus {
+  MNEM_STATUS_OK = 0,
+  /**
+   * Слова не из словаря.
+   */
+  MNEM_STATUS_INVALID_WORDS = 1,
+  /**
+   * Число слов не 12/15/18/21/24.
+   */
+  MNEM_STATUS_BAD_WORD_COUNT = 2,
+  /**
+   * Контрольная сумма не сходится.
+   */
+  MNEM_STATUS_INVALID_CHECKSUM = 3,
+  /**
+   * NULL, не UTF-8 или неизвестный язык в параметрах.
+   */
+  MNEM_STATUS_INVALID_ARGUMENT = 4,
+  /**
+   * Внутренняя ошибка библиотеки.
+   */
+  MNEM_STATUS_INTERNAL = 5,
+} MnemStatus;
+
+/**
+ * Результат [`mnem_decode`]; освобождается только через [`mnem_free_result`].
+ */
+typedef struct MnemResult {
+  MnemStatus status;
+  /**
+   * Энтропия; NULL при ошибке.
+   */
+  uint8_t *entropy;
+  size_t entropy_len;
+  /**
+   * Энтропия в hex, строка с нулём в конце; NULL при ошибке.
+   */
+  char *entropy_hex;
+  /**
+   * Словарь, по которому распознана фраза; NULL при ошибке.
+   */
+  char *language;
+  /**
+   * Описание ошибки; NULL при успехе.
+   */
+  char *error;
+} MnemResult;
+
+/**
+ * Параметры декодирования. Вместо указателя на них можно передать NULL.
+ */
+typedef struct MnemOptions {
+  /**
+   * Декодировать фразу с неверной контрольной суммой.
+   */
+  bool ignore_checksum;
+  /**
+   * Язык словаря (english, japanese, ...); NULL — автоопределение.
+   */
+  const char *language;
+} MnemOptions;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Декодирует мнемонику в энтропию. Всегда возвращает результат (не NULL),
+ * который нужно освободить [`mnem_free_result`].
+ *
+ * # Safety
+ *
+ * `mnemonic` — NULL или строка с нулём в конце; `options` — NULL или
+ * указатель на корректную [`MnemOptions`], `language` в которой — NULL или
+ * строка с нулём в конце.
+ */
+MnemResult *mnem_decode(const char *mnemonic, const MnemOptions *options);
+
+/**
+ * Освобождает результат [`mnem_decode`] вместе со всеми его строками.
+ *
+ * # Safety
+ *
+ * `result` — NULL или указатель, полученный от [`mnem_decode`] и ещё не
+ * освобождённый.
+ */
+void mnem_free_result(MnemResult *result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MNEM_TO_ENTROPY_H */
@@ -0,0 +1,136 @@
+//! Мнемоники aezeed (LND): 24 слова английского словаря BIP39,
+//! в которых зашифрованы энтропия и дата создания кошелька.
+//!
+//! Раскладка 33 байт фразы: версия (1) | шифротекст AEZ (23) | соль (5) | CRC32C (4).
+//! Фраза распознаётся по версии и контрольной сумме; расшифровка не поддерживается.
+
+use crate::{indices_to_bytes, Language};
+use std::fmt;
+
+/// Количество слов в мнемонике aezeed.
+pub const AEZEED_WORD_COUNT: usize = 24;
+
+/// Поддерживаемая версия внешнего формата.
+const CIPHER_SEED_VERSION: u8 = 0;
+
+/// Причина, по которой фраза не распознана как aezeed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AezeedError {
+    /// Количество слов не равно 24.
+    BadWordCount(usize),
+    /// Слова, которых нет в английском словаре BIP39.
+    InvalidWords(Vec<String>),
+    /// Неизвестная версия формата.
+    UnsupportedVersion(u8),
+    /// Контрольная сумма CRC32C не сходится.
+    InvalidChecksum,
+}
+
+impl fmt::Display for AezeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AezeedError::BadWordCount(count) => write!(
+                f,
+                "Неверное количество слов: {} (aezeed требует {} слова)",
+                count, AEZEED_WORD_COUNT
+            ),
+            AezeedError::InvalidWords(words) => write!(
+                f,
+                "Неверные слова (нет в английском словаре): {:?}",
+                words.iter().take(3).collect::<Vec<_>>()
+            ),
+            AezeedError::UnsupportedVersion(version) => {
+                write!(f, "Неподдерживаемая версия aezeed: {}", version)
+            }
+            AezeedError::InvalidChecksum => write!(f, "Неверная контрольная сумма aezeed"),
+        }
+    }
+}
+
+impl std::error::Error for AezeedError {}
+
+/// Проверяет, похожа ли фраза на aezeed: 24 английских слова,
+/// известная версия и верная контрольная сумма. Пароль не нужен.
+pub fn is_aezeed(mnemonic: &str) -> bool {
+    cipher_seed_bytes(mnemonic).is_ok()
+}
+
+/// 33 байта фразы после проверки слов, версии и контрольной суммы.
+fn cipher_seed_bytes(mnemonic: &str) -> Result<Vec<u8>, AezeedError> {
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    if words.len() != AEZEED_WORD_COUNT {
+        return Err(AezeedError::BadWordCount(words.len()));
+    }
+
+    let invalid_words: Vec<String> = words
+        .iter()
+        .filter(|word| Language::English.find_word(word).is_none())
+        .map(|word| word.to_string())
+        .collect();
+    if !invalid_words.is_empty() {
+        return Err(AezeedError::InvalidWords(invalid_words));
+    }
+
+    let indices: Vec<u16> = words
+        .iter()
+        .map(|word| Language::English.find_word(word).expect("слово есть в словаре"))
+        .collect();
+    let bytes = indices_to_bytes(&indices);
+
+    if bytes[0] != CIPHER_SEED_VERSION {
+        return Err(AezeedError::UnsupportedVersion(bytes[0]));
+    }
+
+    let (data, checksum) = bytes.split_at(bytes.len() - 4);
+    if crc32c(data).to_be_bytes()[..] != checksum[..] {
+        return Err(AezeedError::InvalidChecksum);
+    }
+
+    Ok(bytes)
+}
+
+/// CRC-32C (Castagnoli), побитовый вариант.
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82F6_3B78
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Векторы версии 0 из cipherseed_test.go (LND), соль "salt1".
+    const LND_VECTOR_1: &str = "ability liquid travel stem barely drastic pact cupboard apple thrive \
+        morning oak feature tissue couch old math inform success suggest drink motion know royal";
+    const LND_VECTOR_2: &str = "able tree stool crush transfer cloud cross three profit outside hen \
+        citizen plate ride require leg siren drum success suggest drink require fiscal upgrade";
+
+    #[test]
+    fn lnd_vectors_are_recognized() {
+        for mnemonic in [LND_VECTOR_1, LND_VECTOR_2] {
+            assert!(is_aezeed(mnemonic));
+            let bytes = cipher_seed_bytes(mnemonic).unwrap();
+            assert_eq!(bytes[0], CIPHER_SEED_VERSION);
+            assert_eq!(&bytes[24..29], b"salt1");
+        }
+    }
+
+    #[test]
+    fn corrupted_phrases_are_rejected() {
+        let swapped = LND_VECTOR_1.replacen("ability", "able", 1);
+        assert_eq!(cipher_seed_bytes(&swapped), Err(AezeedError::InvalidChecksum));
+        assert_eq!(cipher_seed_bytes("ability liquid"), Err(AezeedError::BadWordCount(2)));
+        let bip39 = ["abandon"; 23].join(" ") + " art";
+        assert!(!is_aezeed(&bip39));
+    }
+}
@@ -0,0 +1,71 @@
+//! BIP85: детерминированная энтропия дочерних ключей из корневого ключа BIP32.
+//! Реализовано приложение 39' — дочерние мнемоники BIP39.
+
+use crate::derive::DeriveError;
+use crate::keys::secp;
+use crate::VALID_WORD_COUNTS;
+use bip39::{Language, Mnemonic};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv};
+use bitcoin_hashes::hmac::{Hmac, HmacEngine};
+use bitcoin_hashes::{sha512, Hash, HashEngine};
+
+/// Purpose путей BIP85 (`m/83696968'/...`).
+pub const BIP85_PURPOSE: u32 = 83696968;
+
+/// Номер приложения BIP85 для мнемоник BIP39.
+pub const BIP85_APP_BIP39: u32 = 39;
+
+/// Код языка словаря в пути BIP85 (0 — English, 1 — Japanese, ...).
+pub fn bip85_language_code(language: Language) -> u32 {
+    match language {
+        Language::English => 0,
+        Language::Japanese => 1,
+        Language::Korean => 2,
+        Language::Spanish => 3,
+        Language::SimplifiedChinese => 4,
+        Language::TraditionalChinese => 5,
+        Language::French => 6,
+        Language::Italian => 7,
+        Language::Czech => 8,
+        Language::Portuguese => 9,
+    }
+}
+
+/// Путь дочерней мнемоники: `m/83696968'/39'/language'/words'/index'`.
+pub fn bip85_bip39_path(language: Language, words: usize, index: u32) -> Result<DerivationPath, DeriveError> {
+    if !VALID_WORD_COUNTS.contains(&words) {
+        return Err(DeriveError::BadWordCount(words));
+    }
+    Ok(DerivationPath::from(vec![
+        ChildNumber::from_hardened_idx(BIP85_PURPOSE)?,
+        ChildNumber::from_hardened_idx(BIP85_APP_BIP39)?,
+        ChildNumber::from_hardened_idx(bip85_language_code(language))?,
+        ChildNumber::from_hardened_idx(words as u32)?,
+        ChildNumber::from_hardened_idx(index)?,
+    ]))
+}
+
+/// 64 байта энтропии BIP85: HMAC-SHA512 с ключом "bip-entropy-from-k"
+/// от закрытого ключа узла `path`.
+pub fn bip85_entropy(root: &Xpriv, path: &DerivationPath) -> Result<[u8; 64], DeriveError> {
+    let node = root.derive_priv(secp(), path)?;
+    let mut engine = HmacEngine::<sha512::Hash>::new(b"bip-entropy-from-k");
+    engine.input(&node.private_key.secret_bytes());
+    Ok(Hmac::<sha512::Hash>::from_engine(engine).to_byte_array())
+}
+
+/// Дочерняя мнемоника BIP85 из `words` слов с номером `index`: первые
+/// `words * 4 / 3` байт энтропии узла. Возвращает путь, энтропию и фразу.
+pub fn bip85_mnemonic(
+    root: &Xpriv,
+    language: Language,
+    words: usize,
+    index: u32,
+) -> Result<(DerivationPath, Vec<u8>, String), DeriveError> {
+    let path = bip85_bip39_path(language, words, index)?;
+    let entropy = bip85_entropy(root, &path)?[..words * 4 / 3].to_vec();
+    let mnemonic = Mnemonic::from_entropy_in(language, &entropy)
+        .expect("длина энтропии соответствует допустимому числу слов")
+        .to_string();
+    Ok((path, entropy, mnemonic))
+}
@@ -0,0 +1,219 @@
+//! Адреса Cardano: корневой ключ Icarus из энтропии фразы (не из BIP39 seed),
+//! деривация BIP32-Ed25519 по CIP-1852 и базовые адреса CIP-19.
+
+use crate::derive::{DeriveError, DerivedAddress};
+use crate::ed25519;
+use bitcoin::bech32::{self, Bech32, Hrp};
+use bitcoin::bip32::{ChildNumber, DerivationPath};
+use bitcoin::Network;
+use bitcoin_hashes::hmac::{Hmac, HmacEngine};
+use bitcoin_hashes::{sha512, Hash, HashEngine};
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+use sha2::Sha512;
+
+/// Число итераций PBKDF2 для корневого ключа Icarus.
+const ICARUS_ROUNDS: u32 = 4096;
+
+/// Purpose путей Cardano по CIP-1852.
+pub const CARDANO_PURPOSE: u32 = 1852;
+
+/// Тип монеты Cardano по SLIP-44.
+pub const CARDANO_COIN_TYPE: u32 = 1815;
+
+/// Расширенный закрытый ключ BIP32-Ed25519: kL, kR и код цепочки.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CardanoKey {
+    key: [u8; 64],
+    chain_code: [u8; 32],
+}
+
+impl CardanoKey {
+    /// Корневой ключ Icarus (Yoroi, Daedalus, Adalite):
+    /// PBKDF2-HMAC-SHA512(пароль, соль = энтропия, 4096 итераций, 96 байт)
+    /// с «зажатием» битов kL как в Ed25519.
+    pub fn icarus(entropy: &[u8], passphrase: &str) -> Self {
+        let mut output = [0u8; 96];
+        pbkdf2::pbkdf2_hmac::<Sha512>(passphrase.as_bytes(), entropy, ICARUS_ROUNDS, &mut output);
+        output[0] &= 0b1111_1000;
+        output[31] &= 0b0001_1111;
+        output[31] |= 0b0100_0000;
+        CardanoKey {
+            key: output[..64].try_into().expect("срез 64 байта"),
+            chain_code: output[64..].try_into().expect("срез 32 байта"),
+        }
+    }
+
+    /// Ключ в формате CIP-5: bech32 `root_xsk` от kL || kR || код цепочки.
+    pub fn to_root_xsk(&self) -> String {
+        let mut bytes = self.key.to_vec();
+        bytes.extend_from_slice(&self.chain_code);
+        bech32_encode("root_xsk", &bytes)
+    }
+
+    /// Открытый ключ: kL * B без хэширования (BIP32-Ed25519).
+    pub fn public_key(&self) -> [u8; 32] {
+        ed25519::scalar_public_key(self.key[..32].try_into().expect("срез 32 байта"))
+    }
+
+    /// Дочерний ключ по схеме BIP32-Ed25519 (вариант V2, как в Icarus);
+    /// индекс кодируется little-endian.
+    pub fn child(&self, index: ChildNumber) -> CardanoKey {
+        let index_bytes = u32::from(index).to_le_bytes();
+        let public_key;
+        let (z_tag, chain_tag, data): (u8, u8, &[u8]) = match index {
+            ChildNumber::Hardened { .. } => (0x00, 0x01, &self.key),
+            ChildNumber::Normal { .. } => {
+                public_key = self.public_key();
+                (0x02, 0x03, &public_key)
+            }
+        };
+        let z = hmac(&self.chain_code, &[&[z_tag], data, &index_bytes]);
+        let chain = hmac(&self.chain_code, &[&[chain_tag], data, &index_bytes]);
+
+        let mut key = [0u8; 64];
+        // kL' = 8 * ZL (первые 28 байт) + kL
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let low = if i < 28 { z[i] << 3 } else { 0 };
+            let high = if (1..=28).contains(&i) { z[i - 1] >> 5 } else { 0 };
+            let sum = u16::from(self.key[i]) + u16::from(low | high) + carry;
+            key[i] = sum as u8;
+            carry = sum >> 8;
+        }
+        // kR' = ZR + kR mod 2^256
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let sum = u16::from(self.key[32 + i]) + u16::from(z[32 + i]) + carry;
+            key[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+        CardanoKey {
+            key,
+            chain_code: chain[32..].try_into().expect("срез 32 байта"),
+        }
+    }
+
+    /// Ключ по пути от этого ключа.
+    pub fn derive(&self, path: &DerivationPath) -> CardanoKey {
+        path.into_iter().fold(self.clone(), |key, &index| key.child(index))
+    }
+}
+
+/// Путь ключа CIP-1852: `m/1852'/1815'/account'/role/index`
+/// (роль 0 — внешняя цепочка, 1 — сдача, 2 — ключ стейкинга).
+pub fn cardano_path(account: u32, role: u32, index: u32) -> Result<DerivationPath, DeriveError> {
+    Ok(DerivationPath::from(vec![
+        ChildNumber::from_hardened_idx(CARDANO_PURPOSE)?,
+        ChildNumber::from_hardened_idx(CARDANO_COIN_TYPE)?,
+        ChildNumber::from_hardened_idx(account)?,
+        ChildNumber::from_normal_idx(role)?,
+        ChildNumber::from_normal_idx(index)?,
+    ]))
+}
+
+/// Выводит `count` базовых адресов (CIP-19, тип 0) счёта `account`, начиная
+/// с индекса `start`: платёжный ключ `m/1852'/1815'/account'/role/i` и общий
+/// ключ стейкинга `m/1852'/1815'/account'/2/0`. Роль 0 — внешняя цепочка,
+/// 1 — сдача. Для любой сети, кроме `Bitcoin`, выводятся адреса тестовой сети.
+pub fn derive_cardano_addresses(
+    root: &CardanoKey,
+    network: Network,
+    account: u32,
+    role: u32,
+    start: u32,
+    count: u32,
+) -> Result<Vec<DerivedAddress>, DeriveError> {
+    let stake = root.derive(&cardano_path(account, 2, 0)?).public_key();
+    let stake_hash = blake2b_224(&stake);
+    let (header, hrp) = match network {
+        Network::Bitcoin => (0x01, "addr"),
+        _ => (0x00, "addr_test"),
+    };
+
+    (start..start.saturating_add(count))
+        .map(|index| {
+            let path = cardano_path(account, role, index)?;
+            let payment = root.derive(&path).public_key();
+            let mut bytes = vec![header];
+            bytes.extend_from_slice(&blake2b_224(&payment));
+            bytes.extend_from_slice(&stake_hash);
+            Ok(DerivedAddress {
+                path,
+                address: bech32_encode(hrp, &bytes),
+            })
+        })
+        .collect()
+}
+
+fn hmac(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut engine = HmacEngine::<sha512::Hash>::new(key);
+    for part in data {
+        engine.input(part);
+    }
+    Hmac::<sha512::Hash>::from_engine(engine).to_byte_array()
+}
+
+fn blake2b_224(data: &[u8]) -> [u8; 28] {
+    let mut hasher = Blake2bVar::new(28).expect("длина BLAKE2b допустима");
+    hasher.update(data);
+    let mut hash = [0u8; 28];
+    hasher.finalize_variable(&mut hash).expect("буфер нужной длины");
+    hash
+}
+
+fn bech32_encode(hrp: &str, data: &[u8]) -> String {
+    let hrp = Hrp::parse(hrp).expect("префикс bech32 задан в коде");
+    bech32::encode::<Bech32>(hrp, data).expect("длина укладывается в ограничение bech32")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, Options};
+
+    fn root(mnemonic: &str, passphrase: &str) -> CardanoKey {
+        let decoded = decode(mnemonic, &Options::default()).unwrap();
+        CardanoKey::icarus(decoded.entropy.as_bytes(), passphrase)
+    }
+
+    fn key_hex(key: &CardanoKey) -> String {
+        hex::encode([&key.key[..], &key.chain_code[..]].concat())
+    }
+
+    /// CIP-3, Icarus: корневой ключ без пароля и с паролем "foo".
+    #[test]
+    fn cip3_icarus_root() {
+        let mnemonic = "eight country switch draw meat scout mystery blade tip drift useless good keep usage title";
+        assert_eq!(
+            key_hex(&root(mnemonic, "")),
+            "c065afd2832cd8b087c4d9ab7011f481ee1e0721e78ea5dd609f3ab3f156d245\
+             d176bd8fd4ec60b4731c3918a2a72a0226c0cd119ec35b47e4d55884667f552a\
+             23f7fdcd4a10c6cd2c7393ac61d877873e248f417634aa3d812af327ffe9d620"
+        );
+        assert_eq!(
+            key_hex(&root(mnemonic, "foo")),
+            "70531039904019351e1afb361cd1b312a4d0565d4ff9f8062d38acf4b15cce41\
+             d7b5738d9c893feea55512a3004acb0d222c35d3e3d5cde943a15a9824cbac59\
+             443cf67e589614076ba01e354b1a432e0e6db3b59e37fc56b5fb0222970a010e"
+        );
+    }
+
+    /// Базовый адрес `m/1852'/1815'/0'/0/0` со стейкингом `.../2/0`
+    /// (тест bip32_12_base из cardano-serialization-lib).
+    #[test]
+    fn base_address() {
+        let root = root("test walk nut penalty hip pave soap entry language right filter choice", "");
+        let mainnet = derive_cardano_addresses(&root, Network::Bitcoin, 0, 0, 0, 1).unwrap();
+        assert_eq!(
+            mainnet[0].address,
+            "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3jcu5d8ps7zex2k2xt3uqxgjqnnj83ws8lhrn648jjxtwqfjkjv7"
+        );
+        assert_eq!(mainnet[0].path.to_string(), "1852'/1815'/0'/0/0");
+        let testnet = derive_cardano_addresses(&root, Network::Testnet, 0, 0, 0, 1).unwrap();
+        assert_eq!(
+            testnet[0].address,
+            "addr_test1qz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3jcu5d8ps7zex2k2xt3uqxgjqnnj83ws8lhrn648jjxtwq2ytjqp"
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use super::decode::load_wordlist;
+use super::input::{decompress, load_lines, open_reader, text_reader, Compression};
+use super::pipeline::{self, Selection, Source};
+use super::{parse_language, EXIT_FAILED};
+use clap::Args;
+use log::error;
+use mnem_to_entropy::{
+    decode, language_name, word_indices, ChiSquared, FrequencyAnalysis, Language, Options,
+    WORDLIST_SIZE,
+};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Порог z-оценки χ², выше которого распределение считается неравномерным.
+const Z_THRESHOLD: f64 = 3.0;
+/// Минимальная ожидаемая частота слова, при которой критерий χ² надёжен.
+const MIN_EXPECTED: f64 = 5.0;
+
+#[derive(Args, Debug)]
+pub struct AnalyzeArgs {
+    /// Файл с мнемониками (одна фраза на строку); без него читается stdin
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Option<PathBuf>,
+
+    /// Учитывать только фразы из словаря указанного языка
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Пользовательский словарь из 2048 слов (одно слово на строку)
+    #[arg(long, value_name = "FILE", conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+
+    /// Сколько самых частых слов вывести
+    #[arg(long, value_name = "N", default_value = "20")]
+    pub top: usize,
+}
+
+/// Индексы слов фразы и её словарь; `None`, если фраза не разбирается.
+/// Контрольная сумма не проверяется: слабые генераторы выдают и фразы
+/// с неверной суммой, а частоты слов от неё не зависят.
+fn phrase_indices(mnemonic_str: &str, options: &Options) -> Option<(Option<Language>, Vec<u16>)> {
+    let decoded = decode(mnemonic_str, options).ok()?;
+    let indices = word_indices(mnemonic_str, decoded.language, options)?;
+    Some((decoded.language, indices))
+}
+
+/// Вывод по критерию χ².
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Uniform,
+    Biased,
+    /// Ожидаемая частота слова меньше [`MIN_EXPECTED`].
+    TooFewSamples,
+}
+
+impl Verdict {
+    fn of(chi_squared: &ChiSquared) -> Self {
+        if chi_squared.expected() < MIN_EXPECTED {
+            Verdict::TooFewSamples
+        } else if chi_squared.z_score > Z_THRESHOLD {
+            Verdict::Biased
+        } else {
+            Verdict::Uniform
+        }
+    }
+
+    /// Значение колонки `verdict`.
+    fn code(self) -> &'static str {
+        match self {
+            Verdict::Uniform => "ok",
+            Verdict::Biased => "biased",
+            Verdict::TooFewSamples => "-",
+        }
+    }
+}
+
+pub fn run(args: &AnalyzeArgs) {
+    let source = match &args.input_file {
+        Some(path) => {
+            let (reader, _) = decompress(open_reader(path), Compression::Auto);
+            Source::reader(text_reader(reader, &format!("{:?}", path)), b'\n')
+        }
+        None => {
+            let lines = load_lines(
+                None,
+                None,
+                &tr!("Введите мнемоническую фразу:", "Enter the mnemonic phrase:"),
+                false,
+                false,
+            );
+            Source::Lines(lines.into_iter().map(Cow::Owned).collect())
+        }
+    };
+
+    let wordlist = args.wordlist.as_deref().map(|path| Arc::new(load_wordlist(path)));
+    let options = Options {
+        ignore_checksum: true,
+        language: args.language,
+        wordlist: wordlist.clone(),
+    };
+
+    let mut analysis = FrequencyAnalysis::new();
+    let mut skipped = 0;
+    // Число фраз по словарям: слова в отчёте показываются по самому частому
+    let mut languages: Vec<(Option<Language>, u64)> = Vec::new();
+    pipeline::run(
+        source,
+        0,
+        Selection::default(),
+        |_, _, mnemonic_str| phrase_indices(mnemonic_str, &options),
+        |_, _, _, parsed| match parsed {
+            Some((language, indices)) => {
+                analysis.add(&indices);
+                match languages.iter_mut().find(|(known, _)| *known == language) {
+                    Some((_, count)) => *count += 1,
+                    None => languages.push((language, 1)),
+                }
+            }
+            None => skipped += 1,
+        },
+    );
+
+    if analysis.phrases() == 0 {
+        error!(
+            "{}",
+            tr!(
+                "Нет фраз для анализа: ни одна строка не разобрана (пропущено: {})",
+                "No phrases to analyze: no line could be parsed (skipped: {})",
+                skipped
+            )
+        );
+        std::process::exit(EXIT_FAILED);
+    }
+
+    let language = languages
+        .iter()
+        .max_by_key(|(_, count)| *count)
+        .and_then(|(language, _)| *language);
+    let word = |index: u16| -> &str {
+        match (&wordlist, language) {
+            (Some(wordlist), _) => wordlist.word(index),
+            (None, Some(language)) => language.word_list()[index as usize],
+            (None, None) => unreachable!("без пользовательского словаря язык определён"),
+        }
+    };
+
+    println!(
+        "{}",
+        tr!(
+            "Фраз: {}, слов: {}, пропущено строк: {}, словарь: {}",
+            "Phrases: {}, words: {}, lines skipped: {}, wordlist: {}",
+            analysis.phrases(),
+            analysis.words(),
+            skipped,
+            language.map(language_name).unwrap_or("custom")
+        )
+    );
+
+    let overall = analysis.chi_squared();
+    println!(
+        "{}",
+        tr!(
+            "χ² по всем словам: {:.1} (степеней свободы: {}), z = {:.2}: {}",
+            "χ² over all words: {:.1} (degrees of freedom: {}), z = {:.2}: {}",
+            overall.statistic,
+            overall.degrees_of_freedom,
+            overall.z_score,
+            match Verdict::of(&overall) {
+                Verdict::Biased => tr!(
+                    "⚠️ распределение неравномерное",
+                    "⚠️ the distribution is not uniform"
+                ),
+                Verdict::Uniform => tr!(
+                    "отклонений от равномерного не видно",
+                    "no deviation from uniform detected"
+                ),
+                Verdict::TooFewSamples => tr!(
+                    "данных мало для вывода (нужно от {} слов)",
+                    "too little data to judge (at least {} words needed)",
+                    MIN_EXPECTED as usize * WORDLIST_SIZE
+                ),
+            }
+        )
+    );
+
+    println!("{}", tr!("\nЧастые слова:", "\nMost common words:"));
+    println!("index\tword\tcount\tratio");
+    for (index, count) in analysis.most_common(args.top) {
+        println!(
+            "{}\t{}\t{}\t{:.2}",
+            index,
+            word(index),
+            count,
+            count as f64 / overall.expected()
+        );
+    }
+
+    println!("{}", tr!("\nПозиции:", "\nPositions:"));
+    println!("position\tsamples\tchi2\tz\tverdict\ttop_word\ttop_count");
+    for position in 0..analysis.positions() {
+        let chi_squared = analysis.position_chi_squared(position);
+        let counts = analysis.position_counts(position);
+        let (top_index, top_count) = counts
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))
+            .expect("словарь не пуст");
+        println!(
+            "{}\t{}\t{:.1}\t{:.2}\t{}\t{}\t{}",
+            position + 1,
+            chi_squared.samples,
+            chi_squared.statistic,
+            chi_squared.z_score,
+            Verdict::of(&chi_squared).code(),
+            word(top_index as u16),
+            top_count
+        );
+    }
+}
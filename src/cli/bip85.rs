@@ -0,0 +1,146 @@
+use super::derive::save_lines;
+use super::i18n::Localized;
+use super::input::load_lines;
+use super::{batch_exit_code, parse_language, quiet, report_timing, shown, EXIT_USAGE};
+use clap::Args;
+use log::error;
+use mnem_to_entropy::{
+    bip85_bip39_path, bip85_mnemonic, decode, mnemonic_to_seed, Language, Network, Options, RootKeys,
+};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Args, Debug)]
+pub struct Bip85Args {
+    /// Мастер-фраза, из которой выводятся дочерние
+    #[arg(short, long, env = "MNEM_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Файл с мастер-фразами (одна фраза на строку)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Option<PathBuf>,
+
+    /// Файл для сохранения дочерних фраз (номер строки, путь, энтропия, фраза
+    /// через табуляцию)
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Пароль BIP39 мастер-фразы (25-е слово)
+    #[arg(long, env = "MNEM_PASSPHRASE", hide_env_values = true, default_value = "")]
+    pub passphrase: String,
+
+    /// Число слов дочерней фразы: 12, 15, 18, 21 или 24
+    #[arg(long, default_value_t = 12)]
+    pub words: usize,
+
+    /// Номер дочерней фразы (index' в пути BIP85)
+    #[arg(long, default_value_t = 0)]
+    pub index: u32,
+
+    /// Сколько дочерних фраз вывести подряд, начиная с --index
+    #[arg(long, default_value_t = 1)]
+    pub count: u32,
+
+    /// Язык дочерней фразы (входит в путь BIP85)
+    #[arg(long, value_parser = parse_language, default_value = "english")]
+    pub child_language: Language,
+
+    /// Использовать только словарь указанного языка для проверки мастер-фразы
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+}
+
+/// Дочерние фразы мастер-фразы: путь, энтропия в hex и фраза для каждого индекса.
+fn process_mnemonic(mnemonic_str: &str, args: &Bip85Args, options: &Options) -> Result<Vec<[String; 3]>, String> {
+    decode(mnemonic_str, options).map_err(|e| e.localized())?;
+    let seed = mnemonic_to_seed(mnemonic_str, &args.passphrase);
+    let root = RootKeys::from_seed(&seed, Network::Bitcoin);
+    (args.index..args.index.saturating_add(args.count))
+        .map(|index| {
+            let (path, entropy, child) =
+                bip85_mnemonic(&root.xprv, args.child_language, args.words, index).map_err(|e| e.localized())?;
+            Ok([format!("m/{}", path), hex::encode(entropy), child])
+        })
+        .collect()
+}
+
+pub fn run(args: &Bip85Args) {
+    // Число слов и индекс проверяются до чтения фраз
+    if let Err(e) = bip85_bip39_path(args.child_language, args.words, args.index) {
+        error!("{}", tr!("Ошибка: {}", "Error: {}", e.localized()));
+        std::process::exit(EXIT_USAGE);
+    }
+
+    let mnemonics = load_lines(
+        args.input_file.as_deref(),
+        args.mnemonic.as_deref(),
+        &tr!("Введите мастер-фразу:", "Enter the master mnemonic:"),
+        args.output_file.is_some(),
+        false,
+    );
+    let options = Options {
+        language: args.language,
+        ..Options::default()
+    };
+
+    let started = Instant::now();
+    let results: Vec<Result<Vec<[String; 3]>, String>> = mnemonics
+        .par_iter()
+        .map(|mnemonic_str| process_mnemonic(mnemonic_str, args, &options))
+        .collect();
+    report_timing(started, results.len());
+
+    let mut lines = Vec::new();
+    let mut error_count = 0;
+    for (idx, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(children) => {
+                if args.output_file.is_none() && !quiet() {
+                    println!("{}", tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1));
+                    println!(
+                        "{}",
+                        tr!("Мастер-фраза: {}", "Master mnemonic: {}", shown(&mnemonics[idx]))
+                    );
+                }
+                for [path, entropy, child] in children {
+                    if args.output_file.is_none() && quiet() {
+                        println!("{}", child);
+                    } else if args.output_file.is_none() {
+                        println!("{}", tr!("Путь: {}", "Path: {}", path));
+                        println!("{}", tr!("  Энтропия: {}", "  Entropy: {}", entropy));
+                        println!("{}", tr!("  Дочерняя фраза: {}", "  Child mnemonic: {}", shown(&child)));
+                    }
+                    lines.push(format!("{}\t{}\t{}\t{}", idx + 1, path, entropy, child));
+                }
+            }
+            Err(message) => {
+                if args.output_file.is_none() && !quiet() {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    eprintln!(
+                        "{}",
+                        tr!("Мастер-фраза: {}", "Master mnemonic: {}", shown(&mnemonics[idx]))
+                    );
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                error_count += 1;
+            }
+        }
+    }
+
+    if let Some(output_path) = &args.output_file {
+        let saved = tr!(
+            "✓ Дочерние фразы сохранены в файл: {:?}",
+            "✓ Child mnemonics saved to file: {:?}",
+            output_path
+        );
+        save_lines(output_path, &lines, &saved, mnemonics.len() - error_count, error_count);
+    }
+    if error_count > 0 && error_count == mnemonics.len() && !quiet() {
+        error!(
+            "{}",
+            tr!("\n❌ Все мнемоники завершились с ошибкой!", "\n❌ All mnemonics failed!")
+        );
+    }
+    std::process::exit(batch_exit_code(mnemonics.len() - error_count, error_count));
+}
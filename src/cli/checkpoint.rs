@@ -0,0 +1,98 @@
+//! Контрольные точки длинных пакетов: сколько строк входа уже записано в вывод.
+
+use super::{EXIT_IO, EXIT_USAGE};
+use log::error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Как часто (в строках входа) обновляется контрольная точка.
+pub const CHECKPOINT_INTERVAL: usize = 10_000;
+
+/// Файл контрольной точки: число обработанных строк и пути ко входным
+/// файлам, чтобы не продолжить по ошибке обработку другого входа.
+pub struct Checkpoint {
+    path: PathBuf,
+    input: String,
+}
+
+impl Checkpoint {
+    pub fn new(path: &Path, inputs: &[PathBuf]) -> Self {
+        let input = inputs
+            .iter()
+            .map(|input| input.display().to_string())
+            .collect::<Vec<_>>()
+            .join("\t");
+        Checkpoint {
+            path: path.to_path_buf(),
+            input,
+        }
+    }
+
+    /// Сколько строк уже обработано; 0, если контрольной точки ещё нет.
+    pub fn load(&self) -> usize {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return 0,
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при чтении контрольной точки {:?}: {}",
+                        "Failed to read checkpoint {:?}: {}",
+                        self.path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        };
+
+        let mut lines = content.lines();
+        let processed = lines.next().and_then(|line| line.trim().parse::<usize>().ok());
+        let input = lines.next().unwrap_or_default();
+        match processed {
+            Some(_) if input != self.input => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Контрольная точка {:?} относится к другому входу: {:?}",
+                        "Checkpoint {:?} belongs to a different input: {:?}",
+                        self.path,
+                        input
+                    )
+                );
+                std::process::exit(EXIT_USAGE);
+            }
+            Some(processed) => processed,
+            None => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Повреждённая контрольная точка {:?}",
+                        "Corrupted checkpoint {:?}",
+                        self.path
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    }
+
+    /// Атомарно записывает число обработанных строк (через временный файл).
+    pub fn save(&self, processed: usize) {
+        let tmp_path = self.path.with_extension("tmp");
+        let content = format!("{}\n{}\n", processed, self.input);
+        if let Err(e) = fs::write(&tmp_path, content).and_then(|_| fs::rename(&tmp_path, &self.path)) {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при записи контрольной точки {:?}: {}",
+                    "Failed to write checkpoint {:?}: {}",
+                    self.path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
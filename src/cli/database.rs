@@ -0,0 +1,237 @@
+//! Вывод результатов в базы данных: SQLite (`--output-sqlite`), PostgreSQL
+//! и MySQL (`--output-db`). Каждая база включается своей функцией Cargo.
+
+use super::decode::DecodeArgs;
+use super::{mnemonic_hash, EXIT_IO};
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+use super::EXIT_USAGE;
+use clap::ValueEnum;
+use log::error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Сколько строк вставляется в одной транзакции.
+pub const DATABASE_BATCH: usize = 10_000;
+
+/// Поведение при строке, хэш фразы которой уже есть в таблице.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Заменить прежнюю строку
+    Update,
+    /// Оставить прежнюю строку
+    Ignore,
+}
+
+/// Строка таблицы результатов. Сама мнемоника не хранится — только её SHA-256.
+pub struct Row {
+    pub mnemonic_hash: String,
+    pub entropy: Option<String>,
+    pub error: Option<String>,
+    pub timestamp: i64,
+}
+
+impl Row {
+    pub fn status(&self) -> &'static str {
+        if self.error.is_none() {
+            "ok"
+        } else {
+            "error"
+        }
+    }
+}
+
+/// Конкретная база: создаёт таблицу при подключении и вставляет пачку строк
+/// одной транзакцией.
+pub trait Backend: Send {
+    fn insert(&mut self, rows: &[Row]) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Таблица результатов; строки копятся и вставляются пачками по [`DATABASE_BATCH`].
+pub struct DatabaseSink {
+    /// Имя базы для сообщений (без пароля из URL).
+    target: String,
+    backend: Box<dyn Backend>,
+    pending: Vec<Row>,
+}
+
+impl DatabaseSink {
+    /// Подключается к базе из `--output-sqlite` или `--output-db`; `None`, если
+    /// ни одна не задана.
+    pub fn open(args: &DecodeArgs) -> Option<Self> {
+        let (target, backend) = connect(args)?;
+        Some(DatabaseSink {
+            target,
+            backend,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Добавляет результат: энтропию для успешной фразы или текст ошибки.
+    pub fn insert(&mut self, mnemonic: &str, entropy: Option<String>, error: Option<&str>) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        self.pending.push(Row {
+            mnemonic_hash: mnemonic_hash(mnemonic),
+            entropy,
+            error: error.map(str::to_string),
+            timestamp,
+        });
+        if self.pending.len() >= DATABASE_BATCH {
+            self.commit();
+        }
+    }
+
+    /// Вставляет накопленные строки одной транзакцией.
+    pub fn commit(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let rows = std::mem::take(&mut self.pending);
+        if let Err(e) = self.backend.insert(&rows) {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при записи в базу {}: {}",
+                    "Failed to write to database {}: {}",
+                    self.target,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Задан ли вывод в базу данных.
+pub fn requested(args: &DecodeArgs) -> bool {
+    let mut requested = false;
+    #[cfg(feature = "sqlite")]
+    {
+        requested |= args.output_sqlite.is_some();
+    }
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    {
+        requested |= args.output_db.is_some();
+    }
+    requested
+}
+
+fn connect(args: &DecodeArgs) -> Option<(String, Box<dyn Backend>)> {
+    if !requested(args) {
+        return None;
+    }
+    #[cfg(all(feature = "sqlite", any(feature = "postgres", feature = "mysql")))]
+    if args.output_sqlite.is_some() && args.output_db.is_some() {
+        error!(
+            "{}",
+            tr!(
+                "--output-sqlite и --output-db нельзя задавать одновременно",
+                "--output-sqlite and --output-db cannot be used together"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+
+    #[cfg(feature = "sqlite")]
+    if let Some(path) = &args.output_sqlite {
+        let target = format!("{:?}", path);
+        return match super::sqlite::SqliteBackend::open(path, &args.db_table, args.on_conflict) {
+            Ok(backend) => Some((target, Box::new(backend))),
+            Err(e) => connection_failed(&target, e),
+        };
+    }
+
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    if let Some(url) = &args.output_db {
+        let target = redact(url);
+        let scheme = url.split("://").next().unwrap_or_default();
+        let backend: Result<Box<dyn Backend>, Box<dyn std::error::Error>> = match scheme {
+            #[cfg(feature = "postgres")]
+            "postgres" | "postgresql" => {
+                super::postgres::PostgresBackend::connect(url, &args.db_table, args.on_conflict)
+                    .map(|backend| Box::new(backend) as Box<dyn Backend>)
+                    .map_err(Into::into)
+            }
+            #[cfg(feature = "mysql")]
+            "mysql" => super::mysql::MysqlBackend::connect(url, &args.db_table, args.on_conflict)
+                .map(|backend| Box::new(backend) as Box<dyn Backend>)
+                .map_err(Into::into),
+            _ => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Неподдерживаемая база {:?}; доступны: {}",
+                        "Unsupported database {:?}; available: {}",
+                        scheme,
+                        supported_schemes()
+                    )
+                );
+                std::process::exit(EXIT_USAGE);
+            }
+        };
+        return match backend {
+            Ok(backend) => Some((target, backend)),
+            Err(e) => connection_failed(&target, e),
+        };
+    }
+
+    None
+}
+
+fn connection_failed<E: std::fmt::Display>(target: &str, e: E) -> ! {
+    error!(
+        "{}",
+        tr!(
+            "Ошибка при подключении к базе {}: {}",
+            "Failed to connect to database {}: {}",
+            target,
+            e
+        )
+    );
+    std::process::exit(EXIT_IO);
+}
+
+/// Схемы URL, для которых собрана поддержка.
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn supported_schemes() -> String {
+    if cfg!(all(feature = "postgres", feature = "mysql")) {
+        "postgres://, mysql://".to_string()
+    } else if cfg!(feature = "postgres") {
+        tr!(
+            "postgres:// (mysql:// — с функцией mysql)",
+            "postgres:// (mysql:// needs the mysql feature)"
+        )
+    } else {
+        tr!(
+            "mysql:// (postgres:// — с функцией postgres)",
+            "mysql:// (postgres:// needs the postgres feature)"
+        )
+    }
+}
+
+/// URL без имени пользователя и пароля — для сообщений.
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn redact(url: &str) -> String {
+    match (url.split_once("://"), url.rsplit_once('@')) {
+        (Some((scheme, _)), Some((_, host))) => format!("{}://{}", scheme, host),
+        _ => url.to_string(),
+    }
+}
+
+/// Парсер имени таблицы для clap: имя подставляется в SQL, поэтому допускаются
+/// только буквы, цифры, '_' и точка между схемой и таблицей.
+pub fn parse_table(name: &str) -> Result<String, String> {
+    let valid = name.split('.').all(|part| {
+        part.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if valid && name.split('.').count() <= 2 {
+        Ok(name.to_string())
+    } else {
+        Err(tr!(
+            "недопустимое имя таблицы '{}': только латинские буквы, цифры, '_' и 'схема.таблица'",
+            "invalid table name '{}': only Latin letters, digits, '_' and 'schema.table'",
+            name
+        ))
+    }
+}
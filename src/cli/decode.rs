@@ -0,0 +1,2525 @@
+use super::checkpoint::{Checkpoint, CHECKPOINT_INTERVAL};
+use super::i18n::Localized;
+use super::input::{
+    decompress, expand_inputs, load_lines, map_file, mapped_text, open_reader, read_interactive,
+    read_text, stdin_piped, stdin_reader, text_reader, Compression,
+};
+use super::pipeline::{self, Dedupe, Keep, Origin, Rejected, Sample, Selection, Source};
+use super::duplicates::Duplicates;
+use super::progress::{suspended, Progress};
+use super::stats::Stats;
+use super::template::{Field, Template};
+use super::output::{compressed, OutputCompression, Staged};
+use super::{
+    batch_exit_code, parse_language, quiet, redacting, report_timing, shown, EXIT_IO,
+    EXIT_USAGE,
+};
+#[cfg(feature = "encryption")]
+use super::encryption::{self, parse_recipient};
+#[cfg(feature = "qr")]
+use super::qr::{self, QrArgs, QrContent};
+#[cfg(feature = "qr")]
+use super::EXIT_FAILED;
+#[cfg(feature = "database")]
+use super::database::{self, DatabaseSink, OnConflict};
+use clap::{Args, ValueEnum};
+use log::{error, info, trace};
+use mnem_to_entropy::{
+    autocorrect, bit_string, check_entropy, decode, decode_all, detect_language,
+    diagnose_mnemonic, find_confusables, fix_homoglyphs, is_aezeed, language_name,
+    lenient_mnemonic, mnemonic_from_indices, mnemonic_to_seed, sanitize_mnemonic, suggest_words,
+    verify_round_trip, word_indices, Confusable, DecodeError, Entropy,
+    EntropyWarning, Fingerprint, IndicesError, Language, Network, Options, RootKeys, Sanitize,
+    VerifyError, WordCorrection, Wordlist, VALID_WORD_COUNTS,
+};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Текстовая кодировка энтропии.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Hex,
+    Base64,
+    Base58,
+}
+
+/// Формат входных строк.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputFormat {
+    /// Слова мнемоники
+    Words,
+    /// Индексы слов в словаре (0–2047) через пробел или запятую
+    Indices,
+}
+
+/// Формат вывода результатов.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Текст: в консоль блоками, в файл — по строке на мнемонику
+    Text,
+    /// JSON Lines: по объекту на строку, в порядке входа
+    Jsonl,
+    /// CSV с заголовком: номер строки, статус и ошибка для каждой мнемоники
+    Csv,
+    /// Сырые байты энтропии подряд, без разделителей
+    Raw,
+    /// Как text, но вместо энтропии — 11-битные индексы слов через пробел
+    Indices,
+    /// Как text, но вместо энтропии — все биты фразы, контрольная сумма отделена: ENT|CS
+    Bits,
+}
+
+/// Заполнитель строки с ошибкой для `--keep-lines`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placeholder {
+    /// Пустая строка
+    Empty,
+    /// ERROR:<категория>, например ERROR:checksum
+    Error,
+}
+
+impl OutputFormat {
+    /// Текстовые форматы: блоки в консоль, строки с колонками через табуляцию в файл.
+    fn is_text(self) -> bool {
+        matches!(self, OutputFormat::Text | OutputFormat::Indices | OutputFormat::Bits)
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DecodeArgs {
+    #[arg(short, long, env = "MNEM_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Файлы с мнемониками; опцию можно повторять или передать шаблон ('dumps/*.txt')
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Vec<PathBuf>,
+
+    /// Изображение с QR-кодом фразы: текст мнемоники, SeedQR или CompactSeedQR
+    #[cfg(feature = "qr")]
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["mnemonic", "input_file", "watch", "repl"])]
+    pub input_image: Option<PathBuf>,
+
+    /// Сжатие входа: auto (по сигнатуре), none, gz или zst
+    #[arg(long, value_enum, default_value = "auto")]
+    pub compression: Compression,
+
+    /// Записи входа и вывода разделяются байтом NUL, а не переводом строки
+    /// (для xargs -0 и фраз с необычными пробелами)
+    #[arg(short = '0', long = "null", default_value = "false")]
+    pub null: bool,
+
+    /// Формат входных строк: words или indices (номера слов в словаре --language
+    /// или --wordlist, по умолчанию английском)
+    #[arg(long, value_enum, default_value = "words")]
+    pub input_format: InputFormat,
+
+    /// Терпимый разбор переписанных фраз: нумерация (1. 2) #3), запятые,
+    /// точки с запятой и лишние пробелы или табуляции между словами
+    #[arg(long, default_value = "false")]
+    pub lenient: bool,
+
+    /// Очищать строки перед декодированием: all (по умолчанию) или этапы через
+    /// запятую — bom, zero-width, quotes, crlf, punctuation, lowercase; что
+    /// изменено, сообщается для каждой строки
+    #[arg(long, value_name = "STEPS", num_args = 0..=1, default_missing_value = "all")]
+    pub sanitize: Option<Sanitize>,
+
+    /// Отбрасывать повторяющиеся фразы до обработки: normalized (по умолчанию) —
+    /// без учёта числа пробелов между словами, exact — только побайтные повторы
+    #[arg(
+        long,
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "normalized",
+        conflicts_with = "checkpoint"
+    )]
+    pub dedupe: Option<Dedupe>,
+
+    /// Не показывать индикатор прогресса (по умолчанию он выводится в stderr,
+    /// если это терминал)
+    #[arg(long, default_value = "false")]
+    pub no_progress: bool,
+
+    /// Пропустить первые N непустых строк входа
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    pub skip: usize,
+
+    /// Обработать не больше N строк входа (после --skip)
+    #[arg(long, value_name = "N")]
+    pub take: Option<usize>,
+
+    /// Обработать случайную выборку: каждая строка берётся с вероятностью
+    /// RATE (0 < RATE ≤ 1), например 0.01 — около процента строк
+    #[arg(long, value_name = "RATE", value_parser = parse_rate, conflicts_with = "checkpoint")]
+    pub sample: Option<f64>,
+
+    /// Зерно генератора для --sample: одинаковое зерно даёт одинаковую выборку
+    #[arg(long, value_name = "N", requires = "sample")]
+    pub seed: Option<u64>,
+
+    /// Обрабатывать только строки с указанным числом слов (опцию можно
+    /// повторять); остальные — пароли и мусор в смешанных дампах —
+    /// пропускаются без ошибок
+    #[arg(long, value_name = "N", value_parser = parse_word_count)]
+    pub filter_words: Vec<usize>,
+
+    /// Записать строки, пропущенные --filter-words, в отдельный файл
+    #[arg(long, value_name = "FILE", requires = "filter_words")]
+    pub filtered_out: Option<PathBuf>,
+
+    /// Строка вывода на каждую строку входа: на месте ошибки — пустая строка
+    /// (empty, по умолчанию) или ERROR:<категория> (error); для форматов text,
+    /// indices и bits
+    #[arg(
+        long,
+        value_enum,
+        value_name = "PLACEHOLDER",
+        num_args = 0..=1,
+        default_missing_value = "empty",
+        conflicts_with_all = ["dedupe", "unordered", "filter_words", "sample"]
+    )]
+    pub keep_lines: Option<Placeholder>,
+
+    /// Отобразить входной файл в память вместо чтения построчно в память процесса
+    #[arg(long, default_value = "false", requires = "input_file")]
+    pub mmap: bool,
+
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Записывать результаты в базу SQLite
+    /// (хэш мнемоники, энтропия, статус, ошибка, время)
+    #[cfg(feature = "sqlite")]
+    #[arg(long, value_name = "FILE", conflicts_with = "unordered")]
+    pub output_sqlite: Option<PathBuf>,
+
+    /// Записывать результаты в PostgreSQL или MySQL: postgres://… или mysql://…
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    #[arg(
+        long,
+        value_name = "URL",
+        env = "MNEM_OUTPUT_DB",
+        hide_env_values = true,
+        conflicts_with = "unordered"
+    )]
+    pub output_db: Option<String>,
+
+    /// Таблица результатов для --output-sqlite и --output-db
+    #[cfg(feature = "database")]
+    #[arg(long, value_name = "NAME", default_value = "results", value_parser = database::parse_table)]
+    pub db_table: String,
+
+    /// Если фраза уже есть в таблице: update (заменить строку) или ignore (оставить)
+    #[cfg(feature = "database")]
+    #[arg(long, value_enum, default_value = "update")]
+    pub on_conflict: OnConflict,
+
+    /// Дописывать результаты и лог ошибок в конец существующих файлов
+    /// вместо их замены
+    #[arg(long, default_value = "false", requires = "output_file")]
+    pub append: bool,
+
+    #[arg(long, default_value = "true")]
+    pub hex: bool,
+
+    /// Кодировка энтропии в текстовых форматах: hex, base64 или base58
+    #[arg(long, value_enum, default_value = "hex")]
+    pub encoding: Encoding,
+
+    #[arg(long, default_value = "false")]
+    pub ignore_checksum: bool,
+
+    #[arg(long, env = "MNEM_ERROR_LOG")]
+    pub error_log: Option<PathBuf>,
+
+    /// Сжимать выходной файл и лог ошибок: gz или zstd
+    #[arg(long, value_enum)]
+    pub compress_output: Option<OutputCompression>,
+
+    #[arg(long, default_value = "false")]
+    pub skip_invalid: bool,
+
+    #[arg(long, default_value = "false")]
+    pub verbose_errors: bool,
+
+    /// Добавить в лог ошибок для каждого слова не из словаря до K ближайших
+    /// слов словаря с учётом опечаток на соседних клавишах (по умолчанию 5);
+    /// строки лога пишутся в подробном виде, как с --verbose-errors
+    #[arg(
+        long,
+        value_name = "K",
+        num_args = 0..=1,
+        default_missing_value = "5",
+        value_parser = clap::value_parser!(u8).range(1..=10),
+        requires = "error_log"
+    )]
+    pub suggest: Option<u8>,
+
+    /// Вывести в stderr статистику прогона: ошибки по категориям, число слов,
+    /// языки, скорость
+    #[arg(long, default_value = "false", conflicts_with = "repl")]
+    pub stats: bool,
+
+    /// Записать отчёт о строках с одинаковой энтропией (одна фраза на разных
+    /// языках, с опечатками или разными пробелами): энтропия, число строк
+    /// и номера строк с языками
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["unordered", "checkpoint", "repl", "watch"])]
+    pub report_duplicates: Option<PathBuf>,
+
+    /// Записать статистику прогона в файл JSON
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["repl", "watch"])]
+    pub stats_json: Option<PathBuf>,
+
+    /// Использовать только словарь указанного языка вместо автоопределения
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Пользовательский словарь из 2048 слов (одно слово на строку)
+    #[arg(long, conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+
+    /// Добавить в выходной файл колонку с языком словаря (через табуляцию)
+    #[arg(long, default_value = "false")]
+    pub with_language: bool,
+
+    /// Определять язык каждой строки, в том числе ошибочной (колонка языка
+    /// в выводе), и вывести в конце распределение строк по языкам
+    #[arg(long, default_value = "false")]
+    pub language_report: bool,
+
+    /// Добавить к каждому результату отпечаток энтропии — первые 8 hex-символов
+    /// её SHA-256: по нему результаты сопоставляются, не раскрывая секрет
+    #[arg(long, default_value = "false")]
+    pub fingerprint: bool,
+
+    /// Добавить к каждому результату отпечаток мастер-ключа BIP32 (4 байта,
+    /// как показывают аппаратные кошельки); учитывает --passphrase
+    #[arg(long, default_value = "false")]
+    pub with_fingerprint: bool,
+
+    /// Вывести корневые ключи BIP32 (xprv/xpub), полученные из seed
+    #[arg(long, default_value = "false")]
+    pub root_keys: bool,
+
+    /// Пароль BIP39 (25-е слово) для вычисления seed
+    #[arg(long, env = "MNEM_PASSPHRASE", hide_env_values = true, default_value = "")]
+    pub passphrase: String,
+
+    /// Сеть для расширенных ключей: bitcoin, testnet, signet, regtest
+    #[arg(long, default_value = "bitcoin")]
+    pub network: Network,
+
+    /// Шаблон строки вывода вместо блоков и колонок, например
+    /// '{index}\t{entropy}\t{language}'; поля: index, file, file_line, mnemonic,
+    /// words, entropy, language, fingerprint, master_fingerprint, xprv, xpub,
+    /// indices, bits, corrected, warnings
+    #[arg(long, conflicts_with_all = ["format", "repl"])]
+    pub template: Option<Template>,
+
+    /// Формат вывода: text, jsonl (по объекту JSON на строку, в порядке входа), csv, raw, indices или bits
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+
+    /// Для --format raw: дополнять каждую запись нулями до N байт
+    #[arg(long, value_name = "N")]
+    pub record_length: Option<usize>,
+
+    /// Для --format raw: предварять каждую запись байтом длины
+    #[arg(long, default_value = "false", conflicts_with = "record_length")]
+    pub length_prefix: bool,
+
+    /// Писать результаты сразу по готовности, с номером строки входа в первой колонке
+    #[arg(long, default_value = "false")]
+    pub unordered: bool,
+
+    /// Файл контрольной точки: при перезапуске с теми же аргументами
+    /// уже записанные строки пропускаются, а вывод дописывается
+    #[arg(long, requires_all = ["input_file", "output_file"])]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Добавить исходную мнемонику в вывод CSV
+    #[arg(long, default_value = "false")]
+    pub with_mnemonic: bool,
+
+    /// Исправлять опечатки: слово не из словаря заменяется единственным
+    /// ближайшим по расстоянию Левенштейна, если сходится контрольная сумма
+    #[arg(long, default_value = "false")]
+    pub autocorrect: bool,
+
+    /// Заменять в словах не из словаря кириллические и греческие буквы-двойники
+    /// латинскими (`е` → `e`), если после замены слово находится в словаре
+    #[arg(long, default_value = "false")]
+    pub fix_homoglyphs: bool,
+
+    /// Проверять качество энтропии: повторяющиеся байты, тестовые векторы
+    /// BIP39, низкая энтропия Шеннона — с предупреждением рядом с результатом
+    #[arg(long, default_value = "false")]
+    pub check_entropy: bool,
+
+    /// Обратная проверка: энтропия снова кодируется во фразу, которая должна
+    /// совпасть с исходной; при расхождении строка считается ошибкой
+    #[arg(long, default_value = "false")]
+    pub verify: bool,
+
+    /// Пословная диагностика: позиция, индекс в словаре, ближайшие слова
+    /// для неизвестных и языки, в словарях которых есть слово
+    #[arg(long, default_value = "false")]
+    pub diagnose: bool,
+
+    /// Следить за каталогом: каждый новый файл обрабатывается по мере появления,
+    /// результаты пишутся в каталог --watch-output
+    #[arg(
+        long,
+        value_name = "DIR",
+        requires = "watch_output",
+        conflicts_with_all = ["mnemonic", "input_file", "output_file", "error_log", "checkpoint"]
+    )]
+    pub watch: Option<PathBuf>,
+
+    /// Каталог для результатов --watch: FILE.out и FILE.errors для каждого входного файла
+    #[arg(long, value_name = "DIR", requires = "watch")]
+    pub watch_output: Option<PathBuf>,
+
+    /// Шифровать --output и --error-log (age): на ключи --recipient или, без них,
+    /// по паролю. Сжатие выполняется до шифрования
+    #[cfg(feature = "encryption")]
+    #[arg(long, default_value = "false", conflicts_with_all = ["append", "checkpoint"])]
+    pub encrypt_output: bool,
+
+    /// Входные файлы зашифрованы: age (по паролю или ключам --identity) или GPG
+    /// (расшифровывает gpg)
+    #[cfg(feature = "encryption")]
+    #[arg(long, default_value = "false", conflicts_with = "mmap")]
+    pub decrypt_input: bool,
+
+    /// Файл с ключами age (AGE-SECRET-KEY-...) для --decrypt-input; можно повторять
+    #[cfg(feature = "encryption")]
+    #[arg(long, value_name = "FILE", requires = "decrypt_input")]
+    pub identity: Vec<PathBuf>,
+
+    /// Открытый ключ age (age1...) получателя зашифрованного вывода; можно повторять
+    #[cfg(feature = "encryption")]
+    #[arg(long, value_name = "KEY", requires = "encrypt_output", value_parser = parse_recipient)]
+    pub recipient: Vec<String>,
+
+    /// Показывать набираемую фразу и выводить её в блоке результата
+    /// (по умолчанию ввод скрыт, а фраза не печатается)
+    #[arg(long, default_value = "false")]
+    pub show_input: bool,
+
+    /// Интерактивный режим: фраза за фразой до exit или Ctrl+D; при ошибке —
+    /// диагностика. История ввода не сохраняется
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with_all = ["mnemonic", "input_file", "output_file", "watch", "checkpoint"]
+    )]
+    pub repl: bool,
+
+    #[cfg(feature = "qr")]
+    #[command(flatten)]
+    pub qr: QrArgs,
+}
+
+/// Результат обработки одной мнемоники.
+struct Record {
+    entropy: Entropy,
+    language: Option<Language>,
+    root_keys: Option<RootKeys>,
+    /// Отпечаток мастер-ключа BIP32 (с `--with-fingerprint`).
+    master_fingerprint: Option<Fingerprint>,
+    /// Исправленные опечатки (с `--autocorrect`).
+    corrections: Vec<WordCorrection>,
+    /// Заменённые буквы-двойники (с `--fix-homoglyphs`).
+    homoglyphs: Vec<Confusable>,
+    /// Индексы слов для `--format indices`.
+    indices: Option<Vec<u16>>,
+    /// Признаки неслучайной энтропии (с `--check-entropy`).
+    warnings: Vec<EntropyWarning>,
+    /// Другие языки, в которых фраза тоже верна (с иной энтропией).
+    also_valid: Vec<Language>,
+}
+
+/// Категория ошибки строки — для статистики `--stats`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Слова не из словаря.
+    UnknownWords,
+    /// Число слов не 12/15/18/21/24.
+    WordCount,
+    /// Неверная контрольная сумма.
+    Checksum,
+    /// Фраза aezeed (LND): распознана, но не расшифровывается.
+    Aezeed,
+    /// Неверные индексы слов (`--input-format indices`).
+    Indices,
+    /// Результат не укладывается в формат вывода (`--record-length`).
+    Output,
+    /// Энтропия не воспроизводит исходную фразу (`--verify`).
+    Verify,
+}
+
+impl ErrorKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::UnknownWords => "unknown_words",
+            ErrorKind::WordCount => "word_count",
+            ErrorKind::Checksum => "checksum",
+            ErrorKind::Aezeed => "aezeed",
+            ErrorKind::Indices => "indices",
+            ErrorKind::Output => "output",
+            ErrorKind::Verify => "verify",
+        }
+    }
+}
+
+/// Ошибка обработки строки: текст на языке интерфейса и категория.
+struct Failure {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl From<&DecodeError> for ErrorKind {
+    fn from(e: &DecodeError) -> Self {
+        match e {
+            DecodeError::InvalidWords(_) => ErrorKind::UnknownWords,
+            DecodeError::BadWordCount(_) => ErrorKind::WordCount,
+            DecodeError::InvalidChecksum(_) => ErrorKind::Checksum,
+        }
+    }
+}
+
+impl From<DecodeError> for Failure {
+    fn from(e: DecodeError) -> Self {
+        Failure {
+            kind: ErrorKind::from(&e),
+            message: e.localized(),
+        }
+    }
+}
+
+impl From<VerifyError> for Failure {
+    fn from(e: VerifyError) -> Self {
+        Failure {
+            kind: ErrorKind::Verify,
+            message: e.localized(),
+        }
+    }
+}
+
+impl From<IndicesError> for Failure {
+    fn from(e: IndicesError) -> Self {
+        Failure {
+            kind: ErrorKind::Indices,
+            message: e.localized(),
+        }
+    }
+}
+
+fn process_mnemonic(mnemonic_str: &str, args: &DecodeArgs, options: &Options) -> Result<Record, Failure> {
+    let converted;
+    let mnemonic_str: &str = match args.input_format {
+        InputFormat::Words => {
+            converted = prepared(mnemonic_str, args);
+            &converted
+        }
+        InputFormat::Indices => {
+            let language = args.language.unwrap_or(Language::English);
+            converted = Cow::Owned(mnemonic_from_indices(mnemonic_str, language, options.wordlist.as_deref())?);
+            &converted
+        }
+    };
+    let dehomoglyphed = args
+        .fix_homoglyphs
+        .then(|| fix_homoglyphs(mnemonic_str, options))
+        .flatten();
+    let (mnemonic_str, homoglyphs) = match &dehomoglyphed {
+        Some(fixed) => (fixed.mnemonic.as_str(), fixed.replaced.clone()),
+        None => (mnemonic_str, Vec::new()),
+    };
+    let mut mnemonic = Cow::Borrowed(mnemonic_str);
+    let mut corrections = Vec::new();
+    let decoded = match decode(mnemonic_str, options) {
+        Ok(decoded) => decoded,
+        Err(_) if is_aezeed(mnemonic_str) => return Err(aezeed_failure()),
+        Err(e @ DecodeError::InvalidWords(_)) if args.autocorrect => {
+            let corrected = autocorrect(mnemonic_str, options).ok_or(e)?;
+            let decoded = decode(&corrected.mnemonic, options)?;
+            mnemonic = Cow::Owned(corrected.mnemonic);
+            corrections = corrected.corrections;
+            decoded
+        }
+        Err(e @ DecodeError::InvalidWords(_)) => {
+            let mut failure = Failure::from(e);
+            let confusables = find_confusables(mnemonic_str, options);
+            if !confusables.is_empty() {
+                failure.message = tr!(
+                    "{}; буквы-двойники: {}",
+                    "{}; lookalike letters: {}",
+                    failure.message,
+                    confusables_text(&confusables)
+                );
+            }
+            return Err(failure);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if args.verify {
+        verify_round_trip(&mnemonic, &decoded, options)?;
+    }
+
+    let root_keys = needs_root_keys(args).then(|| {
+        let seed = mnemonic_to_seed(&mnemonic, &args.passphrase);
+        RootKeys::from_seed(&seed, args.network)
+    });
+    let indices = needs_indices(args)
+        .then(|| word_indices(&mnemonic, decoded.language, options))
+        .flatten();
+    let also_valid = other_languages(&mnemonic, decoded.language, options);
+
+    Ok(Record {
+        warnings: entropy_warnings(&decoded.entropy, args),
+        also_valid,
+        entropy: decoded.entropy,
+        language: decoded.language,
+        master_fingerprint: master_fingerprint(&root_keys, args),
+        root_keys: root_keys.filter(|_| shows_root_keys(args)),
+        corrections,
+        homoglyphs,
+        indices,
+    })
+}
+
+/// Языки, кроме выбранного, в которых фраза тоже проходит проверку BIP39.
+/// Проверяются, только если язык определяется автоматически.
+fn other_languages(mnemonic: &str, chosen: Option<Language>, options: &Options) -> Vec<Language> {
+    if options.language.is_some() || options.wordlist.is_some() {
+        return Vec::new();
+    }
+    decode_all(mnemonic, options)
+        .into_iter()
+        .filter_map(|decoded| decoded.language)
+        .filter(|&language| Some(language) != chosen)
+        .collect()
+}
+
+/// Кандидаты `--suggest` для слов строки не из словаря: `слово 3 abandn:
+/// abandon, abandons; …`; `None`, если все слова из словаря.
+fn suggestions_text(mnemonic: &str, args: &DecodeArgs, options: &Options, limit: u8) -> Option<String> {
+    if args.input_format != InputFormat::Words {
+        return None;
+    }
+    let suggestions = suggest_words(&prepared(mnemonic, args), options, limit as usize);
+    if suggestions.is_empty() {
+        return None;
+    }
+    let words: Vec<String> = suggestions
+        .iter()
+        .map(|suggestion| {
+            let candidates = if suggestion.candidates.is_empty() {
+                "—".to_string()
+            } else {
+                suggestion.candidates.join(", ")
+            };
+            tr!(
+                "слово {} {}: {}",
+                "word {} {}: {}",
+                suggestion.position + 1,
+                suggestion.word,
+                candidates
+            )
+        })
+        .collect();
+    Some(tr!("подсказки: {}", "suggestions: {}", words.join("; ")))
+}
+
+/// Буквы-двойники через «; ».
+fn confusables_text(confusables: &[Confusable]) -> String {
+    confusables
+        .iter()
+        .map(Localized::localized)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Языки через запятую.
+fn languages_text(languages: &[Language]) -> String {
+    languages.iter().map(|&language| language_name(language)).collect::<Vec<_>>().join(", ")
+}
+
+/// Есть ли поле в шаблоне `--template`.
+fn template_uses(args: &DecodeArgs, field: Field) -> bool {
+    args.template.as_ref().is_some_and(|template| template.uses(field))
+}
+
+/// Выводятся ли корневые ключи: колонками или в шаблоне.
+fn shows_root_keys(args: &DecodeArgs) -> bool {
+    args.root_keys || template_uses(args, Field::Xprv) || template_uses(args, Field::Xpub)
+}
+
+/// Выводится ли отпечаток мастер-ключа: колонкой или в шаблоне.
+fn shows_master_fingerprint(args: &DecodeArgs) -> bool {
+    args.with_fingerprint || template_uses(args, Field::MasterFingerprint)
+}
+
+/// Нужны ли корневые ключи: для вывода или для отпечатка мастер-ключа.
+fn needs_root_keys(args: &DecodeArgs) -> bool {
+    shows_root_keys(args) || shows_master_fingerprint(args)
+}
+
+/// Отпечаток мастер-ключа для `--with-fingerprint`.
+fn master_fingerprint(root_keys: &Option<RootKeys>, args: &DecodeArgs) -> Option<Fingerprint> {
+    root_keys
+        .as_ref()
+        .filter(|_| shows_master_fingerprint(args))
+        .map(RootKeys::fingerprint)
+}
+
+/// Проверка качества энтропии (`--check-entropy`).
+fn entropy_warnings(entropy: &Entropy, args: &DecodeArgs) -> Vec<EntropyWarning> {
+    if args.check_entropy {
+        check_entropy(entropy.as_bytes())
+    } else {
+        Vec::new()
+    }
+}
+
+/// Предупреждения о качестве энтропии через «; ».
+fn warnings_text(warnings: &[EntropyWarning]) -> String {
+    warnings
+        .iter()
+        .map(|warning| warning.localized())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Описание исправлений для вывода: «слово → замена» через запятую;
+/// с `--redact` — только номера слов.
+fn corrections_text(corrections: &[WordCorrection]) -> String {
+    corrections
+        .iter()
+        .map(|correction| {
+            if redacting() {
+                tr!("слово {}", "word {}", correction.position + 1)
+            } else {
+                tr!("слово {}: {}", "word {}: {}", correction.position + 1, correction)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Фраза aezeed: версия и CRC32C сходятся, но расшифровка не поддерживается.
+fn aezeed_failure() -> Failure {
+    Failure {
+        kind: ErrorKind::Aezeed,
+        message: tr!(
+            "Фраза aezeed (LND): расшифровка aezeed не поддерживается",
+            "LND aezeed phrase: aezeed decryption is not supported"
+        ),
+    }
+}
+
+/// Строка после очистки (`--sanitize`) и терпимого разбора (`--lenient`).
+fn prepared<'a>(mnemonic: &'a str, args: &DecodeArgs) -> Cow<'a, str> {
+    let mut text = Cow::Borrowed(mnemonic);
+    if let Some(sanitize) = &args.sanitize {
+        text = Cow::Owned(sanitize_mnemonic(&text, sanitize).text);
+    }
+    if args.lenient {
+        text = Cow::Owned(lenient_mnemonic(&text));
+    }
+    text
+}
+
+/// Что изменила очистка `--sanitize` в строке, или `None`, если ничего.
+fn sanitize_report(idx: usize, mnemonic: &str, args: &DecodeArgs) -> Option<String> {
+    let applied = sanitize_mnemonic(mnemonic, args.sanitize.as_ref()?).applied;
+    if applied.is_empty() {
+        return None;
+    }
+    let changes: Vec<String> = applied.iter().map(Localized::localized).collect();
+    Some(tr!("🧹 Строка {}: {}", "🧹 Line {}: {}", idx + 1, changes.join(", ")))
+}
+
+/// Пословный отчёт о фразе для `--diagnose`.
+fn diagnosis_report(idx: usize, mnemonic: &str, args: &DecodeArgs, options: &Options) -> Vec<String> {
+    let diagnosis = diagnose_mnemonic(&prepared(mnemonic, args), options);
+    let mut lines = vec![tr!(
+        "🔍 Диагностика строки {} (словарь: {})",
+        "🔍 Diagnosis of line {} (wordlist: {})",
+        idx + 1,
+        diagnosis.language.map(language_name).unwrap_or("custom")
+    )];
+    for word in &diagnosis.words {
+        let index = word
+            .index
+            .map(|index| format!("#{}", index))
+            .unwrap_or_else(|| "—".to_string());
+        let mut details = Vec::new();
+        if !word.nearest.is_empty() {
+            details.push(tr!("ближайшие: {}", "nearest: {}", word.nearest.join(", ")));
+        }
+        if word.languages.is_empty() {
+            details.push(tr!("нет ни в одном словаре BIP39", "not in any BIP39 wordlist"));
+        } else {
+            let languages: Vec<&str> = word.languages.iter().map(|&l| language_name(l)).collect();
+            details.push(tr!("языки: {}", "languages: {}", languages.join(", ")));
+        }
+        lines.push(format!(
+            "  {:>2}. {:<12} {:<6} {}",
+            word.position + 1,
+            word.word,
+            index,
+            details.join("; ")
+        ));
+    }
+    lines.push(match &diagnosis.error {
+        Some(error) => tr!("  Итог: {}", "  Result: {}", error.localized()),
+        None => tr!("  Итог: фраза верна", "  Result: the phrase is valid"),
+    });
+    lines
+}
+
+/// Нужны ли формату вывода индексы слов.
+fn needs_indices(args: &DecodeArgs) -> bool {
+    matches!(args.format, OutputFormat::Indices | OutputFormat::Bits)
+        || template_uses(args, Field::Indices)
+        || template_uses(args, Field::Bits)
+}
+
+/// Основная колонка текстового вывода: энтропия или индексы слов.
+fn record_value(record: &Record, args: &DecodeArgs) -> String {
+    match (args.format, &record.indices) {
+        (OutputFormat::Indices, Some(indices)) => indices
+            .iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        (OutputFormat::Bits, Some(indices)) => bit_string(indices),
+        _ => entropy_text(&record.entropy, args),
+    }
+}
+
+/// Подпись основной колонки в консольном выводе.
+fn value_label(args: &DecodeArgs) -> String {
+    match args.format {
+        OutputFormat::Indices => tr!("Индексы слов", "Word indices"),
+        OutputFormat::Bits => tr!("Биты (ENT|CS)", "Bits (ENT|CS)"),
+        _ => tr!("Энтропия", "Entropy"),
+    }
+}
+
+/// Энтропия в текстовом виде: в выбранной кодировке или списком байт.
+fn entropy_text(entropy: &Entropy, args: &DecodeArgs) -> String {
+    if !args.hex {
+        return format!("{:?}", entropy.as_bytes());
+    }
+    match args.encoding {
+        Encoding::Hex => entropy.to_hex(),
+        Encoding::Base64 => entropy.to_base64(),
+        Encoding::Base58 => entropy.to_base58(),
+    }
+}
+
+/// Строка вывода успешного результата: по шаблону `--template` или
+/// колонки [`record_line`].
+fn output_line(
+    idx: usize,
+    location: Option<Location>,
+    mnemonic: &str,
+    record: &Record,
+    args: &DecodeArgs,
+) -> String {
+    let Some(template) = &args.template else {
+        return record_line(record, args);
+    };
+    template.render(|field| match field {
+        Field::Index => (idx + 1).to_string(),
+        Field::File => location
+            .map(|location| location.file.display().to_string())
+            .unwrap_or_default(),
+        Field::FileLine => location
+            .map(|location| location.line.to_string())
+            .unwrap_or_default(),
+        Field::Mnemonic => shown(mnemonic).into_owned(),
+        Field::Words => word_count(mnemonic, args).to_string(),
+        Field::Entropy => entropy_text(&record.entropy, args),
+        Field::Language => dictionary_name(record).to_string(),
+        Field::Fingerprint => record.entropy.fingerprint(),
+        Field::MasterFingerprint => record
+            .master_fingerprint
+            .map(|fingerprint| fingerprint.to_string())
+            .unwrap_or_default(),
+        Field::Xprv => record
+            .root_keys
+            .as_ref()
+            .map(|keys| keys.xprv.to_string())
+            .unwrap_or_default(),
+        Field::Xpub => record
+            .root_keys
+            .as_ref()
+            .map(|keys| keys.xpub.to_string())
+            .unwrap_or_default(),
+        Field::Indices => record
+            .indices
+            .as_ref()
+            .map(|indices| indices.iter().map(|index| index.to_string()).collect::<Vec<_>>().join(" "))
+            .unwrap_or_default(),
+        Field::Bits => record.indices.as_deref().map(bit_string).unwrap_or_default(),
+        Field::Corrected => corrections_text(&record.corrections),
+        Field::Warnings => warnings_text(&record.warnings),
+    })
+}
+
+/// Строка выходного файла: энтропия (или индексы) и дополнительные колонки через табуляцию.
+fn record_line(record: &Record, args: &DecodeArgs) -> String {
+    let mut columns = vec![record_value(record, args)];
+    if shows_language(args) {
+        columns.push(dictionary_name(record).to_string());
+    }
+    if args.fingerprint {
+        columns.push(record.entropy.fingerprint());
+    }
+    if let Some(fingerprint) = record.master_fingerprint {
+        columns.push(fingerprint.to_string());
+    }
+    if let Some(keys) = &record.root_keys {
+        columns.push(keys.xprv.to_string());
+        columns.push(keys.xpub.to_string());
+    }
+    columns.join("\t")
+}
+
+/// Число слов строки для статистики; индексы разделяются и запятыми.
+fn word_count(mnemonic: &str, args: &DecodeArgs) -> usize {
+    match args.input_format {
+        InputFormat::Words => prepared(mnemonic, args).split_whitespace().count(),
+        InputFormat::Indices => mnemonic
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|item| !item.is_empty())
+            .count(),
+    }
+}
+
+/// Название словаря для вывода.
+fn dictionary_name(record: &Record) -> &'static str {
+    record.language.map(language_name).unwrap_or("custom")
+}
+
+/// Выводится ли колонка языка: `--with-language` или `--language-report`.
+fn shows_language(args: &DecodeArgs) -> bool {
+    args.with_language || args.language_report
+}
+
+/// Язык строки для `--language-report`, когда она не декодировалась: словарь,
+/// в котором нашлось больше всего её слов, или `unknown`. Для индексов
+/// и пользовательского словаря язык задан аргументами.
+fn line_language(mnemonic: &str, args: &DecodeArgs) -> &'static str {
+    if args.wordlist.is_some() {
+        return "custom";
+    }
+    match args.input_format {
+        InputFormat::Words => detect_language(&prepared(mnemonic, args))
+            .map(language_name)
+            .unwrap_or("unknown"),
+        InputFormat::Indices => language_name(args.language.unwrap_or(Language::English)),
+    }
+}
+
+/// Загружает пользовательский словарь, завершая программу при ошибке.
+pub fn load_wordlist(path: &Path) -> Wordlist {
+    let content = match read_text(path) {
+        Ok(content) => content,
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при чтении словаря {:?}: {}",
+                    "Failed to read wordlist {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    };
+    match Wordlist::parse(&content) {
+        Ok(wordlist) => wordlist,
+        Err(e) => {
+            error!(
+                "{}",
+                tr!("Ошибка в словаре {:?}: {}", "Error in wordlist {:?}: {}", path, e.localized())
+            );
+            std::process::exit(EXIT_USAGE);
+        }
+    }
+}
+
+enum ProcessResult {
+    Success(Box<Record>),
+    /// Успешный результат уже записан в поток и не хранится; остаётся
+    /// только словарь для статистики.
+    Written { dictionary: &'static str },
+    Error {
+        message: String,
+        mnemonic: String,
+        kind: ErrorKind,
+    },
+}
+
+impl ProcessResult {
+    fn of(result: Result<Record, Failure>, mnemonic: &str) -> Self {
+        match result {
+            Ok(record) => ProcessResult::Success(Box::new(record)),
+            Err(failure) => ProcessResult::Error {
+                message: failure.message,
+                mnemonic: mnemonic.to_string(),
+                kind: failure.kind,
+            },
+        }
+    }
+}
+
+/// Строка JSONL: результат с номером строки входа (с единицы).
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entropy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    master_fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    xprv: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    xpub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    corrected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warnings: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    homoglyphs: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    also_valid: Option<Vec<&'static str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mnemonic: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+impl<'a> JsonRecord<'a> {
+    fn new(idx: usize, location: Option<Location>, result: &'a ProcessResult, args: &DecodeArgs) -> Self {
+        let file = location.map(|location| location.file.display().to_string());
+        let file_line = location.map(|location| location.line);
+        match result {
+            ProcessResult::Success(record) => JsonRecord {
+                line: idx + 1,
+                file,
+                file_line,
+                entropy: Some(entropy_text(&record.entropy, args)),
+                language: Some(dictionary_name(record)),
+                fingerprint: args.fingerprint.then(|| record.entropy.fingerprint()),
+                master_fingerprint: record.master_fingerprint.map(|fingerprint| fingerprint.to_string()),
+                xprv: record.root_keys.as_ref().map(|keys| keys.xprv.to_string()),
+                xpub: record.root_keys.as_ref().map(|keys| keys.xpub.to_string()),
+                corrected: (!record.corrections.is_empty())
+                    .then(|| corrections_text(&record.corrections)),
+                warnings: (!record.warnings.is_empty())
+                    .then(|| record.warnings.iter().map(|warning| warning.localized()).collect()),
+                homoglyphs: (!record.homoglyphs.is_empty())
+                    .then(|| confusables_text(&record.homoglyphs)),
+                also_valid: (!record.also_valid.is_empty())
+                    .then(|| record.also_valid.iter().map(|&language| language_name(language)).collect()),
+                mnemonic: None,
+                error: None,
+            },
+            ProcessResult::Written { .. } => {
+                unreachable!("записанный результат не сериализуется повторно")
+            }
+            ProcessResult::Error { message, mnemonic, .. } => JsonRecord {
+                line: idx + 1,
+                file,
+                file_line,
+                entropy: None,
+                language: args.language_report.then(|| line_language(mnemonic, args)),
+                fingerprint: None,
+                master_fingerprint: None,
+                xprv: None,
+                xpub: None,
+                corrected: None,
+                warnings: None,
+                homoglyphs: None,
+                also_valid: None,
+                mnemonic: Some(shown(mnemonic)),
+                error: Some(message),
+            },
+        }
+    }
+}
+
+/// Исходный файл и номер непустой строки в нём (с единицы);
+/// указывается только при нескольких входных файлах.
+#[derive(Clone, Copy)]
+struct Location<'p> {
+    file: &'p Path,
+    line: usize,
+}
+
+impl<'p> Location<'p> {
+    fn of(inputs: &'p [PathBuf], origin: Origin) -> Option<Self> {
+        (inputs.len() > 1).then(|| Location {
+            file: &inputs[origin.file],
+            line: origin.line + 1,
+        })
+    }
+}
+
+/// Вывод по готовности (`--unordered`): строки пишутся из рабочих потоков сразу,
+/// поэтому порядок может отличаться от входного (каждая строка несёт номер).
+struct StreamWriter {
+    out: Mutex<Box<dyn Write + Send>>,
+    delimiter: u8,
+    staged: Option<Staged>,
+}
+
+impl StreamWriter {
+    fn create(args: &DecodeArgs) -> Self {
+        let (out, staged): (Box<dyn Write + Send>, _) = match &args.output_file {
+            Some(path) => match open_output(path, args.append, args) {
+                Ok((file, staged)) => (Box::new(std::io::BufWriter::new(file)), staged),
+                Err(e) => {
+                    error!(
+                        "{}",
+                        tr!(
+                            "Ошибка при создании файла {:?}: {}",
+                            "Failed to create file {:?}: {}",
+                            path,
+                            e
+                        )
+                    );
+                    std::process::exit(EXIT_IO);
+                }
+            },
+            None => (Box::new(std::io::stdout()), None),
+        };
+        StreamWriter {
+            out: Mutex::new(out),
+            delimiter: delimiter(args),
+            staged,
+        }
+    }
+
+    /// Завершает поток и переносит временный файл на место результата.
+    fn finish(self) {
+        let StreamWriter { out, staged, .. } = self;
+        drop(out);
+        if let Some(staged) = staged {
+            staged.commit();
+        }
+    }
+
+    fn write_line(&self, line: &str) {
+        let mut out = self.out.lock().expect("мьютекс вывода не отравлен");
+        let result = out
+            .write_all(line.as_bytes())
+            .and_then(|_| out.write_all(&[self.delimiter]))
+            .and_then(|_| out.flush());
+        if let Err(e) = result {
+            error!(
+                "{}",
+                tr!("Ошибка при потоковой записи: {}", "Streaming write failed: {}", e)
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+
+    /// Пишет результат: JSONL — любой, текст — только успешный, с номером строки.
+    /// Успешные записи после этого не хранятся.
+    fn write(
+        &self,
+        idx: usize,
+        location: Option<Location>,
+        mnemonic: &str,
+        result: ProcessResult,
+        args: &DecodeArgs,
+    ) -> ProcessResult {
+        if args.format == OutputFormat::Jsonl {
+            let line = serde_json::to_string(&JsonRecord::new(idx, location, &result, args))
+                .expect("запись сериализуется в JSON");
+            self.write_line(&line);
+        } else if let ProcessResult::Success(record) = &result {
+            // Номер строки шаблон задаёт сам через {index}
+            let line = match args.template {
+                Some(_) => output_line(idx, location, mnemonic, record, args),
+                None => format!("{}\t{}", idx + 1, record_line(record, args)),
+            };
+            self.write_line(&line);
+        }
+
+        match result {
+            ProcessResult::Success(record) => ProcessResult::Written {
+                dictionary: dictionary_name(&record),
+            },
+            other => other,
+        }
+    }
+}
+
+/// Заголовок CSV; набор колонок совпадает с [`csv_row`].
+fn csv_header(args: &DecodeArgs) -> String {
+    let mut columns = vec!["line"];
+    if args.with_mnemonic {
+        columns.push("mnemonic");
+    }
+    columns.push("entropy");
+    if shows_language(args) {
+        columns.push("language");
+    }
+    if args.fingerprint {
+        columns.push("fingerprint");
+    }
+    if args.with_fingerprint {
+        columns.push("master_fingerprint");
+    }
+    if args.root_keys {
+        columns.extend(["xprv", "xpub"]);
+    }
+    columns.extend(["status", "error"]);
+    if args.check_entropy {
+        columns.push("warnings");
+    }
+    columns.join(",")
+}
+
+fn csv_row(idx: usize, mnemonic: &str, result: &ProcessResult, args: &DecodeArgs) -> String {
+    let mut columns = vec![(idx + 1).to_string()];
+    if args.with_mnemonic {
+        columns.push(shown(mnemonic).into_owned());
+    }
+    match result {
+        ProcessResult::Success(record) => {
+            columns.push(entropy_text(&record.entropy, args));
+            if shows_language(args) {
+                columns.push(dictionary_name(record).to_string());
+            }
+            if args.fingerprint {
+                columns.push(record.entropy.fingerprint());
+            }
+            if args.with_fingerprint {
+                columns.push(record.master_fingerprint.map(|f| f.to_string()).unwrap_or_default());
+            }
+            if args.root_keys {
+                let keys = record.root_keys.as_ref();
+                columns.push(keys.map(|k| k.xprv.to_string()).unwrap_or_default());
+                columns.push(keys.map(|k| k.xpub.to_string()).unwrap_or_default());
+            }
+            columns.extend(["ok".to_string(), String::new()]);
+            if args.check_entropy {
+                columns.push(warnings_text(&record.warnings));
+            }
+        }
+        ProcessResult::Error { message, .. } => {
+            columns.push(String::new());
+            if shows_language(args) {
+                let language = if args.language_report { line_language(mnemonic, args) } else { "" };
+                columns.push(language.to_string());
+            }
+            if args.fingerprint {
+                columns.push(String::new());
+            }
+            if args.with_fingerprint {
+                columns.push(String::new());
+            }
+            if args.root_keys {
+                columns.extend([String::new(), String::new()]);
+            }
+            columns.extend(["error".to_string(), message.clone()]);
+            if args.check_entropy {
+                columns.push(String::new());
+            }
+        }
+        ProcessResult::Written { .. } => unreachable!("CSV не пишется потоком"),
+    }
+    columns.iter().map(|c| csv_escape(c)).collect::<Vec<_>>().join(",")
+}
+
+/// Экранирование поля CSV по RFC 4180.
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Запись формата raw: байты энтропии с префиксом длины или дополнением
+/// до фиксированной длины.
+fn raw_record(entropy: &[u8], args: &DecodeArgs) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(entropy.len() + 1);
+    if args.length_prefix {
+        bytes.push(entropy.len() as u8);
+    }
+    bytes.extend_from_slice(entropy);
+    if let Some(length) = args.record_length {
+        if entropy.len() > length {
+            return Err(tr!(
+                "Энтропия длиннее записи: {} байт при --record-length {}",
+                "Entropy longer than the record: {} bytes with --record-length {}",
+                entropy.len(),
+                length
+            ));
+        }
+        bytes.resize(length, 0);
+    }
+    Ok(bytes)
+}
+
+/// Разделитель записей входа и вывода.
+fn delimiter(args: &DecodeArgs) -> u8 {
+    if args.null {
+        b'\0'
+    } else {
+        b'\n'
+    }
+}
+
+/// Мнемоники читаются потоком из перенаправленного stdin; результаты
+/// тогда пишутся в stdout по строке, как в файл.
+fn reads_stdin_stream(args: &DecodeArgs) -> bool {
+    args.input_file.is_empty()
+        && args.mnemonic.is_none()
+        && !reads_image(args)
+        && !args.repl
+        && stdin_piped()
+}
+
+/// Результаты пишутся в базу данных, поэтому в консоль не выводятся.
+#[cfg_attr(not(feature = "database"), allow(unused_variables))]
+fn writes_database(args: &DecodeArgs) -> bool {
+    #[cfg(feature = "database")]
+    if database::requested(args) {
+        return true;
+    }
+    false
+}
+
+/// Входной файл: зашифрованный (`--decrypt-input`) расшифровывается на лету.
+#[cfg(feature = "encryption")]
+fn input_reader(path: &Path, args: &DecodeArgs) -> Box<dyn std::io::BufRead + Send> {
+    if args.decrypt_input {
+        return encryption::decrypted_input(path, &args.identity);
+    }
+    open_reader(path)
+}
+
+#[cfg(not(feature = "encryption"))]
+fn input_reader(path: &Path, _args: &DecodeArgs) -> Box<dyn std::io::BufRead + Send> {
+    open_reader(path)
+}
+
+/// Входные файлы зашифрованы (`--decrypt-input`).
+#[cfg(feature = "encryption")]
+fn decrypts_input(args: &DecodeArgs) -> bool {
+    args.decrypt_input
+}
+
+#[cfg(not(feature = "encryption"))]
+fn decrypts_input(_args: &DecodeArgs) -> bool {
+    false
+}
+
+/// Файлы вывода шифруются (`--encrypt-output`).
+#[cfg(feature = "encryption")]
+pub fn encrypts_output(args: &DecodeArgs) -> bool {
+    args.encrypt_output
+}
+
+#[cfg(not(feature = "encryption"))]
+pub fn encrypts_output(_args: &DecodeArgs) -> bool {
+    false
+}
+
+/// Фразы читаются из QR-кода на изображении (`--input-image`).
+#[cfg(feature = "qr")]
+fn reads_image(args: &DecodeArgs) -> bool {
+    args.input_image.is_some()
+}
+
+#[cfg(not(feature = "qr"))]
+fn reads_image(_args: &DecodeArgs) -> bool {
+    false
+}
+
+/// Служебное сообщение: в stderr, если stdout занят потоком данных;
+/// с `--quiet` не выводится.
+fn notice(args: &DecodeArgs, message: &str) {
+    if quiet() {
+        return;
+    }
+    let stdout_is_data = !args.format.is_text() || args.unordered || reads_stdin_stream(args);
+    suspended(|| {
+        if stdout_is_data && args.output_file.is_none() {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    });
+}
+
+/// `-vv` (уровень trace): итог строки в журнале — номер, место во входе и язык или ошибка;
+/// сама фраза не выводится.
+fn line_detail(idx: usize, location: Option<Location>, result: &ProcessResult) {
+    let place = match location {
+        Some(location) => format!(" ({}:{})", location.file.display(), location.line),
+        None => String::new(),
+    };
+    let status = match result {
+        ProcessResult::Success(record) => {
+            tr!("ок, язык {}", "ok, language {}", dictionary_name(record))
+        }
+        ProcessResult::Written { dictionary } => tr!("ок, язык {}", "ok, language {}", dictionary),
+        ProcessResult::Error { message, .. } => tr!("ошибка: {}", "error: {}", message),
+    };
+    trace!("{}", tr!("· Строка {}{}: {}", "· Line {}{}: {}", idx + 1, place, status));
+}
+
+/// Этап записи: получает результаты в порядке входа и сразу выводит их.
+struct Sink<'s> {
+    args: &'s DecodeArgs,
+    options: &'s Options,
+    console: bool,
+    /// Файл или stdout для форматов, которые пишутся здесь, а не потоком.
+    output: Option<Box<dyn Write + Send>>,
+    error_log: Option<Box<dyn Write + Send>>,
+    stats: Stats,
+    /// Энтропия успешных строк для `--report-duplicates`.
+    duplicates: Option<Duplicates>,
+    checkpoint: Option<Checkpoint>,
+    /// Файлы дописываются, а не создаются заново (`--append` или продолжение
+    /// прерванного запуска).
+    append: bool,
+    /// Временные файлы, которые станут результатами после завершения.
+    staged: Vec<Staged>,
+    #[cfg(feature = "database")]
+    database: Option<DatabaseSink>,
+}
+
+/// Открывает файл вывода с учётом `--compress-output`. При дозаписи пишет
+/// прямо в файл; иначе — во временный файл, который переименовывается
+/// в `path` по завершении. С `--checkpoint` запись тоже идёт прямо в файл:
+/// продолжение после сбоя дописывает именно его.
+fn open_output(
+    path: &Path,
+    append: bool,
+    args: &DecodeArgs,
+) -> std::io::Result<(Box<dyn Write + Send>, Option<Staged>)> {
+    let (file, staged) = if append {
+        (fs::OpenOptions::new().create(true).append(true).open(path)?, None)
+    } else if args.checkpoint.is_some() {
+        (fs::File::create(path)?, None)
+    } else {
+        let (file, staged) = Staged::create(path)?;
+        (file, Some(staged))
+    };
+    #[cfg(feature = "encryption")]
+    if args.encrypt_output {
+        let file = encryption::encrypted(file, &args.recipient)?;
+        return Ok((compressed(file, args.compress_output), staged));
+    }
+    Ok((compressed(file, args.compress_output), staged))
+}
+
+impl<'s> Sink<'s> {
+    fn new(
+        args: &'s DecodeArgs,
+        options: &'s Options,
+        streaming: bool,
+        checkpoint: Option<Checkpoint>,
+        append: bool,
+    ) -> Self {
+        // С --quiet текстовые результаты идут в stdout строками, без блоков
+        let console = args.output_file.is_none()
+            && !quiet()
+            && args.template.is_none()
+            && !writes_database(args)
+            && args.format.is_text()
+            && !streaming
+            && !reads_stdin_stream(args);
+        // Заголовок CSV нужен, только если файл начинается заново
+        let fresh = !append
+            || args
+                .output_file
+                .as_deref()
+                .is_none_or(|path| fs::metadata(path).map_or(true, |metadata| metadata.len() == 0));
+        let mut staged = Vec::new();
+        let output: Option<Box<dyn Write + Send>> = if streaming {
+            None
+        } else if let Some(output_path) = &args.output_file {
+            match open_output(output_path, append, args) {
+                Ok((file, file_staged)) => {
+                    staged.extend(file_staged);
+                    Some(Box::new(std::io::BufWriter::new(file)))
+                }
+                Err(e) => {
+                    error!(
+                        "{}",
+                        tr!(
+                            "Ошибка при создании файла {:?}: {}",
+                            "Failed to create file {:?}: {}",
+                            output_path,
+                            e
+                        )
+                    );
+                    std::process::exit(EXIT_IO);
+                }
+            }
+        } else if writes_database(args) {
+            None
+        } else if matches!(args.format, OutputFormat::Csv | OutputFormat::Raw) || !console {
+            Some(Box::new(std::io::BufWriter::new(std::io::stdout())))
+        } else {
+            None
+        };
+
+        let mut sink = Sink {
+            args,
+            options,
+            console,
+            output,
+            error_log: None,
+            stats: Stats::default(),
+            duplicates: args.report_duplicates.is_some().then(Duplicates::default),
+            checkpoint,
+            append,
+            staged,
+            #[cfg(feature = "database")]
+            database: DatabaseSink::open(args),
+        };
+        if args.format == OutputFormat::Csv && fresh {
+            sink.write_record(&csv_header(args));
+        }
+        sink
+    }
+
+    fn write_output(&mut self, bytes: &[u8]) {
+        if let Some(out) = &mut self.output {
+            if let Err(e) = out.write_all(bytes) {
+                error!(
+                    "{}",
+                    tr!("Ошибка при записи результатов: {}", "Failed to write results: {}", e)
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    }
+
+    /// Пишет текстовую запись с разделителем.
+    fn write_record(&mut self, record: &str) {
+        self.write_output(record.as_bytes());
+        self.write_output(&[delimiter(self.args)]);
+    }
+
+    fn consume(&mut self, idx: usize, location: Option<Location>, mnemonic: &str, result: ProcessResult) {
+        let args = self.args;
+        let result = match result {
+            ProcessResult::Success(record) if args.format == OutputFormat::Raw => {
+                match raw_record(record.entropy.as_bytes(), args) {
+                    Ok(bytes) => {
+                        self.write_output(&bytes);
+                        ProcessResult::Success(record)
+                    }
+                    Err(message) => ProcessResult::Error {
+                        message,
+                        mnemonic: mnemonic.to_string(),
+                        kind: ErrorKind::Output,
+                    },
+                }
+            }
+            other => other,
+        };
+        if log::log_enabled!(log::Level::Trace) {
+            line_detail(idx, location, &result);
+        }
+        if args.format == OutputFormat::Csv {
+            let row = csv_row(idx, mnemonic, &result, args);
+            self.write_record(&row);
+        }
+        // JSONL читают потоковые обработчики, поэтому запись сразу уходит дальше;
+        // с --unordered её уже записал StreamWriter
+        if args.format == OutputFormat::Jsonl && !args.unordered {
+            let line = serde_json::to_string(&JsonRecord::new(idx, location, &result, args))
+                .expect("запись сериализуется в JSON");
+            self.write_record(&line);
+            self.flush_output();
+        }
+        #[cfg(feature = "database")]
+        if let Some(database) = &mut self.database {
+            match &result {
+                ProcessResult::Success(record) => {
+                    database.insert(mnemonic, Some(entropy_text(&record.entropy, args)), None)
+                }
+                ProcessResult::Error { message, .. } => database.insert(mnemonic, None, Some(message)),
+                ProcessResult::Written { .. } => unreachable!("база SQLite не заполняется потоком"),
+            }
+        }
+
+        let words = word_count(mnemonic, args);
+        if let (Some(duplicates), ProcessResult::Success(record)) = (&mut self.duplicates, &result) {
+            let place = match location {
+                Some(location) => format!("{}:{}", location.file.display(), location.line),
+                None => (idx + 1).to_string(),
+            };
+            duplicates.add(record.entropy.as_bytes(), place, dictionary_name(record));
+        }
+        match result {
+            ProcessResult::Success(record) => {
+                if self.console {
+                    println!("{}", tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1));
+                    if args.show_input || redacting() {
+                        println!(
+                            "{}",
+                            tr!("Мнемоническая фраза: {}", "Mnemonic: {}", shown(mnemonic))
+                        );
+                    }
+                    if !record.corrections.is_empty() {
+                        println!(
+                            "{}",
+                            tr!(
+                                "Исправлено: {}",
+                                "Corrected: {}",
+                                corrections_text(&record.corrections)
+                            )
+                        );
+                    }
+                    if !record.homoglyphs.is_empty() {
+                        println!(
+                            "{}",
+                            tr!(
+                                "Заменены буквы-двойники: {}",
+                                "Lookalike letters replaced: {}",
+                                confusables_text(&record.homoglyphs)
+                            )
+                        );
+                    }
+                    println!("{}", tr!("Язык: {}", "Language: {}", dictionary_name(&record)));
+                    println!("{}: {}", value_label(args), record_value(&record, args));
+                    if args.fingerprint {
+                        println!("{}", tr!("Отпечаток: {}", "Fingerprint: {}", record.entropy.fingerprint()));
+                    }
+                    if let Some(fingerprint) = record.master_fingerprint {
+                        println!(
+                            "{}",
+                            tr!(
+                                "Отпечаток мастер-ключа: {}",
+                                "Master fingerprint: {}",
+                                fingerprint
+                            )
+                        );
+                    }
+                    if let Some(keys) = &record.root_keys {
+                        println!("xprv: {}", keys.xprv);
+                        println!("xpub: {}", keys.xpub);
+                    }
+                    for warning in &record.warnings {
+                        println!("⚠️  {}", warning.localized());
+                    }
+                    if !record.also_valid.is_empty() {
+                        println!(
+                            "{}",
+                            tr!(
+                                "⚠️  Фраза верна и в других словарях (с другой энтропией): {}",
+                                "⚠️  The phrase is also valid in other wordlists (with a different entropy): {}",
+                                languages_text(&record.also_valid)
+                            )
+                        );
+                    }
+                }
+                if args.format.is_text() {
+                    let line = output_line(idx, location, mnemonic, &record, args);
+                    self.write_record(&line);
+                }
+                self.stats.success(words, dictionary_name(&record));
+                if args.language_report {
+                    self.stats.detected(dictionary_name(&record));
+                }
+            }
+            ProcessResult::Written { dictionary } => {
+                self.stats.success(words, dictionary);
+                if args.language_report {
+                    self.stats.detected(dictionary);
+                }
+            }
+            ProcessResult::Error {
+                message,
+                mnemonic,
+                kind,
+            } => {
+                if self.console {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    if let Some(location) = location {
+                        eprintln!(
+                            "{}",
+                            tr!(
+                                "Файл: {}:{}",
+                                "File: {}:{}",
+                                location.file.display(),
+                                location.line
+                            )
+                        );
+                    }
+                    if args.show_input || redacting() {
+                        eprintln!(
+                            "{}",
+                            tr!("Мнемоническая фраза: {}", "Mnemonic: {}", shown(&mnemonic))
+                        );
+                    }
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                if args.format.is_text() {
+                    match args.keep_lines {
+                        Some(Placeholder::Empty) => self.write_record(""),
+                        Some(Placeholder::Error) => {
+                            self.write_record(&format!("ERROR:{}", kind.name()))
+                        }
+                        None => {}
+                    }
+                }
+                self.log_error(location, &mnemonic, &message);
+                self.stats.failure(words, kind.name());
+                if args.language_report {
+                    self.stats.detected(line_language(&mnemonic, args));
+                }
+            }
+        }
+
+        if let Some(report) = sanitize_report(idx, mnemonic, args) {
+            notice(args, &report);
+        }
+        if args.diagnose {
+            for line in diagnosis_report(idx, mnemonic, args, self.options) {
+                notice(args, &line);
+            }
+        }
+
+        if (idx + 1).is_multiple_of(CHECKPOINT_INTERVAL) {
+            self.save_checkpoint(idx + 1);
+        }
+    }
+
+    /// Сбрасывает вывод на диск и отмечает, сколько строк входа записано.
+    fn save_checkpoint(&mut self, processed: usize) {
+        if self.checkpoint.is_none() {
+            return;
+        }
+        self.flush();
+        if let Some(checkpoint) = &self.checkpoint {
+            checkpoint.save(processed);
+        }
+    }
+
+    fn flush_output(&mut self) {
+        if let Some(out) = &mut self.output {
+            if let Err(e) = out.flush() {
+                error!(
+                    "{}",
+                    tr!("Ошибка при записи результатов: {}", "Failed to write results: {}", e)
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        self.flush_output();
+        if let Some(log) = &mut self.error_log {
+            if let Err(e) = log.flush() {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при записи в лог ошибок: {}",
+                        "Failed to write the error log: {}",
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+        #[cfg(feature = "database")]
+        if let Some(database) = &mut self.database {
+            database.commit();
+        }
+    }
+
+    /// Пишет ошибку в лог; файл создаётся при первой ошибке.
+    fn log_error(&mut self, location: Option<Location>, mnemonic: &str, message: &str) {
+        let Some(error_log_path) = &self.args.error_log else {
+            return;
+        };
+        if self.error_log.is_none() {
+            match open_output(error_log_path, self.append, self.args) {
+                Ok((file, staged)) => {
+                    self.error_log = Some(file);
+                    self.staged.extend(staged);
+                }
+                Err(e) => {
+                    error!(
+                        "{}",
+                        tr!(
+                            "Ошибка при создании файла лога {:?}: {}",
+                            "Failed to create log file {:?}: {}",
+                            error_log_path,
+                            e
+                        )
+                    );
+                    std::process::exit(EXIT_IO);
+                }
+            }
+        }
+
+        let suggestions = self.args.suggest.and_then(|limit| suggestions_text(mnemonic, self.args, self.options, limit));
+        let mnemonic = shown(mnemonic);
+        let mut line = if self.args.verbose_errors || self.args.suggest.is_some() {
+            match location {
+                Some(location) => format!(
+                    "{} | {}:{} | {}",
+                    mnemonic,
+                    location.file.display(),
+                    location.line,
+                    message
+                ),
+                None => format!("{} | {}", mnemonic, message),
+            }
+        } else {
+            mnemonic.to_string()
+        };
+        if let Some(suggestions) = suggestions {
+            line.push_str(" | ");
+            line.push_str(&suggestions);
+        }
+        if let Some(file) = &mut self.error_log {
+            let delimiter = [delimiter(self.args)];
+            if let Err(e) = file.write_all(line.as_bytes()).and_then(|_| file.write_all(&delimiter)) {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при записи в лог ошибок {:?}: {}",
+                        "Failed to write the error log {:?}: {}",
+                        error_log_path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    }
+
+    /// Записывает отчёт `--report-duplicates`; возвращает число групп.
+    fn write_duplicates(&self) -> Option<usize> {
+        let path = self.args.report_duplicates.as_deref()?;
+        let duplicates = self.duplicates.as_ref()?;
+        Some(duplicates.write(path, |entropy| {
+            entropy_text(&Entropy::from(entropy.to_vec()), self.args)
+        }))
+    }
+
+    /// Завершает запись; `processed` — общее число строк входа.
+    fn finish(mut self, processed: usize) -> Stats {
+        self.flush();
+        self.save_checkpoint(processed);
+        // Сжатые потоки завершаются при освобождении — до переименования
+        self.output = None;
+        self.error_log = None;
+        for staged in self.staged.drain(..) {
+            staged.commit();
+        }
+        self.stats
+    }
+}
+
+fn decode_options(args: &DecodeArgs) -> Options {
+    Options {
+        ignore_checksum: args.ignore_checksum,
+        language: args.language,
+        wordlist: args.wordlist.as_ref().map(|path| Arc::new(load_wordlist(path))),
+    }
+}
+
+/// `--qr`: одна фраза из `-m`, `--input-image` или ввода, результат — QR-кодом.
+#[cfg(feature = "qr")]
+fn show_qr(args: &DecodeArgs, content: QrContent) -> i32 {
+    let lines = match &args.input_image {
+        Some(path) => qr::read_image(path),
+        None => load_lines(
+            None,
+            args.mnemonic.as_deref(),
+            &tr!("Введите мнемоническую фразу:", "Enter the mnemonic phrase:"),
+            false,
+            args.show_input,
+        ),
+    };
+    let [mnemonic_str] = lines.as_slice() else {
+        error!(
+            "{}",
+            tr!(
+                "--qr работает с одной мнемоникой, получено строк: {}",
+                "--qr works with a single mnemonic, lines given: {}",
+                lines.len()
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    };
+    match process_mnemonic(mnemonic_str, args, &decode_options(args)) {
+        Ok(record) => {
+            qr::show(&args.qr, content, record.entropy.as_bytes(), record.language);
+            0
+        }
+        Err(failure) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", failure.message));
+            EXIT_FAILED
+        }
+    }
+}
+
+/// Пословная диагностика показывает слова фразы и несовместима с `--redact`.
+fn reject_redacted_diagnosis(args: &DecodeArgs) {
+    if args.diagnose && redacting() {
+        error!(
+            "{}",
+            tr!(
+                "--diagnose выводит слова фразы и несовместим с --redact",
+                "--diagnose prints the words of the phrase and cannot be used with --redact"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    if args.suggest.is_some() && redacting() {
+        error!(
+            "{}",
+            tr!(
+                "--suggest выводит слова фразы и несовместим с --redact",
+                "--suggest prints the words of the phrase and cannot be used with --redact"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+}
+
+/// Показывать ли индикатор прогресса: stderr — терминал, и результаты
+/// не печатаются строками в тот же терминал (консольные блоки `console`
+/// выводятся, убрав индикатор).
+fn shows_progress(args: &DecodeArgs, console: bool) -> bool {
+    let terminal_lines = !console
+        && args.output_file.is_none()
+        && !writes_database(args)
+        && std::io::stdout().is_terminal();
+    !args.no_progress && !quiet() && std::io::stderr().is_terminal() && !terminal_lines
+}
+
+/// Доля строк для `--sample`: от 0 (не включая) до 1.
+fn parse_rate(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(rate) if rate > 0.0 && rate <= 1.0 => Ok(rate),
+        _ => Err(tr!(
+            "доля должна быть числом больше 0 и не больше 1",
+            "the rate must be a number greater than 0 and at most 1"
+        )),
+    }
+}
+
+/// Число слов для `--filter-words`: одно из допустимых для BIP39.
+fn parse_word_count(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(count) if VALID_WORD_COUNTS.contains(&count) => Ok(count),
+        _ => Err(tr!(
+            "число слов должно быть одним из {:?}",
+            "the word count must be one of {:?}",
+            VALID_WORD_COUNTS
+        )),
+    }
+}
+
+/// Отбор строк до обработки: `--take`, `--sample`, `--dedupe`,
+/// `--filter-words` и запись пропущенных строк в `--filtered-out`
+/// (дописывается при `append`).
+fn selection(args: &DecodeArgs, append: bool) -> Selection<'_> {
+    let keep = (!args.filter_words.is_empty()).then(|| {
+        Box::new(move |line: &str| args.filter_words.contains(&word_count(line, args)))
+            as Keep
+    });
+    let rejected = args.filtered_out.as_deref().map(|path| {
+        let file = if append {
+            fs::OpenOptions::new().create(true).append(true).open(path)
+        } else {
+            fs::File::create(path)
+        };
+        let mut out = match file {
+            Ok(file) => std::io::BufWriter::new(file),
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при создании файла {:?}: {}",
+                        "Failed to create file {:?}: {}",
+                        path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        };
+        Box::new(move |line: &str| {
+            if let Err(e) = writeln!(out, "{}", shown(line)) {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при записи пропущенных строк {:?}: {}",
+                        "Failed to write skipped lines {:?}: {}",
+                        path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }) as Rejected
+    });
+    Selection {
+        end: args.take.map(|take| args.skip + take),
+        sample: args.sample.map(|rate| Sample::new(rate, args.seed)),
+        dedupe: args.dedupe,
+        keep,
+        rejected,
+    }
+}
+
+/// `--repl`: фразы с клавиатуры по одной, пока не введён `exit` или не закрыт
+/// ввод. Строки читаются без библиотеки редактирования строк, поэтому история
+/// ввода нигде не сохраняется.
+pub fn repl(args: &DecodeArgs) {
+    reject_redacted_diagnosis(args);
+    if quiet() {
+        error!(
+            "{}",
+            tr!(
+                "--repl выводит результаты блоками и несовместим с --quiet",
+                "--repl prints results as blocks and cannot be used with --quiet"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    let options = decode_options(args);
+    let mut sink = Sink::new(args, &options, false, None, false);
+    if !sink.console {
+        error!(
+            "{}",
+            tr!(
+                "--repl выводит результаты только в консоль: форматы text, indices или bits, без записи в базу",
+                "--repl prints results to the console only: formats text, indices or bits, no database"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    println!(
+        "{}",
+        tr!(
+            "Вводите мнемонические фразы по одной; exit или Ctrl+D — выход.",
+            "Enter mnemonic phrases one at a time; exit or Ctrl+D to quit."
+        )
+    );
+
+    let mut idx = 0;
+    loop {
+        print!("> ");
+        let _ = std::io::stdout().flush();
+        let Some(line) = read_interactive(args.show_input) else {
+            break;
+        };
+        let mnemonic_str = line.trim();
+        match mnemonic_str {
+            "" => continue,
+            "exit" | "quit" => break,
+            _ => {}
+        }
+
+        let result = ProcessResult::of(process_mnemonic(mnemonic_str, args, &options), mnemonic_str);
+        let failed = matches!(result, ProcessResult::Error { .. });
+        sink.consume(idx, None, mnemonic_str, result);
+        // С --diagnose отчёт уже выведен для каждой фразы; с --redact слова не показываются
+        if failed && !args.diagnose && !redacting() {
+            for line in diagnosis_report(idx, mnemonic_str, args, &options) {
+                notice(args, &line);
+            }
+        }
+        idx += 1;
+    }
+
+    let stats = sink.finish(idx);
+    println!(
+        "{}",
+        tr!(
+            "\nОбработано фраз: {}, из них с ошибкой: {}",
+            "\nPhrases processed: {}, failed: {}",
+            stats.total,
+            stats.failed
+        )
+    );
+}
+
+pub fn run(args: &DecodeArgs) {
+    let code = convert(args);
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
+
+/// Итог обработки: куда сохранены результаты и сколько фраз обработано.
+fn print_summary(args: &DecodeArgs, success_count: usize, error_count: usize) {
+    if let Some(output_path) = &args.output_file {
+        println!(
+            "{}",
+            tr!("✓ Результаты сохранены в файл: {:?}", "✓ Results saved to file: {:?}", output_path)
+        );
+        println!(
+            "{}",
+            tr!(
+                "  Обработано успешно: {} мнемоник",
+                "  Decoded successfully: {} mnemonics",
+                success_count
+            )
+        );
+        if error_count > 0 {
+            println!("{}", tr!("  Ошибок: {}", "  Errors: {}", error_count));
+        }
+    } else if writes_database(args) {
+        println!(
+            "{}",
+            tr!("✓ Результаты сохранены в базу данных", "✓ Results saved to the database")
+        );
+        println!(
+            "{}",
+            tr!(
+                "  Обработано успешно: {} мнемоник",
+                "  Decoded successfully: {} mnemonics",
+                success_count
+            )
+        );
+        if error_count > 0 {
+            println!("{}", tr!("  Ошибок: {}", "  Errors: {}", error_count));
+        }
+    } else if reads_stdin_stream(args) {
+        info!(
+            "{}",
+            tr!(
+                "Обработано успешно: {} мнемоник",
+                "Decoded successfully: {} mnemonics",
+                success_count
+            )
+        );
+        if error_count > 0 {
+            info!("{}", tr!("Ошибок: {}", "Errors: {}", error_count));
+        }
+    }
+}
+
+/// Обрабатывает вход согласно `args`. Возвращает код завершения по числу
+/// ошибок ([`batch_exit_code`]); с `--skip-invalid` — всегда 0.
+pub fn convert(args: &DecodeArgs) -> i32 {
+    reject_redacted_diagnosis(args);
+    if args.unordered && matches!(args.format, OutputFormat::Csv | OutputFormat::Raw) {
+        error!(
+            "{}",
+            tr!(
+                "--unordered поддерживается только для форматов text и jsonl",
+                "--unordered is supported only for the text and jsonl formats"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    if args.keep_lines.is_some() && !args.format.is_text() {
+        error!(
+            "{}",
+            tr!(
+                "--keep-lines поддерживается только для форматов text, indices и bits",
+                "--keep-lines is supported only for the text, indices and bits formats"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    if args.checkpoint.is_some() && args.unordered {
+        error!(
+            "{}",
+            tr!(
+                "--checkpoint несовместим с выводом по готовности (--unordered)",
+                "--checkpoint cannot be used with as-ready output (--unordered)"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    if writes_database(args) && args.format == OutputFormat::Jsonl {
+        error!(
+            "{}",
+            tr!(
+                "Запись в базу данных несовместима с потоковым выводом (--format jsonl)",
+                "Database output cannot be used with streaming output (--format jsonl)"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    #[cfg(feature = "encryption")]
+    if args.encrypt_output && args.output_file.is_none() && args.error_log.is_none() {
+        error!(
+            "{}",
+            tr!(
+                "--encrypt-output шифрует файлы --output и --error-log; не задан ни один",
+                "--encrypt-output encrypts the --output and --error-log files; neither is given"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    #[cfg(feature = "qr")]
+    if let Some(content) = args.qr.qr {
+        return show_qr(args, content);
+    }
+
+    let inputs = expand_inputs(&args.input_file);
+    #[cfg(feature = "encryption")]
+    if args.decrypt_input && inputs.is_empty() {
+        error!(
+            "{}",
+            tr!(
+                "--decrypt-input расшифровывает файлы --input; входные файлы не заданы",
+                "--decrypt-input decrypts the --input files; no input files given"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    if args.mmap && inputs.len() > 1 {
+        error!(
+            "{}",
+            tr!(
+                "--mmap поддерживается только для одного входного файла",
+                "--mmap is supported only for a single input file"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+
+    let checkpoint = args
+        .checkpoint
+        .as_deref()
+        .map(|path| Checkpoint::new(path, &inputs));
+    let resumed = checkpoint.as_ref().map_or(0, Checkpoint::load);
+    if resumed > 0 && !quiet() {
+        println!(
+            "{}",
+            tr!(
+                "↻ Продолжение с контрольной точки: пропущено строк {}",
+                "↻ Resuming from checkpoint: {} lines skipped",
+                resumed
+            )
+        );
+    }
+    // Контрольная точка хранит сквозной индекс, поэтому с --skip берётся дальний
+    let skip = resumed.max(args.skip);
+    let append = resumed > 0 || args.append;
+
+    // Отображённый файл живёт до конца обработки: строки ссылаются на него
+    let mapped;
+    let mut compressed = false;
+    let source = match (inputs.first(), args.mmap) {
+        (Some(path), true) => {
+            mapped = map_file(path);
+            let detected = match args.compression {
+                Compression::Auto => Compression::detect(&mapped),
+                other => other,
+            };
+            if detected != Compression::Plain {
+                error!(
+                    "{}",
+                    tr!(
+                        "--mmap несовместим со сжатым входом",
+                        "--mmap cannot be used with compressed input"
+                    )
+                );
+                std::process::exit(EXIT_USAGE);
+            }
+            Source::Mapped {
+                content: mapped_text(&mapped, path, delimiter(args), args.output_file.is_some()),
+                delimiter: delimiter(args),
+            }
+        }
+        (Some(_), false) => {
+            let readers = inputs
+                .iter()
+                .map(|path| {
+                    let (reader, applied) = decompress(input_reader(path, args), args.compression);
+                    compressed |= applied != Compression::Plain;
+                    text_reader(reader, &format!("{:?}", path))
+                })
+                .collect();
+            Source::Readers {
+                readers,
+                delimiter: delimiter(args),
+            }
+        }
+        #[cfg(feature = "qr")]
+        (None, _) if args.input_image.is_some() => {
+            let path = args.input_image.as_deref().expect("проверено в условии");
+            Source::Lines(qr::read_image(path).into_iter().map(Cow::Owned).collect())
+        }
+        (None, _) if reads_stdin_stream(args) => {
+            let (reader, _) = decompress(stdin_reader(), args.compression);
+            Source::reader(text_reader(reader, "stdin"), delimiter(args))
+        }
+        (None, _) => {
+            let lines = load_lines(
+                None,
+                args.mnemonic.as_deref(),
+                &tr!("Введите мнемоническую фразу:", "Enter the mnemonic phrase:"),
+                args.output_file.is_some(),
+                args.show_input,
+            );
+            Source::Lines(lines.into_iter().map(Cow::Owned).collect())
+        }
+    };
+
+    let stream = args.unordered.then(|| StreamWriter::create(args));
+    let options = decode_options(args);
+
+    // Прогресс считается по байтам входа; для stdin, сжатого и зашифрованного
+    // файла размер неизвестен, и индикатор считает строки
+    let input_size = (!inputs.is_empty() && !compressed && !decrypts_input(args))
+        .then(|| {
+            inputs
+                .iter()
+                .map(|path| fs::metadata(path).map(|metadata| metadata.len()))
+                .sum::<std::io::Result<u64>>()
+                .ok()
+        })
+        .flatten();
+    let mut sink = Sink::new(args, &options, stream.is_some(), checkpoint, append);
+    let console = sink.console;
+    let progress = shows_progress(args, console).then(|| Progress::start(input_size));
+    let started = Instant::now();
+    let mut processed = skip;
+
+    // Параллельная обработка блоками: чтение, обработка и запись идут одновременно
+    let dropped = pipeline::run(
+        source,
+        skip,
+        selection(args, append),
+        |idx, origin, mnemonic_str| {
+            let location = Location::of(&inputs, origin);
+            let mut result =
+                ProcessResult::of(process_mnemonic(mnemonic_str, args, &options), mnemonic_str);
+
+            // В консольном режиме исправления и предупреждения видны в блоке результата
+            if let ProcessResult::Success(record) = &result {
+                if !record.corrections.is_empty() && !console {
+                    notice(
+                        args,
+                        &tr!(
+                            "✎ Строка {}: {}",
+                            "✎ Line {}: {}",
+                            idx + 1,
+                            corrections_text(&record.corrections)
+                        ),
+                    );
+                }
+                if !record.homoglyphs.is_empty() && !console {
+                    notice(
+                        args,
+                        &tr!(
+                            "✎ Строка {}: заменены буквы-двойники: {}",
+                            "✎ Line {}: lookalike letters replaced: {}",
+                            idx + 1,
+                            confusables_text(&record.homoglyphs)
+                        ),
+                    );
+                }
+                if !record.warnings.is_empty() && !console {
+                    notice(
+                        args,
+                        &tr!(
+                            "⚠️  Строка {}: {}",
+                            "⚠️  Line {}: {}",
+                            idx + 1,
+                            warnings_text(&record.warnings)
+                        ),
+                    );
+                }
+                if !record.also_valid.is_empty() && !console {
+                    notice(
+                        args,
+                        &tr!(
+                            "⚠️  Строка {}: фраза верна и в других словарях (с другой энтропией): {}",
+                            "⚠️  Line {}: the phrase is also valid in other wordlists (with a different entropy): {}",
+                            idx + 1,
+                            languages_text(&record.also_valid)
+                        ),
+                    );
+                }
+            }
+
+            if let Some(writer) = &stream {
+                result = writer.write(idx, location, mnemonic_str, result, args);
+            }
+
+            if let Some(progress) = &progress {
+                progress.advance(mnemonic_str, !matches!(result, ProcessResult::Error { .. }));
+            }
+
+            result
+        },
+        |idx, origin, mnemonic, result| {
+            let location = Location::of(&inputs, origin);
+            // Блоки результатов печатаются в терминал — индикатор на это время убирается
+            if console {
+                suspended(|| sink.consume(idx, location, &mnemonic, result));
+            } else {
+                sink.consume(idx, location, &mnemonic, result);
+            }
+            processed = idx + 1;
+        },
+    );
+
+    if let Some(progress) = progress {
+        progress.finish();
+    }
+    // Сжатый поток завершается при освобождении писателя — до возможного выхода
+    if let Some(stream) = stream {
+        stream.finish();
+    }
+
+    let duplicate_groups = sink.write_duplicates();
+    let mut stats = sink.finish(processed);
+    stats.duplicates = dropped.duplicates;
+    stats.filtered = dropped.filtered;
+    stats.unsampled = dropped.unsampled;
+    stats.finish(started.elapsed());
+    let (success_count, error_count) = (stats.success, stats.failed);
+    let total_count = stats.total;
+    report_timing(started, total_count);
+    if args.stats {
+        stats.print();
+    }
+    if let Some(path) = &args.stats_json {
+        stats.write_json(path);
+    }
+
+    if !quiet() {
+        print_summary(args, success_count, error_count);
+    }
+    if let Some(breakdown) = stats.language_breakdown() {
+        notice(args, &tr!("🌐 Языки строк: {}", "🌐 Line languages: {}", breakdown));
+    }
+    if dropped.duplicates > 0 {
+        notice(
+            args,
+            &tr!("♻ Отброшено повторов: {}", "♻ Duplicates dropped: {}", dropped.duplicates),
+        );
+    }
+    if dropped.unsampled > 0 {
+        notice(
+            args,
+            &tr!(
+                "🎲 Не вошло в выборку строк: {}",
+                "🎲 Lines left out of the sample: {}",
+                dropped.unsampled
+            ),
+        );
+    }
+    if dropped.filtered > 0 {
+        notice(
+            args,
+            &tr!(
+                "⊘ Пропущено строк с другим числом слов: {}",
+                "⊘ Lines with other word counts skipped: {}",
+                dropped.filtered
+            ),
+        );
+    }
+    if let (Some(groups), Some(path)) = (duplicate_groups, &args.report_duplicates) {
+        notice(
+            args,
+            &tr!(
+                "🔁 Групп строк с одинаковой энтропией: {}, отчёт: {:?}",
+                "🔁 Groups of lines with identical entropy: {}, report: {:?}",
+                groups,
+                path
+            ),
+        );
+    }
+
+    if let Some(error_log_path) = &args.error_log {
+        if error_count > 0 {
+            notice(
+                args,
+                &tr!(
+                    "📝 Лог ошибок сохранён в файл: {:?}",
+                    "📝 Error log saved to file: {:?}",
+                    error_log_path
+                )
+            );
+        }
+    }
+
+    // Показываем предупреждение если много ошибок и это не режим skip_invalid
+    if !args.skip_invalid && error_count > 0 {
+        let error_rate = (error_count as f64 / total_count as f64) * 100.0;
+        if error_rate > 50.0 {
+            notice(
+                args,
+                &tr!(
+                    "\n⚠️  ВНИМАНИЕ: {:.1}% мнемоник невалидны!",
+                    "\n⚠️  WARNING: {:.1}% of mnemonics are invalid!",
+                    error_rate
+                )
+            );
+            notice(
+                args,
+                &tr!(
+                    "   Возможно это не BIP39 мнемоники (Electrum, Monero и т.д.)",
+                    "   They may not be BIP39 mnemonics (Electrum, Monero, etc.)"
+                )
+            );
+            notice(
+                args,
+                &tr!(
+                    "   Используйте --skip-invalid для игнорирования ошибок",
+                    "   Use --skip-invalid to ignore errors"
+                )
+            );
+            notice(
+                args,
+                &tr!(
+                    "   Используйте --error-log FILE для сохранения невалидных мнемоник",
+                    "   Use --error-log FILE to save invalid mnemonics"
+                )
+            );
+        }
+    }
+
+    if args.skip_invalid {
+        return 0;
+    }
+    if error_count > 0 && success_count == 0 && !quiet() {
+        error!(
+            "{}",
+            tr!("\n❌ Все мнемоники завершились с ошибкой!", "\n❌ All mnemonics failed!")
+        );
+    }
+    batch_exit_code(success_count, error_count)
+}
@@ -0,0 +1,73 @@
+//! Подкоманда `decrypt`: расшифровка файлов `--encrypt-output`.
+
+use super::encryption::decrypted;
+use super::input::{decompress, Compression};
+use super::{quiet, EXIT_IO};
+use clap::Args;
+use log::{error, info};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Args, Debug)]
+pub struct DecryptArgs {
+    /// Зашифрованный файл age
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: PathBuf,
+
+    /// Куда записать расшифрованный файл (по умолчанию stdout)
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Файл с ключами age (AGE-SECRET-KEY-...) для файлов, зашифрованных
+    /// на получателя; можно повторять. Без него запрашивается пароль
+    #[arg(long, value_name = "FILE")]
+    pub identity: Vec<PathBuf>,
+}
+
+pub fn run(args: &DecryptArgs) {
+    let file = fs::File::open(&args.input_file).unwrap_or_else(|e| {
+        error!(
+            "{}",
+            tr!(
+                "Ошибка при чтении файла {:?}: {}",
+                "Failed to read file {:?}: {}",
+                args.input_file,
+                e
+            )
+        );
+        std::process::exit(EXIT_IO);
+    });
+    let reader = decrypted(file, &args.identity, &args.input_file);
+    // Вывод сжимается до шифрования, поэтому после расшифровки его можно распаковать
+    let (mut reader, _) = decompress(reader, Compression::Auto);
+
+    let mut out: Box<dyn Write> = match &args.output_file {
+        Some(path) => match fs::File::create(path) {
+            Ok(file) => Box::new(std::io::BufWriter::new(file)),
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при создании файла {:?}: {}",
+                        "Failed to create file {:?}: {}",
+                        path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        },
+        None => Box::new(std::io::stdout()),
+    };
+    if let Err(e) = std::io::copy(&mut reader, &mut out).and_then(|_| out.flush()) {
+        error!(
+            "{}",
+            tr!("Ошибка при расшифровке {:?}: {}", "Failed to decrypt {:?}: {}", args.input_file, e)
+        );
+        std::process::exit(EXIT_IO);
+    }
+    if let Some(path) = args.output_file.as_ref().filter(|_| !quiet()) {
+        info!("{}", tr!("✓ Расшифровано в файл: {:?}", "✓ Decrypted to file: {:?}", path));
+    }
+}
@@ -0,0 +1,888 @@
+#[cfg(feature = "electrum")]
+use super::history::{History, HistoryArgs};
+use super::i18n::Localized;
+use super::input::load_lines;
+use super::{batch_exit_code, parse_language, quiet, report_timing, shown, EXIT_IO, EXIT_USAGE};
+use clap::{Args, ValueEnum};
+use log::{error, info};
+use mnem_to_entropy::{
+    account_path, account_xpub, cardano_path, decode, derive_addresses, derive_cardano_addresses,
+    derive_solana_addresses, mnemonic_to_seed, output_descriptor, parse_junctions, solana_path,
+    ss58_encode, substrate_public_key, AddressType, CardanoKey, Coin, DerivationPath,
+    DerivedAddress, Junction, Language, Network, Options, RootKeys, SubstrateCrypto,
+};
+use rayon::prelude::*;
+use std::fs;
+use std::io::Write;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(Args, Debug)]
+pub struct DeriveArgs {
+    /// Мнемоническая фраза
+    #[arg(short, long, env = "MNEM_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Файл с мнемониками (одна фраза на строку)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Option<PathBuf>,
+
+    /// Файл для сохранения адресов (номер строки, путь, адрес через табуляцию)
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Пароль BIP39 (25-е слово)
+    #[arg(long, env = "MNEM_PASSPHRASE", hide_env_values = true, default_value = "")]
+    pub passphrase: String,
+
+    /// Файл паролей BIP39, по одному на строку (пустая строка — без пароля):
+    /// адреса выводятся для каждой пары мнемоника × пароль с номером пароля
+    #[arg(long, value_name = "FILE", conflicts_with = "passphrase")]
+    pub passphrase_file: Option<PathBuf>,
+
+    /// Файл целевых адресов (по одному на строку): выводятся только фразы,
+    /// среди первых --count адресов которых есть целевой, вместе с фразой
+    #[arg(long, value_name = "FILE")]
+    pub match_address: Option<PathBuf>,
+
+    /// Вместо адресов вывести дескриптор цепочки от корневого xprv
+    /// (например wpkh(xprv.../84h/0h/0h/0/*)#контрольная_сумма) для импорта
+    /// в Bitcoin Core или Sparrow; только Bitcoin
+    #[arg(long, conflicts_with_all = ["match_address", "start", "count"])]
+    pub descriptor: bool,
+
+    /// Вывести только ключи без приватных данных: xpub — расширенный
+    /// публичный ключ аккаунта (m/purpose'/coin_type'/account' или --path)
+    #[arg(
+        long,
+        value_name = "KIND",
+        conflicts_with_all = ["descriptor", "match_address", "start", "count", "change"]
+    )]
+    pub export: Option<Export>,
+
+    /// Монета: btc, eth (m/44'/60'/account'/0/i, адреса EIP-55; так же
+    /// bsc, polygon, avax, arbitrum, optimism, base), trx (m/44'/195'/account'/0/i),
+    /// sol (m/44'/501'/i'/0', ключи Ed25519 по SLIP-0010), ada
+    /// (Cardano Icarus, m/1852'/1815'/account'/change/i, базовые адреса)
+    /// или dot (Substrate: один адрес SS58 по --junctions)
+    #[arg(long, default_value = "btc")]
+    pub coin: Coin,
+
+    /// Схема ключей для --coin dot: sr25519 (как в Polkadot.js) или ed25519
+    #[arg(long, default_value = "sr25519")]
+    pub crypto: SubstrateCrypto,
+
+    /// Путь Substrate для --coin dot из соединений //hard и /soft
+    /// (например //polkadot//0); пароль задаётся через --passphrase
+    #[arg(long, value_name = "PATH", default_value = "")]
+    pub junctions: String,
+
+    /// Префикс сети SS58 для --coin dot: 0 — Polkadot, 2 — Kusama, 42 — Substrate
+    #[arg(long, default_value_t = 42)]
+    pub ss58_prefix: u16,
+
+    /// Legacy-адреса P2PKH (m/44'/0'/account'/change/i)
+    #[arg(long, group = "scheme")]
+    pub bip44: bool,
+
+    /// SegWit в P2SH (m/49'/0'/account'/change/i)
+    #[arg(long, group = "scheme")]
+    pub bip49: bool,
+
+    /// Native SegWit P2WPKH (m/84'/0'/account'/change/i), по умолчанию
+    #[arg(long, group = "scheme")]
+    pub bip84: bool,
+
+    /// Taproot P2TR (m/86'/0'/account'/change/i)
+    #[arg(long, group = "scheme")]
+    pub bip86: bool,
+
+    /// Произвольный путь цепочки, к которому добавляется индекс адреса
+    /// (например m/84'/0'/0'/0)
+    #[arg(long, group = "scheme")]
+    pub path: Option<DerivationPath>,
+
+    /// Тип адреса для --path (по умолчанию определяется по purpose пути)
+    #[arg(long = "address-type")]
+    pub address_type: Option<AddressType>,
+
+    /// Номер аккаунта для пресетов BIP44/49/84/86
+    #[arg(long, default_value_t = 0)]
+    pub account: u32,
+
+    /// Использовать внутреннюю цепочку (адреса сдачи) вместо внешней
+    #[arg(long, default_value = "false")]
+    pub change: bool,
+
+    /// Индекс первого адреса
+    #[arg(long, default_value_t = 0)]
+    pub start: u32,
+
+    /// Количество адресов на мнемонику
+    #[arg(long, default_value_t = 20)]
+    pub count: u32,
+
+    /// Сеть: bitcoin, testnet, signet, regtest
+    #[arg(long, default_value = "bitcoin")]
+    pub network: Network,
+
+    /// Использовать только словарь указанного языка для проверки фразы
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    #[cfg(feature = "electrum")]
+    #[command(flatten)]
+    pub history: HistoryArgs,
+}
+
+/// Вид экспорта `--export`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Export {
+    /// Расширенный публичный ключ аккаунта (xpub/tpub)
+    Xpub,
+}
+
+/// Что выводится вместо адресов: по строке на пару фраза × пароль.
+enum KeyOutput {
+    /// Дескриптор цепочки от корневого xprv (`--descriptor`).
+    Descriptor {
+        chain: DerivationPath,
+        address_type: AddressType,
+    },
+    /// xpub аккаунта по пути `account` (`--export xpub`).
+    AccountXpub { account: DerivationPath },
+    /// Адрес SS58 Substrate по пути `path` (`--coin dot`).
+    Substrate {
+        crypto: SubstrateCrypto,
+        path: String,
+        junctions: Vec<Junction>,
+        prefix: u16,
+    },
+}
+
+impl KeyOutput {
+    /// Поля строки результата для фразы и пароля: дескриптор, путь и xpub
+    /// или путь, адрес SS58 и открытый ключ.
+    fn fields(&self, mnemonic_str: &str, entropy: &[u8], passphrase: &str, network: Network) -> Result<Vec<String>, String> {
+        let root = || RootKeys::from_seed(&mnemonic_to_seed(mnemonic_str, passphrase), network).xprv;
+        match self {
+            KeyOutput::Descriptor { chain, address_type } => Ok(vec![
+                output_descriptor(&root(), chain, *address_type).expect("Ethereum отклонён до запуска")
+            ]),
+            KeyOutput::AccountXpub { account } => {
+                let xpub = account_xpub(&root(), account).map_err(|e| e.localized())?;
+                Ok(vec![format!("m/{}", account), xpub.to_string()])
+            }
+            KeyOutput::Substrate {
+                crypto,
+                path,
+                junctions,
+                prefix,
+            } => {
+                // Substrate строит ключ из энтропии фразы, а не из BIP39 seed
+                let public_key =
+                    substrate_public_key(entropy, passphrase, *crypto, junctions).map_err(|e| e.localized())?;
+                let address = ss58_encode(&public_key, *prefix).map_err(|e| e.localized())?;
+                Ok(vec![path.clone(), address, format!("0x{}", hex::encode(public_key))])
+            }
+        }
+    }
+
+    /// Блок результата в консоли.
+    fn print(&self, fields: &[String]) {
+        match self {
+            KeyOutput::Descriptor { .. } => {
+                println!("{}", tr!("Дескриптор: {}", "Descriptor: {}", fields[0]))
+            }
+            KeyOutput::AccountXpub { .. } => {
+                println!("{}", tr!("Путь: {}", "Path: {}", fields[0]));
+                println!("xpub: {}", fields[1]);
+            }
+            KeyOutput::Substrate { .. } => {
+                if fields[0].is_empty() {
+                    println!("{}", tr!("Путь: (корень)", "Path: (root)"));
+                } else {
+                    println!("{}", tr!("Путь: {}", "Path: {}", fields[0]));
+                }
+                println!("{}", tr!("Адрес SS58: {}", "SS58 address: {}", fields[1]));
+                println!("{}", tr!("Открытый ключ: {}", "Public key: {}", fields[2]));
+            }
+        }
+    }
+}
+
+impl DeriveArgs {
+    /// Путь цепочки и тип адреса из выбранного пресета или --path.
+    fn scheme(&self) -> Result<(DerivationPath, AddressType), String> {
+        if self.coin == Coin::Solana {
+            if self.bip44 || self.bip49 || self.bip84 || self.bip86 || self.path.is_some() {
+                return Err(tr!(
+                    "для Solana путь фиксирован (m/44'/501'/i'/0'), пресеты и --path неприменимы",
+                    "Solana uses a fixed path (m/44'/501'/i'/0'); presets and --path do not apply"
+                ));
+            }
+            // Индекс адреса — hardened-счёт внутри пути, поэтому цепочка — только префикс
+            let prefix = solana_path(0).map_err(|e| e.localized())?;
+            return Ok((prefix[..2].into(), AddressType::Solana));
+        }
+        if self.coin == Coin::Cardano {
+            if self.bip44 || self.bip49 || self.bip84 || self.bip86 || self.path.is_some() {
+                return Err(tr!(
+                    "для Cardano путь фиксирован (m/1852'/1815'/account'/change/i), пресеты и --path неприменимы",
+                    "Cardano uses a fixed path (m/1852'/1815'/account'/change/i); presets and --path do not apply"
+                ));
+            }
+            let path = cardano_path(self.account, self.change as u32, 0).map_err(|e| e.localized())?;
+            return Ok((path[..4].into(), AddressType::Cardano));
+        }
+        if matches!(self.coin, Coin::Ethereum | Coin::Tron) {
+            let address_type = match self.coin {
+                Coin::Tron => AddressType::Tron,
+                _ => AddressType::Ethereum,
+            };
+            if self.bip49 || self.bip84 || self.bip86 {
+                return Err(tr!(
+                    "пресеты --bip49/--bip84/--bip86 применимы только к Bitcoin",
+                    "the --bip49/--bip84/--bip86 presets apply only to Bitcoin"
+                ));
+            }
+            let chain = match &self.path {
+                Some(path) => path.clone(),
+                None => self.preset_chain(address_type)?,
+            };
+            return Ok((chain, address_type));
+        }
+
+        if let Some(path) = &self.path {
+            let address_type = self
+                .address_type
+                .or_else(|| AddressType::from_path(path))
+                .ok_or_else(|| {
+                    tr!(
+                        "не удалось определить тип адреса по пути, укажите --address-type",
+                        "cannot infer the address type from the path, specify --address-type"
+                    )
+                })?;
+            return Ok((path.clone(), address_type));
+        }
+
+        let address_type = if self.bip44 {
+            AddressType::P2pkh
+        } else if self.bip49 {
+            AddressType::P2shP2wpkh
+        } else if self.bip86 {
+            AddressType::P2tr
+        } else {
+            AddressType::P2wpkh
+        };
+        Ok((self.preset_chain(address_type)?, address_type))
+    }
+
+    /// Вывод ключей вместо адресов (`--descriptor`, `--export`), если он выбран.
+    /// Для `--export xpub` с `--path` тип адреса не нужен: путь и есть аккаунт.
+    fn key_output(&self) -> Result<Option<KeyOutput>, String> {
+        if self.export == Some(Export::Xpub) {
+            if matches!(self.coin, Coin::Solana | Coin::Cardano | Coin::Polkadot) {
+                return Err(tr!(
+                    "--export xpub поддерживает только Bitcoin, Ethereum и Tron",
+                    "--export xpub supports only Bitcoin, Ethereum and Tron"
+                ));
+            }
+            let account = match &self.path {
+                Some(path) => path.clone(),
+                None => self.preset_account(self.scheme()?.1)?,
+            };
+            return Ok(Some(KeyOutput::AccountXpub { account }));
+        }
+        if self.descriptor {
+            let (chain, address_type) = self.scheme()?;
+            if self.coin != Coin::Bitcoin {
+                return Err(tr!(
+                    "--descriptor поддерживает только Bitcoin",
+                    "--descriptor supports only Bitcoin"
+                ));
+            }
+            return Ok(Some(KeyOutput::Descriptor { chain, address_type }));
+        }
+        if self.coin == Coin::Polkadot {
+            return self.substrate_output().map(Some);
+        }
+        Ok(None)
+    }
+
+    /// Вывод `--coin dot`: путь задаётся --junctions, пресеты BIP44 и поиск
+    /// адресов неприменимы.
+    fn substrate_output(&self) -> Result<KeyOutput, String> {
+        if self.bip44 || self.bip49 || self.bip84 || self.bip86 || self.path.is_some() {
+            return Err(tr!(
+                "для Substrate путь задаётся --junctions, пресеты и --path неприменимы",
+                "Substrate paths are set with --junctions; presets and --path do not apply"
+            ));
+        }
+        if self.match_address.is_some() {
+            return Err(tr!(
+                "--match-address не поддерживает Substrate",
+                "--match-address does not support Substrate"
+            ));
+        }
+        let junctions = parse_junctions(&self.junctions).map_err(|e| e.localized())?;
+        // Проверка префикса до обработки фраз
+        ss58_encode(&[0; 32], self.ss58_prefix).map_err(|e| e.localized())?;
+        Ok(KeyOutput::Substrate {
+            crypto: self.crypto,
+            path: self.junctions.clone(),
+            junctions,
+            prefix: self.ss58_prefix,
+        })
+    }
+
+    /// Путь цепочки пресета: m/purpose'/coin_type'/account'/change.
+    fn preset_chain(&self, address_type: AddressType) -> Result<DerivationPath, String> {
+        Ok(self.preset_account(address_type)?.child((self.change as u32).into()))
+    }
+
+    /// Путь аккаунта пресета: m/purpose'/coin_type'/account'.
+    fn preset_account(&self, address_type: AddressType) -> Result<DerivationPath, String> {
+        account_path(
+            address_type.purpose(),
+            self.coin.coin_type(self.network),
+            self.account,
+        )
+        .map_err(|e| e.localized())
+    }
+}
+
+/// Адреса для одного пароля и, для Cardano, корневой ключ `root_xsk`.
+type Derived = (Vec<DerivedAddress>, Option<String>);
+
+/// Адреса фразы для каждого пароля из `passphrases`, в том же порядке.
+fn process_mnemonic(
+    mnemonic_str: &str,
+    passphrases: &[String],
+    args: &DeriveArgs,
+    options: &Options,
+    chain: &DerivationPath,
+    address_type: AddressType,
+) -> Result<Vec<Derived>, String> {
+    let decoded = decode(mnemonic_str, options).map_err(|e| e.localized())?;
+    passphrases
+        .par_iter()
+        .map(|passphrase| {
+            // Cardano Icarus строит ключ из энтропии, а не из BIP39 seed
+            if address_type == AddressType::Cardano {
+                let root = CardanoKey::icarus(decoded.entropy.as_bytes(), passphrase);
+                let role = args.change as u32;
+                return derive_cardano_addresses(&root, args.network, args.account, role, args.start, args.count)
+                    .map(|addresses| (addresses, Some(root.to_root_xsk())))
+                    .map_err(|e| e.localized());
+            }
+            let seed = mnemonic_to_seed(mnemonic_str, passphrase);
+            let addresses = if address_type == AddressType::Solana {
+                derive_solana_addresses(&seed, args.start, args.count)
+            } else {
+                let root = RootKeys::from_seed(&seed, args.network);
+                derive_addresses(&root.xprv, args.network, chain, address_type, args.start, args.count)
+            };
+            addresses.map(|addresses| (addresses, None)).map_err(|e| e.localized())
+        })
+        .collect()
+}
+
+/// Строки `output` фразы для каждого пароля из `passphrases`, в том же порядке.
+fn mnemonic_keys(
+    mnemonic_str: &str,
+    passphrases: &[String],
+    args: &DeriveArgs,
+    options: &Options,
+    output: &KeyOutput,
+) -> Result<Vec<Vec<String>>, String> {
+    let decoded = decode(mnemonic_str, options).map_err(|e| e.localized())?;
+    passphrases
+        .par_iter()
+        .map(|passphrase| output.fields(mnemonic_str, decoded.entropy.as_bytes(), passphrase, args.network))
+        .collect()
+}
+
+/// Режимы `--descriptor`, `--export` и `--coin dot`: по строке на каждую пару
+/// фраза × пароль; в файл и с `--quiet` — «номер, [пароль,] поля» (дескриптор,
+/// путь и xpub или путь, адрес SS58 и открытый ключ).
+/// С `--export` фраза не выводится даже в консоли. Завершает программу
+/// с кодом по числу ошибок.
+fn run_keys(
+    args: &DeriveArgs,
+    mnemonics: &[String],
+    passphrases: &[String],
+    options: &Options,
+    output: KeyOutput,
+) -> ! {
+    let show_mnemonic = !matches!(output, KeyOutput::AccountXpub { .. });
+    let started = Instant::now();
+    let results: Vec<Result<Vec<Vec<String>>, String>> = mnemonics
+        .par_iter()
+        .map(|mnemonic_str| mnemonic_keys(mnemonic_str, passphrases, args, options, &output))
+        .collect();
+    report_timing(started, results.len());
+
+    let labeled = args.passphrase_file.is_some();
+    let mut lines = Vec::new();
+    let mut error_count = 0;
+    for (idx, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(per_passphrase) => {
+                for (number, fields) in per_passphrase.into_iter().enumerate() {
+                    let label = labeled.then_some(number + 1);
+                    if args.output_file.is_none() && quiet() {
+                        match label {
+                            Some(label) => println!("{}  {}", label, fields.join("  ")),
+                            None => println!("{}", fields.join("  ")),
+                        }
+                    } else if args.output_file.is_none() {
+                        match label {
+                            Some(label) => println!(
+                                "{}",
+                                tr!(
+                                    "\n=== Результат {}, пароль {} ===",
+                                    "\n=== Result {}, passphrase {} ===",
+                                    idx + 1,
+                                    label
+                                )
+                            ),
+                            None => println!(
+                                "{}",
+                                tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1)
+                            ),
+                        }
+                        if show_mnemonic {
+                            println!(
+                                "{}",
+                                tr!("Мнемоническая фраза: {}", "Mnemonic: {}", shown(&mnemonics[idx]))
+                            );
+                        }
+                        output.print(&fields);
+                    }
+                    lines.push(match label {
+                        Some(label) => format!("{}\t{}\t{}", idx + 1, label, fields.join("\t")),
+                        None => format!("{}\t{}", idx + 1, fields.join("\t")),
+                    });
+                }
+            }
+            Err(message) => {
+                if args.output_file.is_none() && !quiet() {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    if show_mnemonic {
+                        eprintln!(
+                            "{}",
+                            tr!("Мнемоническая фраза: {}", "Mnemonic: {}", shown(&mnemonics[idx]))
+                        );
+                    }
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                error_count += 1;
+            }
+        }
+    }
+
+    if let Some(output_path) = &args.output_file {
+        let saved = match output {
+            KeyOutput::Descriptor { .. } => tr!(
+                "✓ Дескрипторы сохранены в файл: {:?}",
+                "✓ Descriptors saved to file: {:?}",
+                output_path
+            ),
+            KeyOutput::AccountXpub { .. } => tr!(
+                "✓ Ключи xpub сохранены в файл: {:?}",
+                "✓ Xpubs saved to file: {:?}",
+                output_path
+            ),
+            KeyOutput::Substrate { .. } => {
+                tr!("✓ Адреса сохранены в файл: {:?}", "✓ Addresses saved to file: {:?}", output_path)
+            }
+        };
+        save_lines(output_path, &lines, &saved, mnemonics.len() - error_count, error_count);
+    }
+    if error_count > 0 && error_count == mnemonics.len() && !quiet() {
+        error!(
+            "{}",
+            tr!("\n❌ Все мнемоники завершились с ошибкой!", "\n❌ All mnemonics failed!")
+        );
+    }
+    std::process::exit(batch_exit_code(mnemonics.len() - error_count, error_count));
+}
+
+/// Адрес для сравнения: bech32 и адреса Ethereum не зависят от регистра,
+/// Base58 — зависит.
+pub fn normalize_address(address: &str) -> String {
+    let lower = address.to_lowercase();
+    if ["0x", "bc1", "tb1", "bcrt1"].iter().any(|prefix| lower.starts_with(prefix)) {
+        lower
+    } else {
+        address.to_string()
+    }
+}
+
+/// Столбец числа транзакций адреса `i` для файла и `--quiet`: `?`, если сервер
+/// не ответил; без `--history-server` (`counts` пуст) — пустая строка.
+fn tx_column(counts: &[Option<usize>], i: usize, separator: &str) -> String {
+    match counts.get(i) {
+        Some(Some(count)) => format!("{}{}", separator, count),
+        Some(None) => format!("{}?", separator),
+        None => String::new(),
+    }
+}
+
+/// Пометка адреса в консоли: число транзакций, если они были.
+fn tx_note(counts: &[Option<usize>], i: usize) -> String {
+    match counts.get(i) {
+        Some(Some(count)) if *count > 0 => tr!("  [транзакций: {}]", "  [transactions: {}]", count),
+        Some(None) => tr!("  [история неизвестна]", "  [history unknown]"),
+        _ => String::new(),
+    }
+}
+
+/// Пометка фразы в консоли, если у её адресов есть история.
+fn print_used(used: bool) {
+    if used {
+        println!("{}", tr!("💰 У адресов есть транзакции", "💰 Addresses have transactions"));
+    }
+}
+
+/// Целевые адреса из `--match-address`; пустые строки и `#`-комментарии пропускаются.
+pub fn load_targets(path: &Path) -> HashSet<String> {
+    match fs::read_to_string(path) {
+        Ok(text) => text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(normalize_address)
+            .collect(),
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при чтении файла адресов {:?}: {}",
+                    "Failed to read the address file {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Пароли из `--passphrase-file`: строки файла как есть, без обрезки пробелов
+/// (они часть пароля); пустая строка — пустой пароль.
+fn load_passphrases(path: &Path) -> Vec<String> {
+    match fs::read_to_string(path) {
+        Ok(text) => text
+            .lines()
+            .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+            .collect(),
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при чтении файла паролей {:?}: {}",
+                    "Failed to read the passphrase file {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+pub fn run(args: &DeriveArgs) {
+    // Ключи вместо адресов; путь адресов нужен только без них
+    let scheme = args.key_output().and_then(|output| match output {
+        Some(output) => Ok(Err(output)),
+        None => args.scheme().map(Ok),
+    });
+    let scheme = match scheme {
+        Ok(scheme) => scheme,
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e));
+            std::process::exit(EXIT_USAGE);
+        }
+    };
+
+    let mnemonics = load_lines(
+        args.input_file.as_deref(),
+        args.mnemonic.as_deref(),
+        &tr!("Введите мнемоническую фразу:", "Enter the mnemonic phrase:"),
+        args.output_file.is_some(),
+        false,
+    );
+
+    let options = Options {
+        language: args.language,
+        ..Options::default()
+    };
+    // С файлом паролей результат каждой пары помечается номером пароля
+    let passphrases = match &args.passphrase_file {
+        Some(path) => load_passphrases(path),
+        None => vec![args.passphrase.clone()],
+    };
+    let labeled = args.passphrase_file.is_some();
+    let (chain, address_type) = match scheme {
+        Ok(scheme) => scheme,
+        Err(output) => run_keys(args, &mnemonics, &passphrases, &options, output),
+    };
+    let targets = args.match_address.as_deref().map(load_targets);
+    #[cfg(feature = "electrum")]
+    let mut history = {
+        if args.history.history_server.is_some() && args.coin != Coin::Bitcoin {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка: --history-server поддерживает только Bitcoin",
+                    "Error: --history-server supports only Bitcoin"
+                )
+            );
+            std::process::exit(EXIT_USAGE);
+        }
+        History::connect(&args.history)
+    };
+
+    // Параллельная обработка, порядок сохраняется
+    let started = Instant::now();
+    let results: Vec<Result<Vec<Derived>, String>> = mnemonics
+        .par_iter()
+        .map(|mnemonic_str| {
+            process_mnemonic(mnemonic_str, &passphrases, args, &options, &chain, address_type)
+        })
+        .collect();
+    report_timing(started, results.len());
+
+    let mut lines = Vec::new();
+    let mut error_count = 0;
+    let mut match_count = 0;
+    let mut used_count = 0;
+    let mut checked_history = false;
+
+    for (idx, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(per_passphrase) => {
+                for (number, (mut addresses, root_key)) in per_passphrase.into_iter().enumerate() {
+                    // Номер пароля (с единицы) — только с --passphrase-file
+                    let label = labeled.then_some(number + 1);
+                    if let Some(targets) = &targets {
+                        addresses.retain(|derived| targets.contains(&normalize_address(&derived.address)));
+                        if addresses.is_empty() {
+                            continue;
+                        }
+                        match_count += 1;
+                    }
+                    // Сервер истории спрашивается только об адресах, прошедших локальные проверки
+                    #[cfg(feature = "electrum")]
+                    let counts = history
+                        .as_mut()
+                        .map_or_else(Vec::new, |history| history.tx_counts(&addresses));
+                    #[cfg(not(feature = "electrum"))]
+                    let counts = Vec::new();
+                    let used = counts.iter().flatten().any(|&count| count > 0);
+                    used_count += used as usize;
+                    checked_history |= !counts.is_empty();
+
+                    if targets.is_some() {
+                        report_match(idx, label, &mnemonics[idx], &addresses, &counts, args, &mut lines);
+                        continue;
+                    }
+                    if args.output_file.is_none() && quiet() {
+                        for (i, derived) in addresses.iter().enumerate() {
+                            let tx = tx_column(&counts, i, "  ");
+                            match label {
+                                Some(label) => {
+                                    println!("{}  {}  {}{}", label, derived.path, derived.address, tx)
+                                }
+                                None => println!("{}  {}{}", derived.path, derived.address, tx),
+                            }
+                        }
+                    } else if args.output_file.is_none() {
+                        match label {
+                            Some(label) => println!(
+                                "{}",
+                                tr!(
+                                    "\n=== Результат {}, пароль {} ===",
+                                    "\n=== Result {}, passphrase {} ===",
+                                    idx + 1,
+                                    label
+                                )
+                            ),
+                            None => println!(
+                                "{}",
+                                tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1)
+                            ),
+                        }
+                        println!(
+                            "{}",
+                            tr!("Мнемоническая фраза: {}", "Mnemonic: {}", shown(&mnemonics[idx]))
+                        );
+                        if let Some(root_key) = &root_key {
+                            println!("{}", tr!("Корневой ключ: {}", "Root key: {}", root_key));
+                        }
+                        print_used(used);
+                        for (i, derived) in addresses.iter().enumerate() {
+                            println!("{}  {}{}", derived.path, derived.address, tx_note(&counts, i));
+                        }
+                    }
+                    for (i, derived) in addresses.into_iter().enumerate() {
+                        let tx = tx_column(&counts, i, "\t");
+                        lines.push(match label {
+                            Some(label) => format!(
+                                "{}\t{}\t{}\t{}{}",
+                                idx + 1,
+                                label,
+                                derived.path,
+                                derived.address,
+                                tx
+                            ),
+                            None => format!("{}\t{}\t{}{}", idx + 1, derived.path, derived.address, tx),
+                        });
+                    }
+                }
+            }
+            Err(message) => {
+                if args.output_file.is_none() && !quiet() {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    eprintln!(
+                        "{}",
+                        tr!("Мнемоническая фраза: {}", "Mnemonic: {}", shown(&mnemonics[idx]))
+                    );
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                error_count += 1;
+            }
+        }
+    }
+
+    if let Some(output_path) = &args.output_file {
+        let saved = tr!("✓ Адреса сохранены в файл: {:?}", "✓ Addresses saved to file: {:?}", output_path);
+        save_lines(output_path, &lines, &saved, mnemonics.len() - error_count, error_count);
+    }
+
+    if targets.is_some() && !quiet() {
+        info!("{}", tr!("Совпадений с адресами: {}", "Address matches: {}", match_count));
+    }
+    if checked_history && !quiet() {
+        info!("{}", tr!("Фраз с историей транзакций: {}", "Mnemonics with transaction history: {}", used_count));
+    }
+    if error_count > 0 && error_count == mnemonics.len() && !quiet() {
+        error!(
+            "{}",
+            tr!("\n❌ Все мнемоники завершились с ошибкой!", "\n❌ All mnemonics failed!")
+        );
+    }
+    std::process::exit(batch_exit_code(mnemonics.len() - error_count, error_count));
+}
+
+/// Записывает строки результата в `--output` и печатает итог: `saved`,
+/// число обработанных фраз и ошибок.
+pub fn save_lines(output_path: &Path, lines: &[String], saved: &str, processed: usize, error_count: usize) {
+    match fs::File::create(output_path) {
+        Ok(mut file) => {
+            for line in lines {
+                if let Err(e) = writeln!(file, "{}", line) {
+                    error!(
+                        "{}",
+                        tr!(
+                            "Ошибка при записи в файл {:?}: {}",
+                            "Failed to write file {:?}: {}",
+                            output_path,
+                            e
+                        )
+                    );
+                    std::process::exit(EXIT_IO);
+                }
+            }
+            if !quiet() {
+                println!("{}", saved);
+                println!(
+                    "{}",
+                    tr!(
+                        "  Обработано успешно: {} мнемоник",
+                        "  Processed successfully: {} mnemonics",
+                        processed
+                    )
+                );
+                if error_count > 0 {
+                    println!("{}", tr!("  Ошибок: {}", "  Errors: {}", error_count));
+                }
+            }
+        }
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при создании файла {:?}: {}",
+                    "Failed to create file {:?}: {}",
+                    output_path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Выводит фразу, давшую целевые адреса (`--match-address`): в консоль
+/// блоком, с `--quiet` или в файл — строками «номер, [пароль,] путь, адрес, фраза».
+/// В файле фраза не маскируется даже с `--redact`: ради неё фильтр и запускается.
+fn report_match(
+    idx: usize,
+    label: Option<usize>,
+    mnemonic: &str,
+    matched: &[DerivedAddress],
+    counts: &[Option<usize>],
+    args: &DeriveArgs,
+    lines: &mut Vec<String>,
+) {
+    let text = match args.output_file {
+        Some(_) => Cow::Borrowed(mnemonic),
+        None => shown(mnemonic),
+    };
+    let rows = matched.iter().enumerate().map(|(i, derived)| {
+        let tx = tx_column(counts, i, "\t");
+        match label {
+            Some(label) => format!(
+                "{}\t{}\t{}\t{}\t{}{}",
+                idx + 1,
+                label,
+                derived.path,
+                derived.address,
+                text,
+                tx
+            ),
+            None => format!("{}\t{}\t{}\t{}{}", idx + 1, derived.path, derived.address, text, tx),
+        }
+    });
+    if args.output_file.is_some() {
+        lines.extend(rows);
+    } else if quiet() {
+        rows.for_each(|row| println!("{}", row));
+    } else {
+        match label {
+            Some(label) => println!(
+                "{}",
+                tr!(
+                    "\n=== Совпадение: строка {}, пароль {} ===",
+                    "\n=== Match: line {}, passphrase {} ===",
+                    idx + 1,
+                    label
+                )
+            ),
+            None => println!(
+                "{}",
+                tr!("\n=== Совпадение: строка {} ===", "\n=== Match: line {} ===", idx + 1)
+            ),
+        }
+        println!("{}", tr!("Мнемоническая фраза: {}", "Mnemonic: {}", text));
+        print_used(counts.iter().flatten().any(|&count| count > 0));
+        for (i, derived) in matched.iter().enumerate() {
+            println!("{}  {}{}", derived.path, derived.address, tx_note(counts, i));
+        }
+    }
+}
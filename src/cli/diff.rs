@@ -0,0 +1,131 @@
+use super::decode::load_wordlist;
+use super::i18n::Localized;
+use super::input::{load_lines, read_interactive, stdin_piped};
+use super::{parse_language, quiet, redacting, EXIT_PARTIAL, EXIT_USAGE};
+use clap::Args;
+use log::error;
+use mnem_to_entropy::{diff_mnemonics, language_name, Decoded, DecodeError, Language, Options, WordChange};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Сравниваемая фраза; опцию нужно указать дважды (недостающие
+    /// фразы запрашиваются со скрытым вводом)
+    #[arg(short, long, num_args = 1, action = clap::ArgAction::Append)]
+    pub mnemonic: Vec<String>,
+
+    /// Файл, две первые непустые строки которого — сравниваемые фразы
+    #[arg(short = 'i', long = "input", conflicts_with = "mnemonic")]
+    pub input_file: Option<PathBuf>,
+
+    /// Декодировать только по словарю указанного языка
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Пользовательский словарь из 2048 слов (одно слово на строку)
+    #[arg(long, value_name = "FILE", conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+}
+
+/// Две сравниваемые фразы из аргументов, файла или ввода.
+fn load_pair(args: &DiffArgs) -> (String, String) {
+    let mut phrases = match &args.input_file {
+        Some(path) => load_lines(Some(path), None, "", false, false),
+        None => args.mnemonic.clone(),
+    };
+    if phrases.len() > 2 || (args.input_file.is_some() && phrases.len() < 2) {
+        error!(
+            "{}",
+            tr!(
+                "Для сравнения нужны ровно две фразы, получено: {}",
+                "Exactly two phrases are needed for comparison, got: {}",
+                phrases.len()
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    while phrases.len() < 2 {
+        if !quiet() && !stdin_piped() {
+            println!("{}", tr!("Введите фразу {}:", "Enter phrase {}:", phrases.len() + 1));
+        }
+        phrases.push(read_interactive(false).unwrap_or_default().trim().to_string());
+    }
+    let right = phrases.pop().expect("две фразы");
+    let left = phrases.pop().expect("две фразы");
+    (left, right)
+}
+
+/// Итог декодирования фразы: язык или причина ошибки.
+fn decoded_text(result: &Result<Decoded, DecodeError>) -> String {
+    match result {
+        Ok(decoded) => tr!(
+            "верна, язык {}",
+            "valid, language {}",
+            decoded.language.map(language_name).unwrap_or("custom")
+        ),
+        Err(e) => e.localized(),
+    }
+}
+
+pub fn run(args: &DiffArgs) {
+    let (left, right) = load_pair(args);
+    let options = Options {
+        language: args.language,
+        wordlist: args.wordlist.as_deref().map(|path| Arc::new(load_wordlist(path))),
+        ..Options::default()
+    };
+    let diff = diff_mnemonics(&left, &right, &options);
+
+    // С --redact слова не выводятся, только позиции и вид различия
+    let word = |word: &Option<String>| match word {
+        _ if redacting() => "***".to_string(),
+        Some(word) => word.clone(),
+        None => "—".to_string(),
+    };
+    for difference in &diff.differences {
+        let change = match difference.change {
+            WordChange::Typo { distance } => {
+                tr!("опечатка, правок: {}", "typo, edits: {}", distance)
+            }
+            WordChange::Transposed { with } => {
+                tr!("перестановка со словом {}", "transposed with word {}", with + 1)
+            }
+            WordChange::Missing => tr!("есть только в одной фразе", "only in one phrase"),
+            WordChange::Different => tr!("другое слово", "different word"),
+        };
+        println!(
+            "{:>2}. {} → {}\t{}",
+            difference.position + 1,
+            word(&difference.left),
+            word(&difference.right),
+            change
+        );
+    }
+
+    if !quiet() {
+        let (left_count, right_count) = diff.word_counts;
+        println!(
+            "{}",
+            tr!(
+                "\nСлов: {} и {}, различий: {}",
+                "\nWords: {} and {}, differences: {}",
+                left_count,
+                right_count,
+                diff.differences.len()
+            )
+        );
+        println!("{}", tr!("Фраза 1: {}", "Phrase 1: {}", decoded_text(&diff.left)));
+        println!("{}", tr!("Фраза 2: {}", "Phrase 2: {}", decoded_text(&diff.right)));
+        println!(
+            "{}",
+            if diff.same_entropy() {
+                tr!("✓ Энтропия совпадает", "✓ The entropy is the same")
+            } else {
+                tr!("✗ Энтропия различается", "✗ The entropy differs")
+            }
+        );
+    }
+    let same = diff.differences.is_empty() || diff.same_entropy();
+    std::process::exit(if same { 0 } else { EXIT_PARTIAL });
+}
@@ -0,0 +1,63 @@
+//! Отчёт о повторяющейся энтропии (`--report-duplicates`): строки входа,
+//! которые дают одну и ту же энтропию — одна фраза на разных языках,
+//! с исправленными опечатками или с разным числом пробелов.
+
+use super::EXIT_IO;
+use log::error;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Строки входа, сгруппированные по энтропии. Хранит энтропию каждой
+/// успешной строки, поэтому память растёт с размером входа.
+#[derive(Default)]
+pub struct Duplicates {
+    /// Энтропия → строки в порядке входа: место во входе и словарь.
+    groups: HashMap<Vec<u8>, Vec<(String, &'static str)>>,
+    /// Порядок первого появления энтропии — группы в отчёте идут в порядке входа.
+    order: Vec<Vec<u8>>,
+}
+
+impl Duplicates {
+    /// Учитывает успешную строку: `place` — номер строки или `файл:строка`.
+    pub fn add(&mut self, entropy: &[u8], place: String, dictionary: &'static str) {
+        let lines = self.groups.entry(entropy.to_vec()).or_insert_with(|| {
+            self.order.push(entropy.to_vec());
+            Vec::new()
+        });
+        lines.push((place, dictionary));
+    }
+
+    /// Записывает группы из двух и более строк, по группе на строку:
+    /// энтропия (в кодировке `encode`), число строк и места с языками через
+    /// табуляцию. Возвращает число групп.
+    pub fn write(&self, path: &Path, encode: impl Fn(&[u8]) -> String) -> usize {
+        let mut report = String::new();
+        let mut count = 0;
+        for entropy in &self.order {
+            let lines = &self.groups[entropy];
+            if lines.len() < 2 {
+                continue;
+            }
+            count += 1;
+            let places: Vec<String> = lines
+                .iter()
+                .map(|(place, dictionary)| format!("{} ({})", place, dictionary))
+                .collect();
+            report.push_str(&format!("{}\t{}\t{}\n", encode(entropy), lines.len(), places.join(", ")));
+        }
+        if let Err(e) = fs::write(path, report) {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при записи отчёта о повторах {:?}: {}",
+                    "Failed to write the duplicates report {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+        count
+    }
+}
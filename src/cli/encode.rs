@@ -0,0 +1,170 @@
+use super::i18n::Localized;
+use super::input::load_lines;
+use super::{batch_exit_code, parse_language, quiet, report_timing, EXIT_IO};
+#[cfg(feature = "qr")]
+use super::qr::{self, QrArgs};
+#[cfg(feature = "qr")]
+use super::{EXIT_FAILED, EXIT_USAGE};
+use clap::Args;
+use log::error;
+use mnem_to_entropy::{encode_in, Language};
+use rayon::prelude::*;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Args, Debug)]
+pub struct EncodeArgs {
+    /// Энтропия в hex (128–256 бит)
+    #[arg(short, long)]
+    pub entropy: Option<String>,
+
+    /// Файл с hex-энтропией (одна запись на строку)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Option<PathBuf>,
+
+    /// Файл для сохранения мнемоник
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Язык словаря для мнемоники
+    #[arg(long, value_parser = parse_language, default_value = "english")]
+    pub language: Language,
+
+    #[cfg(feature = "qr")]
+    #[command(flatten)]
+    pub qr: QrArgs,
+}
+
+fn process_entropy(entropy_hex: &str, language: Language) -> Result<String, String> {
+    let entropy =
+        hex::decode(entropy_hex).map_err(|e| tr!("Неверный hex: {}", "Invalid hex: {}", e))?;
+    encode_in(language, &entropy).map_err(|e| e.localized())
+}
+
+pub fn run(args: &EncodeArgs) {
+    let entropies = load_lines(
+        args.input_file.as_deref(),
+        args.entropy.as_deref(),
+        &tr!("Введите энтропию (hex):", "Enter the entropy (hex):"),
+        args.output_file.is_some(),
+        false,
+    );
+
+    #[cfg(feature = "qr")]
+    if let Some(content) = args.qr.qr {
+        let [entropy_hex] = entropies.as_slice() else {
+            error!(
+                "{}",
+                tr!(
+                    "--qr работает с одной записью, получено строк: {}",
+                    "--qr works with a single entry, lines given: {}",
+                    entropies.len()
+                )
+            );
+            std::process::exit(EXIT_USAGE);
+        };
+        // Проверяем энтропию тем же путём, что и без --qr
+        if let Err(message) = process_entropy(entropy_hex, args.language) {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", message));
+            std::process::exit(EXIT_FAILED);
+        }
+        let entropy = hex::decode(entropy_hex).expect("hex проверен выше");
+        qr::show(&args.qr, content, &entropy, Some(args.language));
+        return;
+    }
+
+    // Параллельная обработка, порядок сохраняется
+    let started = Instant::now();
+    let results: Vec<Result<String, String>> = entropies
+        .par_iter()
+        .map(|entropy_hex| process_entropy(entropy_hex, args.language))
+        .collect();
+    report_timing(started, results.len());
+
+    let mut success_results = Vec::new();
+    let mut error_count = 0;
+
+    for (idx, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(mnemonic) => {
+                if args.output_file.is_none() && quiet() {
+                    println!("{}", mnemonic);
+                } else if args.output_file.is_none() {
+                    println!("{}", tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1));
+                    println!("{}", tr!("Энтропия: {}", "Entropy: {}", entropies[idx]));
+                    println!("{}", tr!("Мнемоническая фраза: {}", "Mnemonic: {}", mnemonic));
+                }
+                success_results.push(mnemonic);
+            }
+            Err(message) => {
+                if args.output_file.is_none() && !quiet() {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    eprintln!("{}", tr!("Энтропия: {}", "Entropy: {}", entropies[idx]));
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                error_count += 1;
+            }
+        }
+    }
+
+    if let Some(output_path) = &args.output_file {
+        match fs::File::create(output_path) {
+            Ok(mut file) => {
+                for mnemonic in &success_results {
+                    if let Err(e) = writeln!(file, "{}", mnemonic) {
+                        error!(
+                            "{}",
+                            tr!(
+                                "Ошибка при записи в файл {:?}: {}",
+                                "Failed to write file {:?}: {}",
+                                output_path,
+                                e
+                            )
+                        );
+                        std::process::exit(EXIT_IO);
+                    }
+                }
+                if !quiet() {
+                    println!(
+                        "{}",
+                        tr!(
+                            "✓ Результаты сохранены в файл: {:?}",
+                            "✓ Results saved to file: {:?}",
+                            output_path
+                        )
+                    );
+                    println!(
+                        "{}",
+                        tr!(
+                            "  Обработано успешно: {} записей",
+                            "  Encoded successfully: {} entries",
+                            success_results.len()
+                        )
+                    );
+                    if error_count > 0 {
+                        println!("{}", tr!("  Ошибок: {}", "  Errors: {}", error_count));
+                    }
+                }
+            }
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при создании файла {:?}: {}",
+                        "Failed to create file {:?}: {}",
+                        output_path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    }
+
+    if error_count > 0 && success_results.is_empty() && !quiet() {
+        error!("{}", tr!("\n❌ Все записи завершились с ошибкой!", "\n❌ All entries failed!"));
+    }
+    std::process::exit(batch_exit_code(success_results.len(), error_count));
+}
@@ -0,0 +1,263 @@
+//! Шифрование (функция `encryption`): файлы вывода age (`--encrypt-output`),
+//! их расшифровка (подкоманда `decrypt`) и зашифрованный вход age или GPG
+//! (`--decrypt-input`).
+
+use super::input::open_reader;
+use super::{EXIT_IO, EXIT_USAGE};
+use age::secrecy::Secret;
+use log::error;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Переменная окружения с паролем — для запуска без терминала.
+pub const PASSPHRASE_ENV: &str = "MNEM_TO_ENTROPY_PASSPHRASE";
+
+/// Пароль шифрования запрашивается один раз на запуск, даже если файлов
+/// несколько (лог ошибок, каталог-приёмник).
+static PASSPHRASE: OnceLock<String> = OnceLock::new();
+
+/// Пароль расшифровки входа — тоже один на все входные файлы.
+static INPUT_PASSPHRASE: OnceLock<String> = OnceLock::new();
+
+/// Парсер `--recipient` для clap: открытый ключ age (`age1...`).
+pub fn parse_recipient(key: &str) -> Result<String, String> {
+    age::x25519::Recipient::from_str(key)
+        .map(|_| key.to_string())
+        .map_err(|e| {
+            tr!(
+                "неверный ключ получателя age '{}': {}",
+                "invalid age recipient key '{}': {}",
+                key,
+                e
+            )
+        })
+}
+
+/// Пароль из [`PASSPHRASE_ENV`] или с терминала без отображения; для
+/// шифрования спрашивается дважды.
+pub fn passphrase(confirm: bool) -> String {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return passphrase;
+    }
+    let passphrase = prompt(&tr!("Пароль: ", "Passphrase: "));
+    if passphrase.is_empty() {
+        error!("{}", tr!("Пароль не может быть пустым", "The passphrase cannot be empty"));
+        std::process::exit(EXIT_USAGE);
+    }
+    if confirm && prompt(&tr!("Повторите пароль: ", "Repeat the passphrase: ")) != passphrase {
+        error!("{}", tr!("Пароли не совпадают", "Passphrases do not match"));
+        std::process::exit(EXIT_USAGE);
+    }
+    passphrase
+}
+
+fn prompt(text: &str) -> String {
+    rpassword::prompt_password(text).unwrap_or_else(|e| {
+        error!(
+            "{}",
+            tr!("Не удалось прочитать пароль: {}", "Failed to read the passphrase: {}", e)
+        );
+        std::process::exit(EXIT_IO);
+    })
+}
+
+/// Поток age дописывает последний блок только в `finish()`; обёртка вызывает
+/// его при освобождении, как сжимающие потоки, чтобы файл не остался обрезанным.
+struct AgeWriter<W: Write>(Option<age::stream::StreamWriter<W>>);
+
+impl<W: Write> Write for AgeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.as_mut().expect("поток age не завершён").write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.as_mut().expect("поток age не завершён").flush()
+    }
+}
+
+impl<W: Write> Drop for AgeWriter<W> {
+    fn drop(&mut self) {
+        if let Some(writer) = self.0.take() {
+            if let Err(e) = writer.finish() {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при завершении зашифрованного файла: {}",
+                        "Failed to finish the encrypted file: {}",
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    }
+}
+
+/// Оборачивает файл шифрующим потоком age: на ключи `recipients` или, если
+/// их нет, по паролю.
+pub fn encrypted(
+    file: fs::File,
+    recipients: &[String],
+) -> std::io::Result<Box<dyn Write + Send>> {
+    let encryptor = if recipients.is_empty() {
+        let passphrase = PASSPHRASE.get_or_init(|| passphrase(true));
+        age::Encryptor::with_user_passphrase(Secret::new(passphrase.clone()))
+    } else {
+        let recipients = recipients
+            .iter()
+            .map(|key| {
+                let recipient = age::x25519::Recipient::from_str(key).expect("ключ проверен при разборе");
+                Box::new(recipient) as Box<dyn age::Recipient + Send>
+            })
+            .collect();
+        age::Encryptor::with_recipients(recipients).expect("список получателей не пуст")
+    };
+    Ok(Box::new(AgeWriter(Some(encryptor.wrap_output(file)?))))
+}
+
+/// Расшифровывает поток age (двоичный или в ASCII-броне): по паролю или
+/// ключами из файлов `identities`.
+pub fn decrypted<R: Read + Send + 'static>(
+    reader: R,
+    identities: &[PathBuf],
+    source: &Path,
+) -> Box<dyn BufRead + Send> {
+    let reader = age::armor::ArmoredReader::new(reader);
+    let decryptor = age::Decryptor::new(reader).unwrap_or_else(|e| {
+        error!("{}", tr!("{:?} — не файл age: {}", "{:?} is not an age file: {}", source, e));
+        std::process::exit(EXIT_IO);
+    });
+    let stream = match decryptor {
+        age::Decryptor::Passphrase(decryptor) => {
+            let passphrase = INPUT_PASSPHRASE.get_or_init(|| passphrase(false));
+            decryptor.decrypt(&Secret::new(passphrase.clone()), None)
+        }
+        age::Decryptor::Recipients(decryptor) => {
+            if identities.is_empty() {
+                error!(
+                    "{}",
+                    tr!(
+                        "{:?} зашифрован на ключ получателя; укажите файл ключа через --identity",
+                        "{:?} is encrypted to a recipient key; pass the key file with --identity",
+                        source
+                    )
+                );
+                std::process::exit(EXIT_USAGE);
+            }
+            let keys: Vec<age::x25519::Identity> =
+                identities.iter().flat_map(|path| load_identities(path)).collect();
+            decryptor.decrypt(keys.iter().map(|key| key as &dyn age::Identity))
+        }
+    };
+    match stream {
+        Ok(stream) => Box::new(BufReader::new(stream)),
+        Err(e) => {
+            error!(
+                "{}",
+                tr!("Не удалось расшифровать {:?}: {}", "Failed to decrypt {:?}: {}", source, e)
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Ключи `AGE-SECRET-KEY-...` из файла; пустые строки и комментарии `#` пропускаются.
+fn load_identities(path: &Path) -> Vec<age::x25519::Identity> {
+    let content = fs::read_to_string(path).unwrap_or_else(|e| {
+        error!(
+            "{}",
+            tr!(
+                "Ошибка при чтении файла ключей {:?}: {}",
+                "Failed to read key file {:?}: {}",
+                path,
+                e
+            )
+        );
+        std::process::exit(EXIT_IO);
+    });
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            age::x25519::Identity::from_str(line).unwrap_or_else(|e| {
+                error!(
+                    "{}",
+                    tr!("Неверный ключ в файле {:?}: {}", "Invalid key in file {:?}: {}", path, e)
+                );
+                std::process::exit(EXIT_USAGE);
+            })
+        })
+        .collect()
+}
+
+/// Входной файл `--decrypt-input`: age расшифровывается здесь же, остальное
+/// (двоичный или текстовый OpenPGP) — через `gpg`, который сам запрашивает
+/// пароль или берёт ключ у gpg-agent.
+pub fn decrypted_input(path: &Path, identities: &[PathBuf]) -> Box<dyn BufRead + Send> {
+    let mut reader = open_reader(path);
+    let is_age = match reader.fill_buf() {
+        Ok(prefix) => {
+            prefix.starts_with(b"age-encryption.org/")
+                || prefix.starts_with(b"-----BEGIN AGE ENCRYPTED FILE-----")
+        }
+        Err(e) => {
+            error!(
+                "{}",
+                tr!("Ошибка при чтении файла {:?}: {}", "Failed to read file {:?}: {}", path, e)
+            );
+            std::process::exit(EXIT_IO);
+        }
+    };
+    if is_age {
+        return decrypted(reader, identities, path);
+    }
+
+    let mut child = Command::new("gpg")
+        .args(["--quiet", "--decrypt"])
+        .arg(path)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| {
+            error!(
+                "{}",
+                tr!(
+                    "Не удалось запустить gpg для {:?}: {}",
+                    "Failed to run gpg for {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        });
+    let stdout = child.stdout.take().expect("stdout gpg перенаправлен");
+    Box::new(BufReader::new(GpgReader { child, stdout }))
+}
+
+/// Вывод `gpg --decrypt`. Код завершения проверяется в конце потока: иначе
+/// неверный пароль выглядел бы как пустой файл.
+struct GpgReader {
+    child: Child,
+    stdout: ChildStdout,
+}
+
+impl Read for GpgReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.stdout.read(buf)?;
+        if read == 0 && !buf.is_empty() {
+            let status = self.child.wait()?;
+            if !status.success() {
+                return Err(std::io::Error::other(tr!(
+                    "gpg завершился с ошибкой ({})",
+                    "gpg failed ({})",
+                    status
+                )));
+            }
+        }
+        Ok(read)
+    }
+}
@@ -0,0 +1,144 @@
+use super::i18n::Localized;
+use super::input::load_lines;
+use super::{parse_language, quiet, EXIT_FAILED, EXIT_USAGE};
+use clap::{Args, Subcommand};
+use log::{error, warn};
+use mnem_to_entropy::{check_entropy, dice_to_entropy, encode_in, entropy_from_bits, DiceError, Die, Language};
+use std::path::PathBuf;
+
+#[derive(Subcommand, Debug)]
+pub enum EntropyCommand {
+    /// Энтропия из бросков кости (d6, d20) или монеты без смещения
+    FromDice(FromDiceArgs),
+    /// Энтропия и мнемоника из битовой строки (броски монеты, вывод --format bits)
+    FromBits(FromBitsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct FromDiceArgs {
+    /// Броски: для d6 и монеты — подряд или через пробел (3615..., HTTH...),
+    /// для d20 — числа через пробел или запятую
+    #[arg(short, long)]
+    pub rolls: Option<String>,
+
+    /// Файл с бросками (строки склеиваются)
+    #[arg(short = 'i', long = "input")]
+    pub input_file: Option<PathBuf>,
+
+    /// Кость: d6, d20 или coin
+    #[arg(long, default_value = "d6")]
+    pub die: Die,
+
+    /// Длина энтропии в битах: 128, 160, 192, 224 или 256
+    #[arg(long, default_value_t = 256)]
+    pub bits: usize,
+
+    /// Корректор фон Неймана против смещения нечестной кости или монеты
+    /// (бросков нужно примерно вчетверо больше)
+    #[arg(long)]
+    pub von_neumann: bool,
+
+    /// Вывести также мнемоническую фразу
+    #[arg(long)]
+    pub mnemonic: bool,
+
+    /// Язык словаря для мнемоники
+    #[arg(long, value_parser = parse_language, default_value = "english")]
+    pub language: Language,
+}
+
+#[derive(Args, Debug)]
+pub struct FromBitsArgs {
+    /// Биты: ENT (128–256), ENT|CS как у --format bits или все 11*N бит фразы;
+    /// пробелы игнорируются
+    #[arg(short, long)]
+    pub bits: Option<String>,
+
+    /// Файл с битовой строкой (строки склеиваются)
+    #[arg(short = 'i', long = "input")]
+    pub input_file: Option<PathBuf>,
+
+    /// Язык словаря для мнемоники
+    #[arg(long, value_parser = parse_language, default_value = "english")]
+    pub language: Language,
+}
+
+pub fn run(command: &EntropyCommand) {
+    match command {
+        EntropyCommand::FromDice(args) => run_from_dice(args),
+        EntropyCommand::FromBits(args) => run_from_bits(args),
+    }
+}
+
+/// Энтропия и, с `--mnemonic`, фраза; с `--quiet` — только фраза или только hex.
+fn print_entropy(entropy: &[u8], with_mnemonic: bool, language: Language) {
+    for warning in check_entropy(entropy) {
+        warn!("⚠️  {}", warning.localized());
+    }
+    let mnemonic = with_mnemonic.then(|| encode_in(language, entropy).expect("длина энтропии проверена"));
+    match (&mnemonic, quiet()) {
+        (Some(mnemonic), true) => println!("{}", mnemonic),
+        (None, true) => println!("{}", hex::encode(entropy)),
+        (_, false) => {
+            println!("{}", tr!("Энтропия: {}", "Entropy: {}", hex::encode(entropy)));
+            if let Some(mnemonic) = &mnemonic {
+                println!("{}", tr!("Мнемоническая фраза: {}", "Mnemonic: {}", mnemonic));
+            }
+        }
+    }
+}
+
+fn run_from_dice(args: &FromDiceArgs) {
+    let rolls = load_lines(
+        args.input_file.as_deref(),
+        args.rolls.as_deref(),
+        &tr!("Введите броски:", "Enter the rolls:"),
+        false,
+        false,
+    )
+    .join(" ");
+
+    let result = match dice_to_entropy(&rolls, args.die, args.bits, args.von_neumann) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e.localized()));
+            std::process::exit(if matches!(e, DiceError::BadLength(_)) {
+                EXIT_USAGE
+            } else {
+                EXIT_FAILED
+            });
+        }
+    };
+
+    if !quiet() {
+        println!(
+            "{}",
+            tr!(
+                "Бросков использовано: {} из {}",
+                "Rolls used: {} of {}",
+                result.rolls_used,
+                result.rolls_total
+            )
+        );
+    }
+    print_entropy(&result.entropy, args.mnemonic, args.language);
+}
+
+fn run_from_bits(args: &FromBitsArgs) {
+    let bits = load_lines(
+        args.input_file.as_deref(),
+        args.bits.as_deref(),
+        &tr!("Введите биты:", "Enter the bits:"),
+        false,
+        false,
+    )
+    .concat();
+
+    match entropy_from_bits(&bits) {
+        Ok(entropy) => print_entropy(&entropy, true, args.language),
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e.localized()));
+            std::process::exit(EXIT_FAILED);
+        }
+    }
+}
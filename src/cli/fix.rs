@@ -0,0 +1,291 @@
+//! Подкоманда `fix`: исправляет набор мнемоник и записывает исправленные
+//! фразы вместо энтропии — для пошаговой починки грязного набора.
+//! Строки выхода соответствуют непустым строкам входа один к одному.
+
+use super::decode::load_wordlist;
+use super::i18n::Localized;
+use super::input::{decompress, load_lines, open_reader, text_reader, Compression};
+use super::pipeline::{self, Selection, Source};
+use super::{batch_exit_code, parse_language, quiet, redacting, report_timing, EXIT_IO, EXIT_USAGE};
+use clap::Args;
+use log::{error, info};
+use mnem_to_entropy::{
+    autocorrect, decode, expand_prefixes, fix_homoglyphs, is_aezeed, lenient_mnemonic,
+    normalize_mnemonic, sanitize_mnemonic, Language, Options, Sanitize, WordCorrection,
+};
+use std::borrow::Cow;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+#[derive(Args, Debug)]
+pub struct FixArgs {
+    /// Мнемоническая фраза
+    #[arg(short, long, env = "MNEM_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Файл с мнемониками (одна фраза на строку)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Option<PathBuf>,
+
+    /// Файл для исправленных фраз (по умолчанию stdout)
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Журнал замен: строка, слово, вид замены, исходное слово и замена
+    /// через табуляцию
+    #[arg(long, value_name = "FILE")]
+    pub changes: Option<PathBuf>,
+
+    /// Исправлять только по словарю указанного языка
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Пользовательский словарь из 2048 слов (одно слово на строку)
+    #[arg(long, value_name = "FILE", conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+
+    /// Терпимый разбор переписанных фраз: нумерация, запятые и лишние пробелы
+    #[arg(long, default_value = "false")]
+    pub lenient: bool,
+
+    /// Очищать строки перед исправлением: all (по умолчанию) или этапы через
+    /// запятую — bom, zero-width, quotes, crlf, punctuation, lowercase
+    #[arg(long, value_name = "STEPS", num_args = 0..=1, default_missing_value = "all")]
+    pub sanitize: Option<Sanitize>,
+}
+
+/// Вид замены слова.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ChangeKind {
+    /// Буквы-двойники заменены латинскими.
+    Homoglyph,
+    /// Сокращение раскрыто до слова словаря.
+    Prefix,
+    /// Опечатка исправлена ближайшим словом.
+    Typo,
+}
+
+impl ChangeKind {
+    fn name(self) -> &'static str {
+        match self {
+            ChangeKind::Homoglyph => "homoglyph",
+            ChangeKind::Prefix => "prefix",
+            ChangeKind::Typo => "typo",
+        }
+    }
+}
+
+/// Замена слова в строке.
+struct Change {
+    kind: ChangeKind,
+    correction: WordCorrection,
+}
+
+/// Исправленная строка.
+struct Fixed {
+    text: String,
+    changes: Vec<Change>,
+    /// Почему фраза не декодируется и после исправлений; `None`, если она верна.
+    failure: Option<String>,
+}
+
+/// Исправляет строку: двойники, затем сокращения, затем опечатки.
+fn fix_line(line: &str, args: &FixArgs, options: &Options) -> Fixed {
+    let mut text = Cow::Borrowed(line);
+    if let Some(sanitize) = &args.sanitize {
+        text = Cow::Owned(sanitize_mnemonic(&text, sanitize).text);
+    }
+    if args.lenient {
+        text = Cow::Owned(lenient_mnemonic(&text));
+    }
+    let mut text = text.into_owned();
+    let mut changes = Vec::new();
+
+    if let Some(fixed) = fix_homoglyphs(&text, options) {
+        let original: Vec<String> =
+            normalize_mnemonic(&text).split_whitespace().map(str::to_string).collect();
+        let replaced: Vec<&str> = fixed.mnemonic.split_whitespace().collect();
+        let mut positions: Vec<usize> = fixed.replaced.iter().map(|confusable| confusable.position).collect();
+        positions.dedup();
+        changes.extend(positions.into_iter().map(|position| Change {
+            kind: ChangeKind::Homoglyph,
+            correction: WordCorrection {
+                position,
+                original: original[position].clone(),
+                replacement: replaced[position].to_string(),
+            },
+        }));
+        text = fixed.mnemonic;
+    }
+    if let Some(expanded) = expand_prefixes(&text, options) {
+        changes.extend(expanded.corrections.into_iter().map(|correction| Change {
+            kind: ChangeKind::Prefix,
+            correction,
+        }));
+        text = expanded.mnemonic;
+    }
+
+    let failure = match decode(&text, options) {
+        Ok(_) => None,
+        Err(_) if is_aezeed(&text) => None,
+        Err(e) => match autocorrect(&text, options) {
+            Some(corrected) => {
+                changes.extend(corrected.corrections.into_iter().map(|correction| Change {
+                    kind: ChangeKind::Typo,
+                    correction,
+                }));
+                text = corrected.mnemonic;
+                None
+            }
+            None => Some(e.localized()),
+        },
+    };
+    Fixed { text, changes, failure }
+}
+
+/// Создаёт файл для записи, завершая программу при ошибке.
+fn create(path: &Path) -> BufWriter<fs::File> {
+    match fs::File::create(path) {
+        Ok(file) => BufWriter::new(file),
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при создании файла {:?}: {}",
+                    "Failed to create file {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Пишет строку, завершая программу при ошибке.
+fn write_line(out: &mut dyn Write, line: &str) {
+    if let Err(e) = writeln!(out, "{}", line) {
+        error!(
+            "{}",
+            tr!("Ошибка при записи результатов: {}", "Failed to write results: {}", e)
+        );
+        std::process::exit(EXIT_IO);
+    }
+}
+
+pub fn run(args: &FixArgs) {
+    if redacting() {
+        error!(
+            "{}",
+            tr!(
+                "fix записывает слова фраз и несовместим с --redact",
+                "fix writes the words of the phrases and cannot be used with --redact"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    let source = match &args.input_file {
+        Some(path) => {
+            let (reader, _) = decompress(open_reader(path), Compression::Auto);
+            Source::reader(text_reader(reader, &format!("{:?}", path)), b'\n')
+        }
+        None => {
+            let lines = load_lines(
+                None,
+                args.mnemonic.as_deref(),
+                &tr!("Введите мнемоническую фразу:", "Enter the mnemonic phrase:"),
+                args.output_file.is_some(),
+                false,
+            );
+            Source::Lines(lines.into_iter().map(Cow::Owned).collect())
+        }
+    };
+
+    let options = Options {
+        language: args.language,
+        wordlist: args.wordlist.as_deref().map(|path| Arc::new(load_wordlist(path))),
+        ..Options::default()
+    };
+
+    let mut output: Box<dyn Write + Send> = match &args.output_file {
+        Some(path) => Box::new(create(path)),
+        None => Box::new(BufWriter::new(std::io::stdout())),
+    };
+    let mut changes_log = args.changes.as_deref().map(create);
+    if let Some(log) = &mut changes_log {
+        write_line(log, "line\tword\tkind\toriginal\treplacement");
+    }
+
+    let (mut valid, mut invalid, mut fixed_lines, mut replaced) = (0, 0, 0, 0);
+    let started = Instant::now();
+    pipeline::run(
+        source,
+        0,
+        Selection::default(),
+        |_, _, line| fix_line(line, args, &options),
+        |idx, _, _, fixed| {
+            write_line(&mut output, &fixed.text);
+            if let Some(log) = &mut changes_log {
+                for change in &fixed.changes {
+                    write_line(
+                        log,
+                        &format!(
+                            "{}\t{}\t{}\t{}\t{}",
+                            idx + 1,
+                            change.correction.position + 1,
+                            change.kind.name(),
+                            change.correction.original,
+                            change.correction.replacement
+                        ),
+                    );
+                }
+            }
+            if !fixed.changes.is_empty() {
+                fixed_lines += 1;
+                replaced += fixed.changes.len();
+            }
+            match &fixed.failure {
+                None => valid += 1,
+                Some(failure) => {
+                    invalid += 1;
+                    if !quiet() {
+                        error!("{}", tr!("Строка {}: {}", "Line {}: {}", idx + 1, failure));
+                    }
+                }
+            }
+        },
+    );
+    if let Err(e) = output.flush() {
+        error!(
+            "{}",
+            tr!("Ошибка при записи результатов: {}", "Failed to write results: {}", e)
+        );
+        std::process::exit(EXIT_IO);
+    }
+    if let Some(Err(e)) = changes_log.as_mut().map(Write::flush) {
+        error!(
+            "{}",
+            tr!("Ошибка при записи журнала замен: {}", "Failed to write the change log: {}", e)
+        );
+        std::process::exit(EXIT_IO);
+    }
+    report_timing(started, valid + invalid);
+
+    if !quiet() {
+        info!(
+            "{}",
+            tr!(
+                "Строк: {}, исправлено строк: {} (замен слов: {}), остались неверными: {}",
+                "Lines: {}, lines fixed: {} ({} words replaced), still invalid: {}",
+                valid + invalid,
+                fixed_lines,
+                replaced,
+                invalid
+            )
+        );
+    }
+    std::process::exit(batch_exit_code(valid, invalid));
+}
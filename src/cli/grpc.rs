@@ -0,0 +1,246 @@
+//! gRPC-вариант серверного режима (`serve-grpc`, функция `grpc`);
+//! описание сервиса — в proto/mnem_to_entropy.proto.
+
+use super::decode::load_wordlist;
+use super::{parse_language, EXIT_IO};
+use super::validate::validate;
+use clap::Args;
+use log::{error, info};
+use mnem_to_entropy::{
+    decode, derive_addresses, language_name, mnemonic_to_seed, AddressType, DerivationPath,
+    Language, Network, Options, RootKeys,
+};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status, Streaming};
+
+pub mod proto {
+    tonic::include_proto!("mnem_to_entropy");
+}
+
+use proto::mnem_to_entropy_server::{MnemToEntropy, MnemToEntropyServer};
+use proto::{
+    Address, DecodeReply, DecodeRequest, DeriveReply, DeriveRequest, ValidateReply, ValidateRequest,
+};
+
+/// Сколько ответов потока может ждать отправки, пока клиент их не прочитал.
+const STREAM_BUFFER: usize = 256;
+
+/// Адресов в ответе Derive, если клиент не указал count.
+const DEFAULT_COUNT: u32 = 20;
+
+#[derive(Args, Debug)]
+pub struct GrpcArgs {
+    /// Адрес и порт для входящих соединений
+    #[arg(long, default_value = "127.0.0.1:50051")]
+    pub bind: SocketAddr,
+
+    /// Наибольший размер сообщения запроса в байтах
+    #[arg(long, value_name = "BYTES", default_value = "1048576")]
+    pub max_message: usize,
+
+    /// Наибольшее число адресов в одном ответе Derive
+    #[arg(long, value_name = "N", default_value = "1000")]
+    pub max_count: u32,
+
+    /// Словарь по умолчанию; запрос может указать свой язык в поле language
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Пользовательский словарь из 2048 слов для всех запросов
+    #[arg(long, value_name = "FILE", conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+}
+
+struct Service {
+    options: Arc<Options>,
+    max_count: u32,
+}
+
+#[tonic::async_trait]
+impl MnemToEntropy for Service {
+    async fn decode(&self, request: Request<DecodeRequest>) -> Result<Response<DecodeReply>, Status> {
+        let request = request.into_inner();
+        let options = request_options(&self.options, request.ignore_checksum, &request.language)?;
+        Ok(Response::new(decode_reply(&request.mnemonic, &options)))
+    }
+
+    async fn validate(
+        &self,
+        request: Request<ValidateRequest>,
+    ) -> Result<Response<ValidateReply>, Status> {
+        let request = request.into_inner();
+        let options = request_options(&self.options, false, &request.language)?;
+        let verdict = validate(&request.mnemonic, &options);
+        Ok(Response::new(ValidateReply {
+            valid: verdict.failure.is_none(),
+            words: verdict.word_count as u32,
+            language: verdict.dictionary.unwrap_or_default().to_string(),
+            reason: verdict.failure.unwrap_or_default(),
+        }))
+    }
+
+    async fn derive(&self, request: Request<DeriveRequest>) -> Result<Response<DeriveReply>, Status> {
+        let request = request.into_inner();
+        let options = request_options(&self.options, false, &request.language)?;
+        let count = match request.count {
+            0 => DEFAULT_COUNT,
+            count if count > self.max_count => {
+                return Err(Status::invalid_argument(format!(
+                    "count больше {}",
+                    self.max_count
+                )))
+            }
+            count => count,
+        };
+        let network = match request.network.as_str() {
+            "" => Network::Bitcoin,
+            name => Network::from_str(name)
+                .map_err(|e| Status::invalid_argument(format!("неверная сеть: {}", e)))?,
+        };
+        let path = DerivationPath::from_str(&request.path)
+            .map_err(|e| Status::invalid_argument(format!("неверный путь: {}", e)))?;
+        let address_type = match request.address_type.as_str() {
+            "" => AddressType::from_path(&path).ok_or_else(|| {
+                Status::invalid_argument("не удалось определить тип адреса по пути, укажите address_type")
+            })?,
+            name => AddressType::from_str(name).map_err(Status::invalid_argument)?,
+        };
+
+        // Деривация — вычисления на процессоре, поэтому не в потоке обработки запросов
+        let reply = tokio::task::spawn_blocking(move || {
+            if let Err(e) = decode(&request.mnemonic, &options) {
+                return DeriveReply {
+                    addresses: Vec::new(),
+                    error: e.to_string(),
+                };
+            }
+            let seed = mnemonic_to_seed(&request.mnemonic, &request.passphrase);
+            let root = RootKeys::from_seed(&seed, network);
+            match derive_addresses(&root.xprv, network, &path, address_type, request.start, count) {
+                Ok(addresses) => DeriveReply {
+                    addresses: addresses
+                        .into_iter()
+                        .map(|derived| Address {
+                            path: derived.path.to_string(),
+                            address: derived.address,
+                        })
+                        .collect(),
+                    error: String::new(),
+                },
+                Err(e) => DeriveReply {
+                    addresses: Vec::new(),
+                    error: e.to_string(),
+                },
+            }
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(reply))
+    }
+
+    type DecodeStreamStream = Pin<Box<dyn Stream<Item = Result<DecodeReply, Status>> + Send>>;
+
+    async fn decode_stream(
+        &self,
+        request: Request<Streaming<DecodeRequest>>,
+    ) -> Result<Response<Self::DecodeStreamStream>, Status> {
+        let mut incoming = request.into_inner();
+        let options = Arc::clone(&self.options);
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+
+        tokio::spawn(async move {
+            loop {
+                let reply = match incoming.message().await {
+                    Ok(Some(request)) => {
+                        request_options(&options, request.ignore_checksum, &request.language)
+                            .map(|options| decode_reply(&request.mnemonic, &options))
+                    }
+                    Ok(None) => break,
+                    Err(status) => Err(status),
+                };
+                let failed = reply.is_err();
+                // Клиент закрыл поток ответов — дальше читать незачем
+                if sender.send(reply).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(
+            Box::pin(ReceiverStream::new(receiver)) as Self::DecodeStreamStream
+        ))
+    }
+}
+
+/// Параметры декодирования для запроса: настройки сервера плюс поля запроса.
+fn request_options(base: &Options, ignore_checksum: bool, language: &str) -> Result<Options, Status> {
+    let mut options = Options {
+        ignore_checksum,
+        ..base.clone()
+    };
+    if !language.is_empty() {
+        if options.wordlist.is_some() {
+            return Err(Status::invalid_argument(
+                "сервер использует пользовательский словарь; поле language недопустимо",
+            ));
+        }
+        options.language = Some(parse_language(language).map_err(Status::invalid_argument)?);
+    }
+    Ok(options)
+}
+
+fn decode_reply(mnemonic: &str, options: &Options) -> DecodeReply {
+    match decode(mnemonic, options) {
+        Ok(decoded) => DecodeReply {
+            entropy: decoded.entropy.to_hex(),
+            language: decoded.language.map(language_name).unwrap_or("custom").to_string(),
+            error: String::new(),
+        },
+        Err(e) => DecodeReply {
+            entropy: String::new(),
+            language: String::new(),
+            error: e.to_string(),
+        },
+    }
+}
+
+pub fn run(args: &GrpcArgs) {
+    let service = Service {
+        options: Arc::new(Options {
+            language: args.language,
+            wordlist: args.wordlist.as_deref().map(|path| Arc::new(load_wordlist(path))),
+            ..Options::default()
+        }),
+        max_count: args.max_count,
+    };
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Не удалось запустить асинхронную среду: {}",
+                    "Failed to start the async runtime: {}",
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    };
+
+    info!("{}", tr!("gRPC-сервер слушает {}", "gRPC server listening on {}", args.bind));
+    let server = tonic::transport::Server::builder()
+        .add_service(MnemToEntropyServer::new(service).max_decoding_message_size(args.max_message))
+        .serve(args.bind);
+    if let Err(e) = runtime.block_on(server) {
+        error!("{}", tr!("Ошибка gRPC-сервера: {}", "gRPC server error: {}", e));
+        std::process::exit(EXIT_IO);
+    }
+}
@@ -0,0 +1,293 @@
+//! История адресов (функция `electrum`): `derive --history-server` спрашивает
+//! у сервера Electrum (`tcp://host:port`) или Esplora (`http://host:port/путь`),
+//! были ли у адреса транзакции. Запросы идут не чаще `--history-rate` в секунду,
+//! ответы кэшируются в памяти и, с `--history-cache`, в файле.
+//!
+//! TLS не поддерживается: сервер должен быть свой (electrs, Fulcrum, esplora)
+//! или доступен через локальный туннель.
+
+use super::{EXIT_IO, EXIT_USAGE};
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::Address;
+use mnem_to_entropy::DerivedAddress;
+use clap::Args;
+use log::{error, warn};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Сколько ждать ответа сервера.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Args, Debug, Clone)]
+pub struct HistoryArgs {
+    /// Проверять историю адресов на сервере: tcp://host:port (Electrum)
+    /// или http://host:port/path (Esplora); только Bitcoin, без TLS
+    #[arg(long, value_name = "URL", conflicts_with_all = ["descriptor", "export"])]
+    pub history_server: Option<String>,
+
+    /// Наибольшее число запросов к серверу в секунду
+    #[arg(long, value_name = "N", default_value_t = 10.0, requires = "history_server")]
+    pub history_rate: f64,
+
+    /// Файл кэша ответов (адрес и число транзакций через табуляцию);
+    /// дописывается по ходу работы
+    #[arg(long, value_name = "FILE", requires = "history_server")]
+    pub history_cache: Option<PathBuf>,
+}
+
+/// Протокол сервера.
+enum Backend {
+    /// Electrum: JSON-RPC по строкам через одно TCP-соединение.
+    Electrum {
+        stream: BufReader<TcpStream>,
+        next_id: u64,
+    },
+    /// Esplora REST: `GET {base}/address/{адрес}`, по соединению на запрос.
+    Esplora { authority: String, base: String },
+}
+
+/// Клиент истории адресов с ограничением частоты и кэшем.
+pub struct History {
+    backend: Backend,
+    cache: HashMap<String, usize>,
+    cache_file: Option<File>,
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl History {
+    /// Подключается к серверу из `--history-server`; `None` без опции.
+    /// Ошибка подключения завершает программу.
+    pub fn connect(args: &HistoryArgs) -> Option<Self> {
+        let url = args.history_server.as_deref()?;
+        if args.history_rate.is_nan() || args.history_rate <= 0.0 {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка: --history-rate должен быть больше нуля",
+                    "Error: --history-rate must be greater than zero"
+                )
+            );
+            std::process::exit(EXIT_USAGE);
+        }
+        let backend = Backend::open(url).unwrap_or_else(|e| {
+            error!(
+                "{}",
+                tr!(
+                    "Не удалось подключиться к серверу истории {}: {}",
+                    "Failed to connect to the history server {}: {}",
+                    url,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        });
+        let (cache, cache_file) = match &args.history_cache {
+            Some(path) => (load_cache(path), Some(open_cache(path))),
+            None => (HashMap::new(), None),
+        };
+        Some(History {
+            backend,
+            cache,
+            cache_file,
+            interval: Duration::from_secs_f64(1.0 / args.history_rate),
+            last: None,
+        })
+    }
+
+    /// Число транзакций каждого адреса; `None` — сервер не ответил
+    /// (предупреждение пишется в журнал).
+    pub fn tx_counts(&mut self, addresses: &[DerivedAddress]) -> Vec<Option<usize>> {
+        addresses
+            .iter()
+            .map(|derived| {
+                self.tx_count(&derived.address)
+                    .map_err(|e| {
+                        warn!(
+                            "{}",
+                            tr!(
+                                "Не удалось узнать историю адреса {}: {}",
+                                "Failed to check the history of {}: {}",
+                                derived.address,
+                                e
+                            )
+                        )
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Число транзакций адреса (подтверждённых и в мемпуле).
+    pub fn tx_count(&mut self, address: &str) -> Result<usize, String> {
+        if let Some(&count) = self.cache.get(address) {
+            return Ok(count);
+        }
+        if let Some(last) = self.last {
+            thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+        self.last = Some(Instant::now());
+
+        let count = self.backend.tx_count(address)?;
+        self.cache.insert(address.to_string(), count);
+        if let Some(file) = &mut self.cache_file {
+            writeln!(file, "{}\t{}", address, count).map_err(|e| e.to_string())?;
+        }
+        Ok(count)
+    }
+}
+
+impl Backend {
+    fn open(url: &str) -> Result<Self, String> {
+        if let Some(authority) = url.strip_prefix("tcp://") {
+            let stream = connect(authority.trim_end_matches('/'))?;
+            let mut backend = Backend::Electrum {
+                stream: BufReader::new(stream),
+                next_id: 0,
+            };
+            // Часть серверов не отвечает на запросы до согласования версии
+            backend.electrum_call("server.version", serde_json::json!(["mnem_to_entropy", "1.4"]))?;
+            Ok(backend)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            let (authority, base) = match rest.find('/') {
+                Some(slash) => (&rest[..slash], rest[slash..].trim_end_matches('/')),
+                None => (rest, ""),
+            };
+            Ok(Backend::Esplora {
+                authority: authority.to_string(),
+                base: base.to_string(),
+            })
+        } else {
+            Err(tr!(
+                "ожидается tcp://host:port (Electrum) или http://host:port/path (Esplora)",
+                "expected tcp://host:port (Electrum) or http://host:port/path (Esplora)"
+            ))
+        }
+    }
+
+    fn tx_count(&mut self, address: &str) -> Result<usize, String> {
+        match self {
+            Backend::Electrum { .. } => {
+                let result = self.electrum_call(
+                    "blockchain.scripthash.get_history",
+                    serde_json::json!([script_hash(address)?]),
+                )?;
+                result
+                    .as_array()
+                    .map(Vec::len)
+                    .ok_or_else(|| tr!("неожиданный ответ сервера", "unexpected server response"))
+            }
+            Backend::Esplora { authority, base } => {
+                let body = http_get(authority, &format!("{}/address/{}", base, address))?;
+                let value: Value = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+                let count = |stats: &str| value[stats]["tx_count"].as_u64();
+                match (count("chain_stats"), count("mempool_stats")) {
+                    (Some(chain), Some(mempool)) => Ok((chain + mempool) as usize),
+                    _ => Err(tr!("неожиданный ответ сервера", "unexpected server response")),
+                }
+            }
+        }
+    }
+
+    /// Запрос JSON-RPC к серверу Electrum; возвращает поле `result`.
+    fn electrum_call(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let Backend::Electrum { stream, next_id } = self else {
+            unreachable!("вызов Electrum для другого протокола");
+        };
+        *next_id += 1;
+        let request = serde_json::json!({ "jsonrpc": "2.0", "id": *next_id, "method": method, "params": params });
+        let mut line = request.to_string();
+        line.push('\n');
+        stream
+            .get_mut()
+            .write_all(line.as_bytes())
+            .map_err(|e| e.to_string())?;
+
+        let mut response = String::new();
+        if stream.read_line(&mut response).map_err(|e| e.to_string())? == 0 {
+            return Err(tr!("сервер закрыл соединение", "the server closed the connection"));
+        }
+        let mut value: Value = serde_json::from_str(&response).map_err(|e| e.to_string())?;
+        match value.get("error") {
+            Some(error) if !error.is_null() => Err(error.to_string()),
+            _ => Ok(value["result"].take()),
+        }
+    }
+}
+
+fn connect(authority: &str) -> Result<TcpStream, String> {
+    let stream = TcpStream::connect(authority).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(|e| e.to_string())?;
+    Ok(stream)
+}
+
+/// GET по HTTP/1.0: ответ без chunked, соединение закрывается сервером.
+fn http_get(authority: &str, path: &str) -> Result<String, String> {
+    let mut stream = connect(authority)?;
+    let request = format!(
+        "GET {} HTTP/1.0\r\nHost: {}\r\nAccept: application/json\r\n\r\n",
+        path, authority
+    );
+    stream.write_all(request.as_bytes()).map_err(|e| e.to_string())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(|e| e.to_string())?;
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| tr!("неверный ответ HTTP", "invalid HTTP response"))?;
+    let status = head.split_whitespace().nth(1).unwrap_or_default();
+    if status != "200" {
+        return Err(format!("HTTP {}: {}", status, body.trim()));
+    }
+    Ok(body.to_string())
+}
+
+/// Хэш скрипта для Electrum: SHA-256 от scriptPubKey в обратном порядке байт.
+fn script_hash(address: &str) -> Result<String, String> {
+    let address = Address::from_str(address)
+        .map_err(|e| e.to_string())?
+        .assume_checked();
+    let mut hash = sha256::Hash::hash(address.script_pubkey().as_bytes()).to_byte_array();
+    hash.reverse();
+    Ok(hex::encode(hash))
+}
+
+/// Записи кэша из прошлых запусков; нечитаемые строки пропускаются.
+fn load_cache(path: &Path) -> HashMap<String, usize> {
+    let Ok(text) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    text.lines()
+        .filter_map(|line| {
+            let (address, count) = line.split_once('\t')?;
+            Some((address.to_string(), count.parse().ok()?))
+        })
+        .collect()
+}
+
+fn open_cache(path: &Path) -> File {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap_or_else(|e| {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при открытии кэша {:?}: {}",
+                    "Failed to open the cache {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        })
+}
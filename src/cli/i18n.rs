@@ -0,0 +1,473 @@
+//! Язык сообщений утилиты (`--ui-lang`): русский или английский. Без флага
+//! язык берётся из локали (LC_ALL, LC_MESSAGES, LANG).
+//!
+//! Каждое сообщение записывается в обоих вариантах прямо в месте вывода
+//! через [`tr!`]; ошибки библиотеки переводятся здесь же ([`Localized`]),
+//! потому что их `Display` — часть API и остаётся русским.
+
+use clap::ValueEnum;
+use mnem_to_entropy::slip39::Slip39Error;
+use mnem_to_entropy::{
+    language_name, AezeedError, BitsError, Cleanup, Confusable, DecodeError, DeriveError, DiceError, EncodeError, EntropyWarning,
+    IndicesError, RecoverError, SeedXorError, ShamirError, SubstrateError, VerifyError, WordlistError,
+    MAX_PLACEHOLDERS, MAX_SEEDXOR_PARTS, MAX_SHAMIR_SHARES, MAX_SS58_PREFIX,
+};
+use std::sync::OnceLock;
+
+/// Язык сообщений.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UiLang {
+    /// English
+    En,
+    /// Русский
+    Ru,
+}
+
+/// Язык, выбранный `--ui-lang`. Пока аргументы не разобраны (сообщения
+/// парсеров clap), действует язык локали.
+static UI_LANG: OnceLock<UiLang> = OnceLock::new();
+
+/// Задаёт язык сообщений из `--ui-lang`; без флага остаётся язык локали.
+pub fn configure_ui_lang(lang: Option<UiLang>) {
+    if let Some(lang) = lang {
+        let _ = UI_LANG.set(lang);
+    }
+}
+
+/// Текущий язык сообщений.
+pub fn ui_lang() -> UiLang {
+    static SYSTEM: OnceLock<UiLang> = OnceLock::new();
+    match UI_LANG.get() {
+        Some(lang) => *lang,
+        None => *SYSTEM.get_or_init(system_lang),
+    }
+}
+
+/// Язык первой непустой переменной локали в порядке приоритета POSIX;
+/// русский только для `ru*`, для остальных локалей — английский.
+fn system_lang() -> UiLang {
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty());
+    match locale {
+        Some(locale) if locale.to_lowercase().starts_with("ru") => UiLang::Ru,
+        _ => UiLang::En,
+    }
+}
+
+/// Сообщение на языке интерфейса: `tr!("русский {}", "English {}", args...)`.
+/// Оба варианта — литералы формата, поэтому аргументы проверяются компилятором.
+macro_rules! tr {
+    ($ru:literal, $en:literal $(, $arg:expr)* $(,)?) => {
+        match $crate::cli::i18n::ui_lang() {
+            $crate::cli::i18n::UiLang::Ru => format!($ru $(, $arg)*),
+            $crate::cli::i18n::UiLang::En => format!($en $(, $arg)*),
+        }
+    };
+}
+
+/// Текст ошибки библиотеки на языке интерфейса.
+pub trait Localized {
+    fn localized(&self) -> String;
+}
+
+/// Первые слова списка — как в `Display` ошибок библиотеки.
+fn first_words(words: &[String]) -> Vec<&String> {
+    words.iter().take(3).collect()
+}
+
+impl Localized for DecodeError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            DecodeError::InvalidWords(words) => {
+                format!("Invalid words (not in any BIP39 wordlist): {:?}", first_words(words))
+            }
+            DecodeError::BadWordCount(count) => format!(
+                "Invalid word count: {} (BIP39 requires 12/15/18/21/24 words)",
+                count
+            ),
+            DecodeError::InvalidChecksum(Some(language)) => format!(
+                "Invalid BIP39 checksum (language: {})",
+                language_name(*language)
+            ),
+            DecodeError::InvalidChecksum(None) => {
+                "Invalid BIP39 checksum (custom wordlist)".to_string()
+            }
+        }
+    }
+}
+
+impl Localized for EncodeError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            EncodeError::BadEntropyLength(bits) => format!(
+                "Invalid entropy length: {} bits (BIP39 requires 128/160/192/224/256 bits)",
+                bits
+            ),
+        }
+    }
+}
+
+impl Localized for RecoverError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            RecoverError::NoPlaceholder => {
+                "No unknown word given: replace it with '?' or '_'".to_string()
+            }
+            RecoverError::TooManyPlaceholders(count) => format!(
+                "{} unknown words; the search supports at most {}",
+                count, MAX_PLACEHOLDERS
+            ),
+            RecoverError::BadWordCount(count) => format!(
+                "Invalid word count: {} (BIP39 requires 12/15/18/21/24 words)",
+                count
+            ),
+            RecoverError::InvalidWords(words) => {
+                format!("Invalid words (not in any BIP39 wordlist): {:?}", first_words(words))
+            }
+            RecoverError::AlreadyValid => "The phrase checksum is already valid".to_string(),
+            RecoverError::NothingToComplete { known, word_count } => format!(
+                "{} words are known and the phrase has {}: nothing to complete",
+                known, word_count
+            ),
+        }
+    }
+}
+
+impl Localized for Slip39Error {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            Slip39Error::UnknownWord(word) => format!("Word not in the SLIP-39 wordlist: {}", word),
+            Slip39Error::TooShort(count) => {
+                format!("Share too short: {} words (minimum 20)", count)
+            }
+            Slip39Error::InvalidPadding => "Invalid share value padding".to_string(),
+            Slip39Error::InvalidChecksum => "Invalid SLIP-39 share checksum".to_string(),
+            Slip39Error::InvalidGroupThreshold => {
+                "Group threshold exceeds the number of groups".to_string()
+            }
+            Slip39Error::NoShares => "No shares given".to_string(),
+            Slip39Error::MismatchedShares => {
+                "Shares belong to different sets (identifier or parameters differ)".to_string()
+            }
+            Slip39Error::ConflictingShares { group, member } => format!(
+                "Share of member {} in group {} given with different values",
+                member + 1,
+                group + 1
+            ),
+            Slip39Error::NotEnoughGroups {
+                have,
+                need,
+                incomplete,
+            } => {
+                let mut text = format!("Not enough groups: {} of {} collected", have, need);
+                for (group, count, threshold) in incomplete {
+                    text += &format!("; group {}: {} of {} shares", group + 1, count, threshold);
+                }
+                text
+            }
+            Slip39Error::DigestMismatch => {
+                "Secret digest mismatch: shares are corrupted or from different sets".to_string()
+            }
+            Slip39Error::InvalidSecretLength(len) => format!(
+                "Invalid secret length: {} bytes (must be even and at least 16)",
+                len
+            ),
+            Slip39Error::InvalidThreshold { threshold, count } => format!(
+                "Invalid {}-of-{} scheme: the threshold must be between 1 and the share count \
+                 (at most 16), and 1 is allowed only for a single share",
+                threshold, count
+            ),
+        }
+    }
+}
+
+impl Localized for AezeedError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            AezeedError::BadWordCount(count) => {
+                format!("Invalid word count: {} (aezeed requires 24 words)", count)
+            }
+            AezeedError::InvalidWords(words) => {
+                format!("Invalid words (not in the English wordlist): {:?}", first_words(words))
+            }
+            AezeedError::UnsupportedVersion(version) => {
+                format!("Unsupported aezeed version: {}", version)
+            }
+            AezeedError::InvalidChecksum => "Invalid aezeed checksum".to_string(),
+        }
+    }
+}
+
+impl Localized for WordlistError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            WordlistError::BadLength(len) => {
+                format!("A wordlist must contain 2048 words, found: {}", len)
+            }
+            WordlistError::DuplicateWord(word) => {
+                format!("Word appears in the wordlist twice: {}", word)
+            }
+        }
+    }
+}
+
+impl Localized for IndicesError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            IndicesError::InvalidNumber(item) => format!("Not a number: {:?}", item),
+            IndicesError::OutOfRange(index) => {
+                format!("Index outside the wordlist: {} (allowed 0–2047)", index)
+            }
+        }
+    }
+}
+
+impl Localized for DeriveError {
+    fn localized(&self) -> String {
+        match self {
+            DeriveError::Bip32(e) => {
+                tr!("Ошибка деривации BIP32: {}", "BIP32 derivation error: {}", e)
+            }
+            DeriveError::NonHardened(index) => tr!(
+                "Ed25519 допускает только hardened-индексы, а в пути есть {}",
+                "Ed25519 allows only hardened indices, but the path contains {}",
+                index
+            ),
+            DeriveError::Unsupported(address_type) => tr!(
+                "Адреса {:?} выводятся не из ключа BIP32",
+                "{:?} addresses are not derived from a BIP32 key",
+                address_type
+            ),
+            DeriveError::BadWordCount(words) => tr!(
+                "BIP85 выводит фразы из 12/15/18/21/24 слов, а не из {}",
+                "BIP85 derives 12/15/18/21/24-word mnemonics, not {}",
+                words
+            ),
+        }
+    }
+}
+
+impl Localized for SeedXorError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            SeedXorError::BadPartCount(count) => format!(
+                "Invalid number of SeedXOR parts: {} (allowed 2–{})",
+                count, MAX_SEEDXOR_PARTS
+            ),
+            SeedXorError::BadLength(bits) => format!(
+                "Invalid entropy length: {} bits (BIP39 requires 128/160/192/224/256 bits)",
+                bits
+            ),
+            SeedXorError::LengthMismatch { expected, actual } => format!(
+                "SeedXOR parts have different lengths: {} and {} bits",
+                expected, actual
+            ),
+            SeedXorError::Random(e) => format!("Random number generator unavailable: {}", e),
+        }
+    }
+}
+
+impl Localized for ShamirError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            ShamirError::BadParameters { threshold, shares } => format!(
+                "Invalid parameters: threshold {} of {} shares (need 2 ≤ threshold ≤ shares ≤ {})",
+                threshold, shares, MAX_SHAMIR_SHARES
+            ),
+            ShamirError::BadLength(bits) => format!(
+                "Invalid entropy length: {} bits (BIP39 requires 128/160/192/224/256 bits)",
+                bits
+            ),
+            ShamirError::BadShare(e) => format!("Invalid share: {}", e),
+            ShamirError::NotEnoughShares { threshold, count } => {
+                format!("Not enough shares: {} of {}", count, threshold)
+            }
+            ShamirError::Inconsistent => "Shares are from different sets: threshold or length differ".to_string(),
+            ShamirError::DuplicateIndex(index) => format!("Share {} is given twice", index),
+            ShamirError::DigestMismatch => {
+                "Secret checksum mismatch: shares are corrupted or from different sets".to_string()
+            }
+            ShamirError::Random(e) => format!("Random number generator unavailable: {}", e),
+        }
+    }
+}
+
+impl Localized for SubstrateError {
+    fn localized(&self) -> String {
+        match self {
+            SubstrateError::InvalidPath(path) => tr!(
+                "Неверный путь Substrate '{}': ожидаются соединения вида //hard и /soft",
+                "Invalid Substrate path '{}': expected junctions like //hard and /soft",
+                path
+            ),
+            SubstrateError::SoftEd25519 => tr!(
+                "Ключи ed25519 допускают только жёсткие соединения (//name)",
+                "ed25519 keys allow only hard junctions (//name)"
+            ),
+            SubstrateError::BadEntropy(length) => tr!(
+                "Неверная длина энтропии для Substrate: {} байт (нужно 16–32, кратно 4)",
+                "Invalid entropy length for Substrate: {} bytes (16–32, a multiple of 4, required)",
+                length
+            ),
+            SubstrateError::BadPrefix(prefix) => tr!(
+                "Префикс SS58 {} вне диапазона 0–{}",
+                "SS58 prefix {} is outside 0–{}",
+                prefix,
+                MAX_SS58_PREFIX
+            ),
+        }
+    }
+}
+
+impl Localized for BitsError {
+    fn localized(&self) -> String {
+        match self {
+            BitsError::InvalidChar(c) => tr!(
+                "Недопустимый символ в битовой строке: {:?}",
+                "Invalid character in the bit string: {:?}",
+                c
+            ),
+            BitsError::BadLength(bits) => tr!(
+                "Неверная длина энтропии: {} бит (BIP39 требует 128/160/192/224/256 бит)",
+                "Invalid entropy length: {} bits (BIP39 requires 128/160/192/224/256 bits)",
+                bits
+            ),
+            BitsError::ChecksumMismatch { expected, actual } => tr!(
+                "Контрольная сумма не совпадает: указано {}, должно быть {}",
+                "Checksum mismatch: got {}, expected {}",
+                actual,
+                expected
+            ),
+        }
+    }
+}
+
+impl Localized for DiceError {
+    fn localized(&self) -> String {
+        match self {
+            DiceError::InvalidRoll(roll) => tr!("Неверный бросок '{}'", "Invalid roll '{}'", roll),
+            DiceError::BadLength(bits) => tr!(
+                "Неверная длина энтропии: {} бит (BIP39 требует 128/160/192/224/256 бит)",
+                "Invalid entropy length: {} bits (BIP39 requires 128/160/192/224/256 bits)",
+                bits
+            ),
+            DiceError::NotEnoughRolls { bits, needed, more } => tr!(
+                "Бросков не хватает: получено {} бит из {}, нужно ещё около {} бросков",
+                "Not enough rolls: got {} of {} bits, about {} more rolls needed",
+                bits,
+                needed,
+                more
+            ),
+        }
+    }
+}
+
+impl Localized for EntropyWarning {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            EntropyWarning::RepeatingPattern { period: 1 } => {
+                "All entropy bytes are the same".to_string()
+            }
+            EntropyWarning::RepeatingPattern { period } => {
+                format!("Entropy repeats a {}-byte pattern", period)
+            }
+            EntropyWarning::TestVector => "Entropy is a BIP39 test vector".to_string(),
+            EntropyWarning::LowShannon { bits_per_byte, max } => format!(
+                "Low Shannon entropy: {:.2} of {:.2} possible bits per byte",
+                bits_per_byte, max
+            ),
+        }
+    }
+}
+
+impl Localized for VerifyError {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            VerifyError::BadEntropyLength(len) => format!(
+                "Round-trip check: {}-byte entropy cannot be encoded into a phrase",
+                len
+            ),
+            VerifyError::WordCount { expected, actual } => format!(
+                "Round-trip check: entropy encodes to {} words instead of {}",
+                actual, expected
+            ),
+            VerifyError::Mismatch {
+                position,
+                expected,
+                actual,
+            } => format!(
+                "Round-trip check: word {} is {} instead of {}",
+                position + 1,
+                actual,
+                expected
+            ),
+        }
+    }
+}
+
+impl Localized for Confusable {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        format!(
+            "word {}: '{}' (U+{:04X}) instead of Latin '{}'",
+            self.position + 1,
+            self.found,
+            self.found as u32,
+            self.latin
+        )
+    }
+}
+
+impl Localized for Cleanup {
+    fn localized(&self) -> String {
+        if ui_lang() == UiLang::Ru {
+            return self.to_string();
+        }
+        match self {
+            Cleanup::Bom => "BOM removed",
+            Cleanup::ZeroWidth => "zero-width characters removed",
+            Cleanup::Quotes => "quotes removed",
+            Cleanup::LineBreaks => "carriage returns replaced",
+            Cleanup::Punctuation => "punctuation removed",
+            Cleanup::Lowercase => "lowercased",
+        }
+        .to_string()
+    }
+}
@@ -0,0 +1,452 @@
+use super::{quiet, EXIT_IO, EXIT_USAGE};
+use chardetng::EncodingDetector;
+use clap::ValueEnum;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use log::{error, info};
+use memmap2::Mmap;
+use mnem_to_entropy::languages_containing;
+use std::fs;
+use std::io::{BufRead, BufReader, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Кодировка входного текста (`--input-encoding`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputEncoding {
+    /// Определить по BOM, нулевым байтам UTF-16 или статистике байтов.
+    Auto,
+    /// Заданная кодировка.
+    Fixed(&'static Encoding),
+}
+
+/// Кодировка входа для всех подкоманд; задаётся один раз при запуске.
+static INPUT_ENCODING: OnceLock<InputEncoding> = OnceLock::new();
+
+/// Парсер значения `--input-encoding` для clap: `auto` или метка WHATWG
+/// (`utf-8`, `utf-16le`, `utf-16be`, `windows-1251`, `koi8-r`, `cp1252`, ...).
+pub fn parse_input_encoding(label: &str) -> Result<InputEncoding, String> {
+    if label.eq_ignore_ascii_case("auto") {
+        return Ok(InputEncoding::Auto);
+    }
+    Encoding::for_label(label.as_bytes())
+        .map(InputEncoding::Fixed)
+        .ok_or_else(|| {
+            tr!(
+                "неизвестная кодировка '{}'; примеры: auto, utf-8, utf-16le, utf-16be, windows-1251, koi8-r",
+                "unknown encoding '{}'; examples: auto, utf-8, utf-16le, utf-16be, windows-1251, koi8-r",
+                label
+            )
+        })
+}
+
+/// Задаёт `--input-encoding` для всех подкоманд.
+pub fn configure_input_encoding(encoding: InputEncoding) {
+    let _ = INPUT_ENCODING.set(encoding);
+}
+
+fn input_encoding() -> InputEncoding {
+    INPUT_ENCODING.get().copied().unwrap_or(InputEncoding::Auto)
+}
+
+/// Кодировка текста по первым байтам: BOM, затем UTF-16 без BOM (каждый
+/// второй байт латиницы нулевой), затем UTF-8, иначе — однобайтовая.
+/// `complete` — байты содержат весь текст, а не только его начало.
+///
+/// На коротких строках статистика chardetng часто путает близкие кодировки
+/// (é в windows-1252 и ė в windows-1257), поэтому её догадка сравнивается
+/// с windows-1252, в которой обычно сохранены французские и испанские фразы:
+/// побеждает та, в которой больше слов из словарей BIP39.
+fn detect_encoding(prefix: &[u8], complete: bool) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(prefix) {
+        return encoding;
+    }
+    let pairs = prefix.len() / 2;
+    let zeros = |offset: usize| {
+        prefix
+            .iter()
+            .skip(offset)
+            .step_by(2)
+            .take(pairs)
+            .filter(|&&b| b == 0)
+            .count()
+    };
+    let (even, odd) = (zeros(0), zeros(1));
+    if pairs >= 2 && odd * 2 > pairs && even * 8 < odd {
+        return UTF_16LE;
+    }
+    if pairs >= 2 && even * 2 > pairs && odd * 8 < even {
+        return UTF_16BE;
+    }
+    match std::str::from_utf8(prefix) {
+        Ok(_) => return UTF_8,
+        // Начало файла обрывается посреди символа UTF-8
+        Err(e) if !complete && e.error_len().is_none() => return UTF_8,
+        Err(_) => {}
+    }
+    let mut detector = EncodingDetector::new();
+    detector.feed(prefix, complete);
+    let guess = detector.guess(None, false);
+    // max_by_key при равенстве берёт последний — догадку chardetng
+    [WINDOWS_1252, guess]
+        .into_iter()
+        .max_by_key(|encoding| {
+            let (text, _) = encoding.decode_without_bom_handling(prefix);
+            text.split_whitespace()
+                .filter(|word| !languages_containing(&[word]).is_empty())
+                .count()
+        })
+        .expect("список кодировок не пуст")
+}
+
+/// Кодировка входа `source`: заданная `--input-encoding` или определённая
+/// по первым байтам. Перекодирование из не-UTF-8 отмечается в журнале.
+fn source_encoding(prefix: &[u8], complete: bool, source: &str) -> &'static Encoding {
+    let encoding = match input_encoding() {
+        InputEncoding::Fixed(encoding) => encoding,
+        InputEncoding::Auto => detect_encoding(prefix, complete),
+    };
+    if encoding != UTF_8 {
+        info!(
+            "{}",
+            tr!(
+                "📄 {}: кодировка {}, перекодируется в UTF-8",
+                "📄 {}: encoding {}, converting to UTF-8",
+                source,
+                encoding.name()
+            )
+        );
+    }
+    encoding
+}
+
+/// Читает текстовый файл целиком в UTF-8 с учётом `--input-encoding`;
+/// BOM отбрасывается.
+pub fn read_text(path: &Path) -> std::io::Result<String> {
+    let bytes = fs::read(path)?;
+    let encoding = source_encoding(&bytes, true, &format!("{:?}", path));
+    let (text, _, _) = encoding.decode(&bytes);
+    Ok(text.into_owned())
+}
+
+/// Перекодирует поток текста `source` в UTF-8 с учётом `--input-encoding`.
+/// Поток UTF-8 без BOM возвращается как есть.
+pub fn text_reader(mut reader: Box<dyn BufRead + Send>, source: &str) -> Box<dyn BufRead + Send> {
+    let (encoding, has_bom) = match reader.fill_buf() {
+        Ok(prefix) => (source_encoding(prefix, false, source), Encoding::for_bom(prefix).is_some()),
+        Err(e) => {
+            error!("{}", tr!("Ошибка при чтении входа: {}", "Failed to read input: {}", e));
+            std::process::exit(EXIT_IO);
+        }
+    };
+    if encoding == UTF_8 && !has_bom {
+        return reader;
+    }
+    Box::new(BufReader::new(
+        DecodeReaderBytesBuilder::new()
+            .encoding(Some(encoding))
+            .bom_override(true)
+            .build(reader),
+    ))
+}
+
+/// Загружает входные строки: из файла, из аргумента командной строки
+/// или, если не задано ни то ни другое, из stdin — интерактивно одну строку
+/// (без отображения набранного, если не задан `echo`) или все строки,
+/// если stdin перенаправлен. С `--quiet` ни приглашение, ни число строк
+/// не выводятся.
+pub fn load_lines(
+    input_file: Option<&Path>,
+    inline: Option<&str>,
+    prompt: &str,
+    announce: bool,
+    echo: bool,
+) -> Vec<String> {
+    if let Some(input_path) = input_file {
+        match read_text(input_path) {
+            Ok(content) => {
+                let data: Vec<String> = content
+                    .lines()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+
+                if announce && !quiet() {
+                    println!("{}", tr!("📂 Загружено строк: {}", "📂 Lines loaded: {}", data.len()));
+                }
+
+                data
+            }
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при чтении файла {:?}: {}",
+                        "Failed to read file {:?}: {}",
+                        input_path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    } else if let Some(line) = inline {
+        vec![line.to_string()]
+    } else if stdin_piped() {
+        text_reader(stdin_reader(), "stdin")
+            .lines()
+            .map(|line| {
+                line.unwrap_or_else(|e| {
+                    error!(
+                        "{}",
+                        tr!("Ошибка при чтении stdin: {}", "Failed to read stdin: {}", e)
+                    );
+                    std::process::exit(EXIT_IO);
+                })
+            })
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()
+    } else {
+        if !quiet() {
+            println!("{}", prompt);
+        }
+        let input = read_interactive(echo).unwrap_or_default();
+        vec![input.trim().to_string()]
+    }
+}
+
+/// Читает строку ввода; `None`, если ввод закрыт. С терминала без `echo`
+/// набранное не отображается — секрет не остаётся на экране и в записи сеанса.
+pub fn read_interactive(echo: bool) -> Option<String> {
+    if !echo && !stdin_piped() {
+        return match rpassword::read_password() {
+            Ok(line) => Some(line),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
+            Err(e) => {
+                error!("{}", tr!("Ошибка при чтении ввода: {}", "Failed to read input: {}", e));
+                std::process::exit(EXIT_IO);
+            }
+        };
+    }
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) => None,
+        Ok(_) => Some(line),
+        Err(e) => {
+            error!("{}", tr!("Ошибка при чтении ввода: {}", "Failed to read input: {}", e));
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Раскрывает шаблоны входных файлов (`dumps/*.txt`); совпадения каждого
+/// шаблона сортируются по имени. Пути без метасимволов остаются как есть.
+pub fn expand_inputs(patterns: &[PathBuf]) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        let text = pattern.to_string_lossy();
+        if !text.contains(['*', '?', '[']) {
+            paths.push(pattern.clone());
+            continue;
+        }
+
+        let matches = match glob::glob(&text) {
+            Ok(matches) => matches,
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!("Неверный шаблон {:?}: {}", "Invalid pattern {:?}: {}", text, e)
+                );
+                std::process::exit(EXIT_USAGE);
+            }
+        };
+        let mut found: Vec<PathBuf> = matches
+            .map(|entry| {
+                entry.unwrap_or_else(|e| {
+                    error!(
+                        "{}",
+                        tr!(
+                            "Ошибка при чтении {:?}: {}",
+                            "Failed to read {:?}: {}",
+                            e.path(),
+                            e.error()
+                        )
+                    );
+                    std::process::exit(EXIT_IO);
+                })
+            })
+            .filter(|path| path.is_file())
+            .collect();
+        if found.is_empty() {
+            error!(
+                "{}",
+                tr!(
+                    "По шаблону {:?} не найдено ни одного файла",
+                    "No files match the pattern {:?}",
+                    text
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+        found.sort();
+        paths.append(&mut found);
+    }
+    paths
+}
+
+/// Отображает файл в память, завершая программу при ошибке.
+pub fn map_file(path: &Path) -> Mmap {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            error!(
+                "{}",
+                tr!("Ошибка при чтении файла {:?}: {}", "Failed to read file {:?}: {}", path, e)
+            );
+            std::process::exit(EXIT_IO);
+        }
+    };
+    // Файл не должен изменяться, пока идёт обработка
+    match unsafe { Mmap::map(&file) } {
+        Ok(mmap) => mmap,
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при отображении файла {:?} в память: {}",
+                    "Failed to map file {:?} into memory: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// Текст отображённого файла без копирования; метка порядка байтов в начале
+/// отбрасывается, как при обычном чтении. Записи режутся по `delimiter`
+/// лениво, уже при обработке.
+pub fn mapped_text<'a>(mmap: &'a Mmap, path: &Path, delimiter: u8, announce: bool) -> &'a str {
+    let content = match std::str::from_utf8(mmap) {
+        Ok(content) => content,
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Файл {:?} не в кодировке UTF-8 ({}); без --mmap он будет перекодирован",
+                    "File {:?} is not valid UTF-8 ({}); it is converted when read without --mmap",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    };
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    if announce && !quiet() {
+        let count = mapped_records(content, delimiter).count();
+        println!("{}", tr!("📂 Загружено строк: {}", "📂 Lines loaded: {}", count));
+    }
+
+    content
+}
+
+/// Непустые записи текста до `delimiter` — срезы без копирования.
+pub fn mapped_records(content: &str, delimiter: u8) -> impl Iterator<Item = &str> {
+    content
+        .split(delimiter as char)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Открывает файл для построчного чтения, завершая программу при ошибке.
+pub fn open_reader(path: &Path) -> Box<dyn BufRead + Send> {
+    match fs::File::open(path) {
+        Ok(file) => Box::new(BufReader::new(file)),
+        Err(e) => {
+            error!(
+                "{}",
+                tr!("Ошибка при чтении файла {:?}: {}", "Failed to read file {:?}: {}", path, e)
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// stdin перенаправлен (канал или файл), а не подключён к терминалу.
+pub fn stdin_piped() -> bool {
+    !std::io::stdin().is_terminal()
+}
+
+/// stdin для построчного чтения.
+pub fn stdin_reader() -> Box<dyn BufRead + Send> {
+    Box::new(BufReader::new(std::io::stdin()))
+}
+
+/// Сжатие входного потока.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// Определить по сигнатуре в начале потока
+    Auto,
+    /// Без сжатия
+    #[value(name = "none")]
+    Plain,
+    /// gzip
+    Gz,
+    /// Zstandard
+    Zst,
+}
+
+impl Compression {
+    /// Сжатие по первым байтам потока.
+    pub fn detect(prefix: &[u8]) -> Compression {
+        if prefix.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gz
+        } else if prefix.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zst
+        } else {
+            Compression::Plain
+        }
+    }
+}
+
+/// Оборачивает поток распаковщиком. При `Auto` сжатие определяется
+/// по сигнатуре; возвращается также фактически применённое сжатие.
+pub fn decompress(
+    mut reader: Box<dyn BufRead + Send>,
+    compression: Compression,
+) -> (Box<dyn BufRead + Send>, Compression) {
+    let compression = match compression {
+        Compression::Auto => match reader.fill_buf() {
+            Ok(prefix) => Compression::detect(prefix),
+            Err(e) => {
+                error!("{}", tr!("Ошибка при чтении входа: {}", "Failed to read input: {}", e));
+                std::process::exit(EXIT_IO);
+            }
+        },
+        other => other,
+    };
+
+    let reader: Box<dyn BufRead + Send> = match compression {
+        Compression::Auto | Compression::Plain => reader,
+        Compression::Gz => Box::new(BufReader::new(flate2::read::MultiGzDecoder::new(reader))),
+        Compression::Zst => match zstd::stream::read::Decoder::new(reader) {
+            Ok(decoder) => Box::new(BufReader::new(decoder)),
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при инициализации распаковки zstd: {}",
+                        "Failed to initialize zstd decompression: {}",
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        },
+    };
+    (reader, compression)
+}
@@ -0,0 +1,111 @@
+//! Диагностические сообщения утилиты через `log`: ошибки, итоги, время
+//! обработки и подробности строк. Результаты по-прежнему пишутся в stdout
+//! напрямую, а сообщения — в stderr и, с `--log-file`, в файл.
+//!
+//! В stderr сообщение выводится как есть, в файл — с отметкой времени UTC
+//! и уровнем.
+
+use super::progress::suspended;
+use super::EXIT_IO;
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+struct Logger {
+    level: LevelFilter,
+    file: Option<Mutex<File>>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        suspended(|| eprintln!("{}", record.args()));
+        if let Some(file) = &self.file {
+            let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+            // Ведущие переводы строк разделяют блоки в консоли, в файле они лишние
+            let message = record.args().to_string();
+            let _ = writeln!(file, "{} {:<5} {}", timestamp(), record.level(), message.trim());
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = &self.file {
+            let _ = file.lock().unwrap_or_else(|e| e.into_inner()).flush();
+        }
+    }
+}
+
+/// Включает журнал: уровень из `--log-level`, а без него — из `-q` (только
+/// ошибки), `-v` (debug: время обработки) и `-vv` (trace: итог каждой строки).
+pub fn configure_logging(level: Option<LevelFilter>, quiet: bool, verbose: u8, file: Option<&Path>) {
+    let level = level.unwrap_or(match (quiet, verbose) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Info,
+        (false, 1) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    });
+    let file = file.map(|path| {
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => Mutex::new(file),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    tr!(
+                        "Ошибка при открытии журнала {:?}: {}",
+                        "Failed to open log file {:?}: {}",
+                        path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    });
+    if log::set_boxed_logger(Box::new(Logger { level, file })).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+/// Текущее время UTC в формате RFC 3339 с миллисекундами.
+fn timestamp() -> String {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let seconds = elapsed.as_secs();
+    let (year, month, day) = civil_from_days((seconds / 86400) as i64);
+    let time = seconds % 86400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+/// Дата григорианского календаря по числу дней от 1970-01-01
+/// (алгоритм Говарда Хиннанта).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,113 @@
+//! Метрики HTTP-сервера (`serve`, `GET /metrics`) в текстовом формате
+//! Prometheus: запросы по пути и коду ответа, фразы по результату и время
+//! обработки `POST /decode`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Верхние границы корзин гистограммы времени обработки, в секундах.
+const LATENCY_BUCKETS: [f64; 10] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+/// Гистограмма: число наблюдений в каждой корзине (не накопительно), сумма и число.
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    /// Запросы по пути (`/decode`, `/metrics`, `other`) и коду ответа.
+    requests: BTreeMap<(&'static str, u16), u64>,
+    /// Успешно декодированные фразы.
+    successes: u64,
+    /// Ошибки фраз по категориям, как в `--stats`.
+    failures: BTreeMap<&'static str, u64>,
+    /// Время обработки `POST /decode`.
+    latency: Histogram,
+}
+
+/// Счётчики сервера; фразы пачки считаются из потоков rayon.
+#[derive(Default)]
+pub struct Metrics {
+    counters: Mutex<Counters>,
+}
+
+impl Metrics {
+    pub fn request(&self, path: &'static str, status: u16) {
+        *self.lock().requests.entry((path, status)).or_default() += 1;
+    }
+
+    pub fn success(&self) {
+        self.lock().successes += 1;
+    }
+
+    pub fn failure(&self, kind: &'static str) {
+        *self.lock().failures.entry(kind).or_default() += 1;
+    }
+
+    pub fn latency(&self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        let latency = &mut self.lock().latency;
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|&bound| seconds <= bound) {
+            latency.buckets[bucket] += 1;
+        }
+        latency.sum += seconds;
+        latency.count += 1;
+    }
+
+    /// Метрики в текстовом формате Prometheus 0.0.4.
+    pub fn render(&self) -> String {
+        let counters = self.lock();
+        let mut text = String::new();
+
+        text += "# HELP mnem_http_requests_total HTTP requests by path and status code.\n";
+        text += "# TYPE mnem_http_requests_total counter\n";
+        for ((path, status), count) in &counters.requests {
+            let _ = writeln!(
+                text,
+                "mnem_http_requests_total{{path=\"{}\",status=\"{}\"}} {}",
+                path, status, count
+            );
+        }
+
+        text += "# HELP mnem_decode_success_total Mnemonics decoded successfully.\n";
+        text += "# TYPE mnem_decode_success_total counter\n";
+        let _ = writeln!(text, "mnem_decode_success_total {}", counters.successes);
+
+        text += "# HELP mnem_decode_failures_total Mnemonics that failed to decode, by category.\n";
+        text += "# TYPE mnem_decode_failures_total counter\n";
+        for (kind, count) in &counters.failures {
+            let _ = writeln!(text, "mnem_decode_failures_total{{category=\"{}\"}} {}", kind, count);
+        }
+
+        let latency = &counters.latency;
+        text += "# HELP mnem_decode_request_duration_seconds Time to handle POST /decode.\n";
+        text += "# TYPE mnem_decode_request_duration_seconds histogram\n";
+        let mut cumulative = 0;
+        for (bound, count) in LATENCY_BUCKETS.iter().zip(&latency.buckets) {
+            cumulative += count;
+            let _ = writeln!(
+                text,
+                "mnem_decode_request_duration_seconds_bucket{{le=\"{}\"}} {}",
+                bound, cumulative
+            );
+        }
+        let _ = writeln!(
+            text,
+            "mnem_decode_request_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            latency.count
+        );
+        let _ = writeln!(text, "mnem_decode_request_duration_seconds_sum {}", latency.sum);
+        let _ = writeln!(text, "mnem_decode_request_duration_seconds_count {}", latency.count);
+
+        text
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Counters> {
+        self.counters.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
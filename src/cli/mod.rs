@@ -0,0 +1,228 @@
+// Первым: макрос tr! нужен остальным модулям
+#[macro_use]
+pub mod i18n;
+
+pub mod analyze;
+pub mod bip85;
+pub mod checkpoint;
+#[cfg(feature = "database")]
+pub mod database;
+pub mod decode;
+#[cfg(feature = "encryption")]
+pub mod decrypt;
+pub mod derive;
+pub mod diff;
+pub mod duplicates;
+pub mod encode;
+#[cfg(feature = "encryption")]
+pub mod encryption;
+pub mod entropy;
+pub mod fix;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "electrum")]
+pub mod history;
+pub mod input;
+pub mod logging;
+#[cfg(feature = "server")]
+pub mod metrics;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+pub mod output;
+pub mod pipeline;
+pub mod progress;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod recover;
+pub mod seedxor;
+pub mod shamir;
+#[cfg(feature = "server")]
+pub mod serve;
+pub mod slip39;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod template;
+pub mod validate;
+pub mod watch;
+pub mod wordlist;
+
+use log::{debug, error};
+use mnem_to_entropy::{language_from_name, normalize_mnemonic, Language};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// Часть записей не обработана.
+pub const EXIT_PARTIAL: i32 = 1;
+/// Не обработана ни одна запись (или единственная операция завершилась ошибкой).
+pub const EXIT_FAILED: i32 = 2;
+/// Ошибка ввода-вывода: файл не читается или не пишется, сбой системы.
+pub const EXIT_IO: i32 = 3;
+/// Неверные аргументы или несовместимые опции.
+pub const EXIT_USAGE: i32 = 4;
+
+/// Маскировать фразы в выводе и логах (`--redact`); задаётся один раз при запуске.
+static REDACT: AtomicBool = AtomicBool::new(false);
+
+/// Только результаты в stdout (`--quiet`); задаётся один раз при запуске.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Парсер значения `--language` для clap.
+pub fn parse_language(name: &str) -> Result<Language, String> {
+    language_from_name(name).ok_or_else(|| {
+        tr!(
+            "неизвестный язык '{}'; допустимые: english, japanese, spanish, french, italian, \
+             korean, chinese-simplified, chinese-traditional, czech, portuguese",
+            "unknown language '{}'; allowed: english, japanese, spanish, french, italian, \
+             korean, chinese-simplified, chinese-traditional, czech, portuguese",
+            name
+        )
+    })
+}
+
+/// Код завершения пакетной обработки: 0, если ошибок нет (или записей нет
+/// вовсе), [`EXIT_FAILED`], если не удалась ни одна запись, иначе [`EXIT_PARTIAL`].
+pub fn batch_exit_code(success_count: usize, error_count: usize) -> i32 {
+    match (success_count, error_count) {
+        (_, 0) => 0,
+        (0, _) => EXIT_FAILED,
+        _ => EXIT_PARTIAL,
+    }
+}
+
+/// Настраивает глобальный пул rayon. Без `--threads` rayon сам учитывает
+/// переменную RAYON_NUM_THREADS.
+pub fn configure_threads(threads: Option<usize>) {
+    let Some(threads) = threads else {
+        return;
+    };
+    if threads == 0 {
+        error!(
+            "{}",
+            tr!("--threads должно быть больше нуля", "--threads must be greater than zero")
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    if let Err(e) = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+    {
+        error!(
+            "{}",
+            tr!("Не удалось создать пул потоков: {}", "Failed to create the thread pool: {}", e)
+        );
+        std::process::exit(EXIT_IO);
+    }
+}
+
+/// Закрепляет в памяти все текущие и будущие страницы процесса (`--mlock`),
+/// чтобы секреты не выгружались в swap. Ошибка завершает программу: молча
+/// работать без защиты, о которой попросили, нельзя.
+pub fn lock_memory(enabled: bool) {
+    if !enabled {
+        return;
+    }
+    #[cfg(unix)]
+    {
+        // mlockall не принимает указателей и меняет только атрибуты страниц
+        let result = unsafe { libc::mlockall(libc::MCL_CURRENT | libc::MCL_FUTURE) };
+        if result != 0 {
+            error!(
+                "{}",
+                tr!(
+                    "Не удалось закрепить память (--mlock): {}",
+                    "Failed to lock memory (--mlock): {}",
+                    std::io::Error::last_os_error()
+                )
+            );
+            error!(
+                "{}",
+                tr!(
+                    "Увеличьте лимит RLIMIT_MEMLOCK (ulimit -l) или запустите с CAP_IPC_LOCK",
+                    "Raise RLIMIT_MEMLOCK (ulimit -l) or run with CAP_IPC_LOCK"
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        error!(
+            "{}",
+            tr!("--mlock поддерживается только в Unix", "--mlock is supported only on Unix")
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+}
+
+/// Включает маскировку фраз (`--redact`) для всех подкоманд.
+pub fn configure_redaction(enabled: bool) {
+    REDACT.store(enabled, Ordering::Relaxed);
+}
+
+/// Включена ли маскировка фраз.
+pub fn redacting() -> bool {
+    REDACT.load(Ordering::Relaxed)
+}
+
+/// Задаёт `--quiet` для всех подкоманд; подробность сообщений (`-v`, `-vv`)
+/// задаёт уровень журнала, см. [`logging::configure_logging`].
+pub fn configure_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// `--quiet`: в stdout только результаты, без заголовков, итогов и предупреждений.
+pub fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Сообщает (уровень debug, `-v`) время обработки `count` записей и скорость.
+pub fn report_timing(started: Instant, count: usize) {
+    if !log::log_enabled!(log::Level::Debug) {
+        return;
+    }
+    let seconds = started.elapsed().as_secs_f64();
+    let rate = if seconds > 0.0 { count as f64 / seconds } else { 0.0 };
+    debug!(
+        "{}",
+        tr!(
+            "⏱ Время: {:.3} с, записей: {}, {:.0} в секунду",
+            "⏱ Time: {:.3} s, entries: {}, {:.0} per second",
+            seconds,
+            count,
+            rate
+        )
+    );
+}
+
+/// SHA-256 фразы в hex; фраза приводится к NFKD, пробелы между словами нормализуются.
+pub fn mnemonic_hash(mnemonic: &str) -> String {
+    let normalized = normalize_mnemonic(mnemonic).split_whitespace().collect::<Vec<_>>().join(" ");
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// Фраза для вывода: как есть или, с `--redact`, первое и последнее слово,
+/// число слов и начало [`mnemonic_hash`] — этого хватает, чтобы сопоставить
+/// строку с исходной, не раскрывая фразу.
+pub fn shown(mnemonic: &str) -> Cow<'_, str> {
+    if !redacting() {
+        return Cow::Borrowed(mnemonic);
+    }
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    let hash = mnemonic_hash(mnemonic);
+    Cow::Owned(match words.as_slice() {
+        [first, .., last] => tr!(
+            "{} … {} ({} сл., sha256:{})",
+            "{} … {} ({} words, sha256:{})",
+            first,
+            last,
+            words.len(),
+            &hash[..8]
+        ),
+        _ => format!("… (sha256:{})", &hash[..8]),
+    })
+}
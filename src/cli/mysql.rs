@@ -0,0 +1,83 @@
+//! Вывод результатов в MySQL (`--output-db mysql://…`, функция `mysql`).
+//!
+//! Строки вставляются многострочными INSERT внутри одной транзакции на пачку.
+
+use super::database::{Backend, OnConflict, Row};
+use mysql::prelude::Queryable;
+use mysql::{Conn, TxOpts, Value};
+
+/// Строк в одном INSERT: 5 параметров на строку, предел протокола — 65 535.
+const ROWS_PER_STATEMENT: usize = 1_000;
+
+pub struct MysqlBackend {
+    connection: Conn,
+    table: String,
+    on_conflict: OnConflict,
+}
+
+impl MysqlBackend {
+    pub fn connect(url: &str, table: &str, on_conflict: OnConflict) -> Result<Self, mysql::Error> {
+        let mut connection = Conn::new(url)?;
+        connection.query_drop(format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                mnemonic_hash CHAR(64) PRIMARY KEY,
+                entropy TEXT,
+                status VARCHAR(8) NOT NULL,
+                error TEXT,
+                timestamp BIGINT NOT NULL
+            )",
+            table
+        ))?;
+        Ok(MysqlBackend {
+            connection,
+            table: table.to_string(),
+            on_conflict,
+        })
+    }
+
+    fn statement(&self, rows: usize) -> String {
+        let values = vec!["(?, ?, ?, ?, ?)"; rows].join(", ");
+        match self.on_conflict {
+            OnConflict::Update => format!(
+                "INSERT INTO {} (mnemonic_hash, entropy, status, error, timestamp) VALUES {} \
+                 ON DUPLICATE KEY UPDATE entropy = VALUES(entropy), status = VALUES(status), \
+                 error = VALUES(error), timestamp = VALUES(timestamp)",
+                self.table, values
+            ),
+            OnConflict::Ignore => format!(
+                "INSERT IGNORE INTO {} (mnemonic_hash, entropy, status, error, timestamp) VALUES {}",
+                self.table, values
+            ),
+        }
+    }
+}
+
+impl Backend for MysqlBackend {
+    fn insert(&mut self, rows: &[Row]) -> Result<(), Box<dyn std::error::Error>> {
+        let statements: Vec<(String, Vec<Value>)> = rows
+            .chunks(ROWS_PER_STATEMENT)
+            .map(|chunk| {
+                let params = chunk
+                    .iter()
+                    .flat_map(|row| {
+                        [
+                            Value::from(row.mnemonic_hash.clone()),
+                            Value::from(row.entropy.clone()),
+                            Value::from(row.status().to_string()),
+                            Value::from(row.error.clone()),
+                            Value::from(row.timestamp),
+                        ]
+                    })
+                    .collect();
+                (self.statement(chunk.len()), params)
+            })
+            .collect();
+
+        let mut transaction = self.connection.start_transaction(TxOpts::default())?;
+        for (statement, params) in statements {
+            transaction.exec_drop(statement, params)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use super::EXIT_IO;
+use clap::ValueEnum;
+use log::error;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Сжатие выходных файлов.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputCompression {
+    /// gzip
+    Gz,
+    /// Zstandard
+    #[value(alias = "zst")]
+    Zstd,
+}
+
+impl OutputCompression {
+    /// Расширение, которое добавляется к имени сжатого файла.
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputCompression::Gz => "gz",
+            OutputCompression::Zstd => "zst",
+        }
+    }
+}
+
+/// Уровень сжатия zstd: быстрый, но заметно лучше gzip по размеру.
+const ZSTD_LEVEL: i32 = 3;
+
+/// Оборачивает файл сжимающим потоком. Поток завершается при освобождении,
+/// поэтому писатель нужно отпустить до выхода из программы. Дозапись
+/// в сжатый файл добавляет новый кадр (gzip и zstd допускают склейку).
+pub fn compressed<W: Write + Send + 'static>(
+    file: W,
+    compression: Option<OutputCompression>,
+) -> Box<dyn Write + Send> {
+    match compression {
+        None => Box::new(file),
+        Some(OutputCompression::Gz) => Box::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        )),
+        Some(OutputCompression::Zstd) => match zstd::stream::write::Encoder::new(file, ZSTD_LEVEL) {
+            Ok(encoder) => Box::new(encoder.auto_finish()),
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при инициализации сжатия zstd: {}",
+                        "Failed to initialize zstd compression: {}",
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        },
+    }
+}
+
+/// Файл вывода, который появляется под своим именем только после успешного
+/// завершения: до этого данные пишутся во временный файл в том же каталоге,
+/// поэтому при сбое прежний результат не оказывается обрезанным.
+pub struct Staged {
+    temp: PathBuf,
+    path: PathBuf,
+}
+
+impl Staged {
+    /// Создаёт временный файл `.NAME.tmp` рядом с `path`.
+    pub fn create(path: &Path) -> std::io::Result<(fs::File, Staged)> {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let temp = path.with_file_name(format!(".{}.tmp", name));
+        let file = fs::File::create(&temp)?;
+        Ok((
+            file,
+            Staged {
+                temp,
+                path: path.to_path_buf(),
+            },
+        ))
+    }
+
+    /// Переименовывает временный файл в итоговый. Писатель к этому моменту
+    /// должен быть освобождён, чтобы сжатый поток успел завершиться.
+    pub fn commit(self) {
+        if let Err(e) = fs::rename(&self.temp, &self.path) {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при сохранении файла {:?}: {}",
+                    "Failed to save file {:?}: {}",
+                    self.path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
@@ -0,0 +1,333 @@
+//! Конвейер пакетной обработки: чтение → параллельная обработка → запись.
+//!
+//! Этапы связаны каналами ограниченной ёмкости, поэтому в памяти
+//! одновременно находится лишь несколько блоков строк и результатов,
+//! независимо от размера входа. Результаты уходят на запись по одному, как
+//! только готовы, а этап записи восстанавливает порядок входа небольшим
+//! буфером ([`Reorder`]).
+
+use super::input::mapped_records;
+use super::EXIT_IO;
+use clap::ValueEnum;
+use log::error;
+use mnem_to_entropy::normalize_mnemonic;
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::io::BufRead;
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::thread;
+
+/// Строк в одном блоке, передаваемом между этапами.
+const CHUNK_SIZE: usize = 4096;
+/// Сколько блоков может ожидать в каждом канале.
+const CHANNEL_CAPACITY: usize = 4;
+
+/// Источник входных строк.
+pub enum Source<'a> {
+    /// Строки уже в памяти (аргумент, stdin или изображение с QR-кодом).
+    Lines(Vec<Cow<'a, str>>),
+    /// Текст отображённого файла; записи до `delimiter` режутся по мере
+    /// обработки, пустые пропускаются.
+    Mapped { content: &'a str, delimiter: u8 },
+    /// Потоки, читаемые по очереди по записям до `delimiter` по мере
+    /// обработки; пустые записи пропускаются.
+    Readers {
+        readers: Vec<Box<dyn BufRead + Send + 'a>>,
+        delimiter: u8,
+    },
+}
+
+impl<'a> Source<'a> {
+    /// Поток записей, разделённых байтом `delimiter` (обычно `\n`).
+    pub fn reader(reader: Box<dyn BufRead + Send + 'a>, delimiter: u8) -> Self {
+        Source::Readers {
+            readers: vec![reader],
+            delimiter,
+        }
+    }
+}
+
+/// Отбрасывание повторяющихся строк (`--dedupe`).
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dedupe {
+    /// Строки совпадают побайтно
+    Exact,
+    /// Совпадают слова после NFKD; число пробелов между ними не учитывается
+    Normalized,
+}
+
+impl Dedupe {
+    /// Ключ строки — SHA-256, чтобы множество уже встреченных строк не хранило
+    /// сами фразы.
+    fn key(self, line: &str) -> Vec<u8> {
+        match self {
+            Dedupe::Exact => Sha256::digest(line.as_bytes()).to_vec(),
+            Dedupe::Normalized => {
+                let words = normalize_mnemonic(line).split_whitespace().collect::<Vec<_>>().join(" ");
+                Sha256::digest(words.as_bytes()).to_vec()
+            }
+        }
+    }
+}
+
+/// Условие отбора строки.
+pub type Keep<'a> = Box<dyn Fn(&str) -> bool + Send + 'a>;
+/// Получатель строк, не прошедших отбор.
+pub type Rejected<'a> = Box<dyn FnMut(&str) + Send + 'a>;
+
+/// Случайная выборка строк (`--sample`): каждая строка берётся с вероятностью
+/// `rate` независимо от других. Генератор SplitMix64 — выборка с одним
+/// зерном (`--seed`) повторяется от запуска к запуску.
+pub struct Sample {
+    rate: f64,
+    state: u64,
+}
+
+impl Sample {
+    /// Выборка с долей `rate`; без `seed` зерно берётся из системного
+    /// генератора случайных чисел.
+    pub fn new(rate: f64, seed: Option<u64>) -> Self {
+        let state = seed.unwrap_or_else(|| {
+            let mut bytes = [0u8; 8];
+            getrandom::getrandom(&mut bytes).expect("системный генератор случайных чисел недоступен");
+            u64::from_le_bytes(bytes)
+        });
+        Sample { rate, state }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Берётся ли очередная строка.
+    fn pick(&mut self) -> bool {
+        ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < self.rate
+    }
+}
+
+/// Отбор строк до обработки.
+#[derive(Default)]
+pub struct Selection<'a> {
+    /// Сквозной индекс, на котором чтение останавливается (`--take`).
+    pub end: Option<usize>,
+    /// Случайная выборка (`--sample`).
+    pub sample: Option<Sample>,
+    /// Отбрасывать повторы (`--dedupe`).
+    pub dedupe: Option<Dedupe>,
+    /// Обрабатывать только строки, для которых условие истинно
+    /// (`--filter-words`).
+    pub keep: Option<Keep<'a>>,
+    /// Получает строки, не прошедшие `keep`, в порядке входа.
+    pub rejected: Option<Rejected<'a>>,
+}
+
+/// Сколько строк отброшено до обработки.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Dropped {
+    /// Повторы (`--dedupe`).
+    pub duplicates: usize,
+    /// Строки, не прошедшие отбор (`--filter-words`).
+    pub filtered: usize,
+    /// Строки, не попавшие в выборку (`--sample`).
+    pub unsampled: usize,
+}
+
+/// Откуда взята строка: номер потока в источнике и номер непустой строки
+/// в нём (оба с нуля).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Origin {
+    pub file: usize,
+    pub line: usize,
+}
+
+/// Строка входа: сквозной индекс, происхождение и текст.
+type Item<'a> = (usize, Origin, Cow<'a, str>);
+/// Блок строк с порядковым номером первой из них среди переданных на
+/// обработку (без пропущенных и повторов — номера идут подряд).
+type Chunk<'a> = (usize, Vec<Item<'a>>);
+/// Результат строки с её порядковым номером.
+type Done<'a, T> = (usize, Item<'a>, T);
+
+/// Буфер записи: результаты приходят в порядке готовности, а отдаются
+/// в порядке входа. Блоки обрабатываются по очереди, поэтому в буфере
+/// не больше одного блока.
+struct Reorder<'a, T> {
+    next: usize,
+    pending: BTreeMap<usize, (Item<'a>, T)>,
+}
+
+impl<'a, T> Reorder<'a, T> {
+    fn new() -> Self {
+        Reorder {
+            next: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Принимает результат и отдаёт `consume` все готовые подряд.
+    fn push(&mut self, (seq, item, result): Done<'a, T>, consume: &mut impl FnMut(Item<'a>, T)) {
+        self.pending.insert(seq, (item, result));
+        while let Some((item, result)) = self.pending.remove(&self.next) {
+            consume(item, result);
+            self.next += 1;
+        }
+    }
+}
+
+/// Обрабатывает строки источника.
+///
+/// `process` вызывается параллельно в пуле rayon, `consume` — в отдельном
+/// потоке записи строго в порядке входа, по мере готовности очередной строки,
+/// не дожидаясь конца блока. Сквозной индекс строки считается
+/// среди непустых строк всех потоков, с нуля; первые `skip` строк пропускаются.
+/// Строки, отброшенные `selection`, до обработки не доходят (их индексы
+/// остаются пропущенными); возвращается число отброшенных строк.
+pub fn run<'a, T, P, C>(
+    source: Source<'a>,
+    skip: usize,
+    selection: Selection<'a>,
+    process: P,
+    consume: C,
+) -> Dropped
+where
+    T: Send,
+    P: Fn(usize, Origin, &str) -> T + Sync,
+    C: FnMut(usize, Origin, Cow<'a, str>, T) + Send,
+{
+    let (line_tx, line_rx) = sync_channel::<Chunk<'a>>(CHANNEL_CAPACITY);
+    let (result_tx, result_rx) = sync_channel::<Done<'a, T>>(CHUNK_SIZE);
+
+    thread::scope(|scope| {
+        let reader = scope.spawn(move || read_chunks(source, skip, selection, line_tx));
+        scope.spawn(move || {
+            let mut consume = consume;
+            let mut reorder = Reorder::new();
+            for done in result_rx {
+                reorder.push(done, &mut |(idx, origin, line), result| {
+                    consume(idx, origin, line, result)
+                });
+            }
+        });
+
+        for (first, chunk) in line_rx {
+            let sent = chunk.into_par_iter().enumerate().try_for_each_with(
+                result_tx.clone(),
+                |tx, (offset, (idx, origin, line))| {
+                    let result = process(idx, origin, &line);
+                    tx.send((first + offset, (idx, origin, line), result))
+                },
+            );
+            if sent.is_err() {
+                break;
+            }
+        }
+        drop(result_tx);
+        reader.join().expect("поток чтения завершился паникой")
+    })
+}
+
+/// Этап чтения: режет источник на блоки и отправляет их обработчикам.
+/// Возвращает число строк, отброшенных отбором.
+fn read_chunks<'a>(
+    source: Source<'a>,
+    skip: usize,
+    mut selection: Selection<'a>,
+    tx: SyncSender<Chunk<'a>>,
+) -> Dropped {
+    let items: Box<dyn Iterator<Item = Item<'a>> + 'a> = match source {
+        Source::Lines(lines) => Box::new(
+            lines
+                .into_iter()
+                .enumerate()
+                .map(|(idx, line)| (idx, Origin { file: 0, line: idx }, line)),
+        ),
+        Source::Mapped { content, delimiter } => Box::new(
+            mapped_records(content, delimiter)
+                .enumerate()
+                .map(|(idx, line)| (idx, Origin { file: 0, line: idx }, Cow::Borrowed(line))),
+        ),
+        Source::Readers { readers, delimiter } => {
+            let records = readers.into_iter().enumerate().flat_map(move |(file, reader)| {
+                reader
+                    .split(delimiter)
+                    .map(|record| {
+                        let record = record.unwrap_or_else(|e| {
+                            error!(
+                                "{}",
+                                tr!("Ошибка при чтении входа: {}", "Failed to read input: {}", e)
+                            );
+                            std::process::exit(EXIT_IO);
+                        });
+                        String::from_utf8(record).unwrap_or_else(|e| {
+                            error!(
+                                "{}",
+                                tr!(
+                                    "Вход не в кодировке UTF-8: {}",
+                                    "Input is not valid UTF-8: {}",
+                                    e
+                                )
+                            );
+                            std::process::exit(EXIT_IO);
+                        })
+                    })
+                    .map(|line| line.trim().to_string())
+                    .filter(|line| !line.is_empty())
+                    .enumerate()
+                    .map(move |(line_idx, line)| (Origin { file, line: line_idx }, line))
+            });
+            Box::new(
+                records
+                    .enumerate()
+                    .map(|(idx, (origin, line))| (idx, origin, Cow::Owned(line))),
+            )
+        }
+    };
+
+    let mut seen = HashSet::new();
+    let mut dropped = Dropped::default();
+    let mut sent = 0;
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    let end = selection.end;
+    let items = items.skip(skip).take_while(|(idx, _, _)| end.is_none_or(|end| *idx < end));
+    for item in items {
+        if let Some(sample) = &mut selection.sample {
+            if !sample.pick() {
+                dropped.unsampled += 1;
+                continue;
+            }
+        }
+        if let Some(keep) = &selection.keep {
+            if !keep(&item.2) {
+                if let Some(rejected) = &mut selection.rejected {
+                    rejected(&item.2);
+                }
+                dropped.filtered += 1;
+                continue;
+            }
+        }
+        if let Some(dedupe) = selection.dedupe {
+            if !seen.insert(dedupe.key(&item.2)) {
+                dropped.duplicates += 1;
+                continue;
+            }
+        }
+        chunk.push(item);
+        if chunk.len() == CHUNK_SIZE {
+            let full = std::mem::replace(&mut chunk, Vec::with_capacity(CHUNK_SIZE));
+            if tx.send((sent, full)).is_err() {
+                return dropped;
+            }
+            sent += CHUNK_SIZE;
+        }
+    }
+    if !chunk.is_empty() {
+        let _ = tx.send((sent, chunk));
+    }
+    dropped
+}
@@ -0,0 +1,86 @@
+//! Вывод результатов в PostgreSQL (`--output-db postgres://…`, функция `postgres`).
+//!
+//! Пачка загружается командой COPY во временную таблицу и переносится
+//! в таблицу результатов одним INSERT … ON CONFLICT.
+
+use super::database::{Backend, OnConflict, Row};
+use postgres::{Client, NoTls};
+use std::io::Write;
+
+/// Временная таблица сеанса для COPY; очищается при каждом COMMIT.
+const STAGING_TABLE: &str = "mnem_to_entropy_batch";
+
+pub struct PostgresBackend {
+    client: Client,
+    merge: String,
+}
+
+impl PostgresBackend {
+    pub fn connect(url: &str, table: &str, on_conflict: OnConflict) -> Result<Self, postgres::Error> {
+        let mut client = Client::connect(url, NoTls)?;
+        client.batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                mnemonic_hash TEXT PRIMARY KEY,
+                entropy TEXT,
+                status TEXT NOT NULL,
+                error TEXT,
+                timestamp BIGINT NOT NULL
+            );
+            CREATE TEMP TABLE IF NOT EXISTS {STAGING_TABLE}
+                (LIKE {table} INCLUDING DEFAULTS) ON COMMIT DELETE ROWS;"
+        ))?;
+        let action = match on_conflict {
+            OnConflict::Update => {
+                "DO UPDATE SET entropy = EXCLUDED.entropy, status = EXCLUDED.status, \
+                 error = EXCLUDED.error, timestamp = EXCLUDED.timestamp"
+            }
+            OnConflict::Ignore => "DO NOTHING",
+        };
+        // В одной команде ON CONFLICT не может затронуть строку дважды,
+        // поэтому повторы внутри пачки схлопываются заранее
+        let merge = format!(
+            "INSERT INTO {table} (mnemonic_hash, entropy, status, error, timestamp)
+                SELECT DISTINCT ON (mnemonic_hash) mnemonic_hash, entropy, status, error, timestamp
+                FROM {STAGING_TABLE}
+                ON CONFLICT (mnemonic_hash) {action}"
+        );
+        Ok(PostgresBackend { client, merge })
+    }
+}
+
+impl Backend for PostgresBackend {
+    fn insert(&mut self, rows: &[Row]) -> Result<(), Box<dyn std::error::Error>> {
+        let mut transaction = self.client.transaction()?;
+        let copy = format!(
+            "COPY {STAGING_TABLE} (mnemonic_hash, entropy, status, error, timestamp) FROM STDIN"
+        );
+        let mut writer = transaction.copy_in(copy.as_str())?;
+        for row in rows {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}",
+                row.mnemonic_hash,
+                copy_field(row.entropy.as_deref()),
+                row.status(),
+                copy_field(row.error.as_deref()),
+                row.timestamp
+            )?;
+        }
+        writer.finish()?;
+        transaction.batch_execute(&self.merge)?;
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+/// Значение для текстового формата COPY: `\N` для NULL, спецсимволы экранируются.
+fn copy_field(value: Option<&str>) -> String {
+    match value {
+        None => "\\N".to_string(),
+        Some(value) => value
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r"),
+    }
+}
@@ -0,0 +1,187 @@
+//! Индикатор прогресса пакетной обработки в stderr. Пока он показан,
+//! сообщения в терминал выводятся через [`suspended`], чтобы не смешиваться
+//! с полосой.
+
+use indicatif::{ProgressBar, ProgressState, ProgressStyle};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Показанный сейчас индикатор — для [`suspended`].
+static ACTIVE: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+/// Счётчики строк, которые индикатор показывает при каждой перерисовке.
+#[derive(Default)]
+struct Counters {
+    lines: AtomicUsize,
+    succeeded: AtomicUsize,
+    failed: AtomicUsize,
+}
+
+impl Counters {
+    /// Строк в секунду за `elapsed` секунд.
+    fn rate(&self, elapsed: f64) -> f64 {
+        if elapsed > 0.0 {
+            self.lines.load(Ordering::Relaxed) as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Индикатор прогресса: по байтам входа (с оценкой оставшегося времени),
+/// если его размер известен, иначе — счётчик строк. В обоих случаях
+/// показываются скорость в строках в секунду и число успешных и ошибочных строк.
+pub struct Progress {
+    bar: ProgressBar,
+    by_bytes: bool,
+    counters: Arc<Counters>,
+}
+
+impl Progress {
+    /// Показывает индикатор; `total_bytes` — размер входа, если известен.
+    pub fn start(total_bytes: Option<u64>) -> Self {
+        let counters = Arc::new(Counters::default());
+        let (bar, by_bytes, template) = match total_bytes {
+            Some(size) => (
+                ProgressBar::new(size),
+                true,
+                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] \
+                 {bytes}/{total_bytes} ({eta}) {rate} {counts}"
+                    .to_string(),
+            ),
+            None => (
+                ProgressBar::new_spinner(),
+                false,
+                format!(
+                    "{{spinner:.green}} [{{elapsed_precise}}] {{pos}} {} {{rate}} {{counts}}",
+                    tr!("строк", "lines")
+                ),
+            ),
+        };
+        let unit = tr!("строк/с", "lines/s");
+        let rate = {
+            let counters = Arc::clone(&counters);
+            move |state: &ProgressState, w: &mut dyn std::fmt::Write| {
+                let _ = write!(w, "{:.0} {}", counters.rate(state.elapsed().as_secs_f64()), unit);
+            }
+        };
+        let counts = {
+            let counters = Arc::clone(&counters);
+            move |_: &ProgressState, w: &mut dyn std::fmt::Write| {
+                let _ = write!(
+                    w,
+                    "✓ {} ✗ {}",
+                    counters.succeeded.load(Ordering::Relaxed),
+                    counters.failed.load(Ordering::Relaxed)
+                );
+            }
+        };
+        let style = if by_bytes {
+            ProgressStyle::default_bar()
+        } else {
+            ProgressStyle::default_spinner()
+        };
+        bar.set_style(
+            style
+                .template(&template)
+                .expect("шаблон индикатора корректен")
+                .with_key("rate", rate)
+                .with_key("counts", counts)
+                .progress_chars("#>-"),
+        );
+        *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = Some(bar.clone());
+        Progress { bar, by_bytes, counters }
+    }
+
+    /// Учитывает обработанную строку и её итог.
+    pub fn advance(&self, line: &str, succeeded: bool) {
+        self.counters.lines.fetch_add(1, Ordering::Relaxed);
+        let outcome = if succeeded { &self.counters.succeeded } else { &self.counters.failed };
+        outcome.fetch_add(1, Ordering::Relaxed);
+        self.bar.inc(if self.by_bytes { line.len() as u64 + 1 } else { 1 });
+    }
+
+    /// Убирает индикатор и печатает в stderr итоговую скорость.
+    pub fn finish(self) {
+        ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let elapsed = self.bar.elapsed().as_secs_f64();
+        self.bar.finish_and_clear();
+        eprintln!(
+            "{}",
+            tr!(
+                "⏱ Строк: {} за {:.1} с ({:.0} строк/с), успешно: {}, с ошибкой: {}",
+                "⏱ Lines: {} in {:.1} s ({:.0} lines/s), succeeded: {}, failed: {}",
+                self.counters.lines.load(Ordering::Relaxed),
+                elapsed,
+                self.counters.rate(elapsed),
+                self.counters.succeeded.load(Ordering::Relaxed),
+                self.counters.failed.load(Ordering::Relaxed)
+            )
+        );
+    }
+}
+
+/// Индикатор перебора кандидатов: проверено из общего числа с оценкой
+/// оставшегося времени и число найденных.
+pub struct SearchProgress {
+    bar: ProgressBar,
+    found: Arc<AtomicUsize>,
+}
+
+impl SearchProgress {
+    /// Показывает индикатор перебора `total` кандидатов.
+    pub fn start(total: u64) -> Self {
+        let found = Arc::new(AtomicUsize::new(0));
+        let bar = ProgressBar::new(total);
+        let counts = {
+            let found = Arc::clone(&found);
+            move |_: &ProgressState, w: &mut dyn std::fmt::Write| {
+                let _ = write!(w, "✓ {}", found.load(Ordering::Relaxed));
+            }
+        };
+        bar.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] \
+                     {human_pos}/{human_len} ({eta}) {per_sec} {counts}",
+                )
+                .expect("шаблон индикатора корректен")
+                .with_key("counts", counts)
+                .progress_chars("#>-"),
+        );
+        *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = Some(bar.clone());
+        SearchProgress { bar, found }
+    }
+
+    /// Учитывает `checked` проверенных кандидатов, из которых `found` подошли.
+    pub fn advance(&self, checked: u64, found: usize) {
+        self.found.fetch_add(found, Ordering::Relaxed);
+        self.bar.inc(checked);
+    }
+
+    /// Убирает индикатор и печатает в stderr итог перебора.
+    pub fn finish(self) {
+        ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let elapsed = self.bar.elapsed().as_secs_f64();
+        self.bar.finish_and_clear();
+        eprintln!(
+            "{}",
+            tr!(
+                "⏱ Проверено кандидатов: {} за {:.1} с, подошло: {}",
+                "⏱ Candidates checked: {} in {:.1} s, matched: {}",
+                self.bar.position(),
+                elapsed,
+                self.found.load(Ordering::Relaxed)
+            )
+        );
+    }
+}
+
+/// Выполняет вывод в терминал, убрав на это время индикатор.
+pub fn suspended<R>(f: impl FnOnce() -> R) -> R {
+    let active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    match active {
+        Some(bar) => bar.suspend(f),
+        None => f(),
+    }
+}
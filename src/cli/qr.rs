@@ -0,0 +1,274 @@
+//! QR-коды (функция `qr`): вывод результата (`--qr`) для передачи на устройство
+//! подписи без сети — в терминал или в файл PNG/SVG, и чтение фраз
+//! с фотографий и сканов (`--input-image`).
+
+use super::i18n::Localized;
+use super::{quiet, EXIT_FAILED, EXIT_IO, EXIT_USAGE};
+use clap::{Args, ValueEnum};
+use log::error;
+use mnem_to_entropy::{encode_in, Language};
+use qrcode::render::{svg, unicode};
+use qrcode::QrCode;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str;
+
+/// Сторона изображения PNG/SVG в пикселях (не меньше).
+const IMAGE_SIZE: u32 = 400;
+
+/// Что кодируется в QR.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QrContent {
+    /// Энтропия в hex
+    Entropy,
+    /// Мнемоническая фраза, заново собранная из энтропии
+    Mnemonic,
+    /// SeedQR: индексы слов по четыре цифры (только English, 12 или 24 слова)
+    Seedqr,
+    /// CompactSeedQR: энтропия байтами (только English, 12 или 24 слова)
+    CompactSeedqr,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct QrArgs {
+    /// Показать результат QR-кодом (без значения — энтропию); только для одной фразы
+    #[arg(
+        long,
+        value_enum,
+        value_name = "CONTENT",
+        num_args = 0..=1,
+        default_missing_value = "entropy",
+        conflicts_with_all = ["input_file", "output_file"]
+    )]
+    pub qr: Option<QrContent>,
+
+    /// Сохранить QR-код в файл PNG или SVG (по расширению) вместо вывода в терминал
+    #[arg(long, value_name = "FILE", requires = "qr")]
+    pub qr_output: Option<PathBuf>,
+}
+
+/// Выводит QR-код с содержимым `content` для энтропии `entropy`. `language` —
+/// словарь фразы (`None` для пользовательского словаря).
+pub fn show(args: &QrArgs, content: QrContent, entropy: &[u8], language: Option<Language>) {
+    let payload = payload(content, entropy, language).unwrap_or_else(|message| {
+        error!("{}", tr!("Ошибка: {}", "Error: {}", message));
+        std::process::exit(EXIT_USAGE);
+    });
+    let code = QrCode::new(&payload).unwrap_or_else(|e| {
+        error!(
+            "{}",
+            tr!("Не удалось построить QR-код: {}", "Failed to build the QR code: {}", e)
+        );
+        std::process::exit(EXIT_FAILED);
+    });
+
+    match &args.qr_output {
+        Some(path) => {
+            save(&code, path);
+            if !quiet() {
+                println!(
+                    "{}",
+                    tr!("✓ QR-код сохранён в файл: {:?}", "✓ QR code saved to file: {:?}", path)
+                );
+            }
+        }
+        None => {
+            // Светлые модули на тёмном фоне: так код читается с экрана терминала
+            let text = code
+                .render::<unicode::Dense1x2>()
+                .dark_color(unicode::Dense1x2::Light)
+                .light_color(unicode::Dense1x2::Dark)
+                .build();
+            println!("{}", text);
+        }
+    }
+}
+
+fn payload(content: QrContent, entropy: &[u8], language: Option<Language>) -> Result<Vec<u8>, String> {
+    match content {
+        QrContent::Entropy => Ok(hex::encode(entropy).into_bytes()),
+        QrContent::Mnemonic => {
+            let language = language.ok_or_else(|| {
+                tr!(
+                    "--qr mnemonic не поддерживается с пользовательским словарём",
+                    "--qr mnemonic is not supported with a custom wordlist"
+                )
+            })?;
+            encode_in(language, entropy)
+                .map(String::into_bytes)
+                .map_err(|e| e.localized())
+        }
+        QrContent::Seedqr | QrContent::CompactSeedqr => {
+            if language != Some(Language::English) {
+                return Err(tr!(
+                    "SeedQR определён только для английского словаря",
+                    "SeedQR is defined only for the English wordlist"
+                ));
+            }
+            if entropy.len() != 16 && entropy.len() != 32 {
+                return Err(tr!(
+                    "SeedQR определён только для фраз из 12 и 24 слов",
+                    "SeedQR is defined only for 12- and 24-word phrases"
+                ));
+            }
+            if content == QrContent::CompactSeedqr {
+                return Ok(entropy.to_vec());
+            }
+            let mnemonic = encode_in(Language::English, entropy).map_err(|e| e.localized())?;
+            Ok(mnemonic
+                .split_whitespace()
+                .map(|word| {
+                    let index = Language::English.find_word(word).expect("слово есть в словаре");
+                    format!("{:04}", index)
+                })
+                .collect::<String>()
+                .into_bytes())
+        }
+    }
+}
+
+fn save(code: &QrCode, path: &Path) {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase);
+    let result = match extension.as_deref() {
+        Some("png") => code
+            .render::<image::Luma<u8>>()
+            .min_dimensions(IMAGE_SIZE, IMAGE_SIZE)
+            .build()
+            .save(path)
+            .map_err(|e| e.to_string()),
+        Some("svg") => {
+            let image = code
+                .render::<svg::Color>()
+                .min_dimensions(IMAGE_SIZE, IMAGE_SIZE)
+                .build();
+            fs::write(path, image).map_err(|e| e.to_string())
+        }
+        _ => {
+            error!(
+                "{}",
+                tr!(
+                    "--qr-output: ожидается файл .png или .svg, получено {:?}",
+                    "--qr-output: expected a .png or .svg file, got {:?}",
+                    path
+                )
+            );
+            std::process::exit(EXIT_USAGE);
+        }
+    };
+    if let Err(e) = result {
+        error!(
+            "{}",
+            tr!(
+                "Ошибка при записи QR-кода в {:?}: {}",
+                "Failed to write the QR code to {:?}: {}",
+                path,
+                e
+            )
+        );
+        std::process::exit(EXIT_IO);
+    }
+}
+
+/// Фразы из всех QR-кодов на изображении, в порядке обнаружения.
+pub fn read_image(path: &Path) -> Vec<String> {
+    let image = match image::open(path) {
+        Ok(image) => image.to_luma8(),
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при чтении изображения {:?}: {}",
+                    "Failed to read image {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    };
+    let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+        image.width() as usize,
+        image.height() as usize,
+        |x, y| image.get_pixel(x as u32, y as u32).0[0],
+    );
+    let grids = prepared.detect_grids();
+    if grids.is_empty() {
+        error!(
+            "{}",
+            tr!("На изображении {:?} не найден QR-код", "No QR code found in image {:?}", path)
+        );
+        std::process::exit(EXIT_FAILED);
+    }
+
+    grids
+        .iter()
+        .enumerate()
+        .map(|(idx, grid)| {
+            let mut content = Vec::new();
+            let phrase = grid
+                .decode_to(&mut content)
+                .map_err(|e| e.to_string())
+                .and_then(|_| mnemonic_from_payload(&content));
+            phrase.unwrap_or_else(|message| {
+                error!(
+                    "{}",
+                    tr!(
+                        "QR-код {} на изображении {:?}: {}",
+                        "QR code {} in image {:?}: {}",
+                        idx + 1,
+                        path,
+                        message
+                    )
+                );
+                std::process::exit(EXIT_FAILED);
+            })
+        })
+        .collect()
+}
+
+/// Содержимое QR-кода → мнемоника: SeedQR (только цифры), текст фразы
+/// или CompactSeedQR (16 или 32 байта энтропии).
+fn mnemonic_from_payload(content: &[u8]) -> Result<String, String> {
+    let text = str::from_utf8(content).ok().map(str::trim);
+    match text {
+        Some(digits)
+            if (digits.len() == 48 || digits.len() == 96)
+                && digits.bytes().all(|byte| byte.is_ascii_digit()) =>
+        {
+            let words = Language::English.word_list();
+            digits
+                .as_bytes()
+                .chunks(4)
+                .map(|chunk| {
+                    let index: usize = str::from_utf8(chunk)
+                        .expect("цифры ASCII")
+                        .parse()
+                        .expect("четыре цифры");
+                    words
+                        .get(index)
+                        .copied()
+                        .ok_or_else(|| {
+                            tr!(
+                                "индекс слова SeedQR {} больше 2047",
+                                "SeedQR word index {} exceeds 2047",
+                                index
+                            )
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|words| words.join(" "))
+        }
+        // В текстовой фразе слова разделены пробелами; в CompactSeedQR их нет
+        Some(phrase) if phrase.contains(char::is_whitespace) => Ok(phrase.to_string()),
+        _ if content.len() == 16 || content.len() == 32 => {
+            encode_in(Language::English, content).map_err(|e| e.localized())
+        }
+        _ => Err(tr!(
+            "содержимое не похоже на мнемонику, SeedQR или CompactSeedQR",
+            "the content is not a mnemonic, SeedQR or CompactSeedQR"
+        )),
+    }
+}
@@ -0,0 +1,577 @@
+use super::decode::load_wordlist;
+use super::derive::{load_targets, normalize_address};
+use super::i18n::Localized;
+use super::input::load_lines;
+use super::progress::SearchProgress;
+use super::{parse_language, quiet, EXIT_FAILED, EXIT_IO, EXIT_USAGE};
+use clap::Args;
+use log::{error, info, warn};
+use mnem_to_entropy::{
+    account_path, completions, derive_addresses, derive_cardano_addresses, derive_solana_addresses,
+    insertion_templates, last_word_templates, mnemonic_to_seed, parse_missing_word, try_swaps, AddressType, Candidate,
+    CardanoKey, Coin, Completions, DerivationPath, DerivedAddress, Language, MissingWord, Network, Options,
+    RootKeys, WORDLIST_SIZE,
+};
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[derive(Args, Debug)]
+pub struct RecoverArgs {
+    /// Мнемоника, в которой одно или два неизвестных слова заменены на '?' или '_'
+    #[arg(short, long, env = "MNEM_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Искать перепутанные местами соседние слова у фразы с неверной контрольной суммой
+    #[arg(long, default_value = "false")]
+    pub try_swaps: bool,
+
+    /// Вместе с --try-swaps: переставлять любые пары слов, а не только соседние
+    #[arg(long, default_value = "false", requires = "try_swaps")]
+    pub full_swaps: bool,
+
+    /// Подобрать последнее слово фразы с неверной контрольной суммой:
+    /// вывести все варианты, с которыми она сходится
+    #[arg(long, default_value = "false", conflicts_with = "try_swaps")]
+    pub last_word: bool,
+
+    /// Фраза без одного слова, место которого неизвестно (например 23 из 24):
+    /// вставить каждое слово словаря в каждую позицию; в выводе перед
+    /// мнемоникой — номер вставленного слова
+    #[arg(long, default_value = "false", conflicts_with_all = ["try_swaps", "last_word"])]
+    pub anywhere: bool,
+
+    /// Дополнить известные первые слова фразы до N слов: вывести все
+    /// продолжения с верной контрольной суммой по порядку индексов слов
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with_all = ["try_swaps", "last_word", "anywhere", "match_address"]
+    )]
+    pub complete: Option<usize>,
+
+    /// Наибольшее число продолжений, которые выводит --complete
+    #[arg(long, default_value_t = 1_000_000, requires = "complete")]
+    pub limit: u64,
+
+    /// Вместе с --complete: только посчитать продолжения, не выводя их
+    #[arg(long, default_value = "false", requires = "complete")]
+    pub count_only: bool,
+
+    /// Файл для сохранения кандидатов (мнемоника и энтропия через табуляцию)
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Перебирать слова только указанного языка
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Пользовательский словарь из 2048 слов (одно слово на строку)
+    #[arg(long, value_name = "FILE", conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+
+    /// Файл известных адресов кошелька (по одному на строку): выводятся только
+    /// кандидаты, среди первых --count адресов которых есть один из них
+    #[arg(long, value_name = "FILE", conflicts_with = "try_swaps")]
+    pub match_address: Option<PathBuf>,
+
+    /// Тип адресов для --match-address (по умолчанию определяется по самим
+    /// адресам): p2pkh, p2sh-p2wpkh, p2wpkh, p2tr, ethereum, tron, solana, cardano
+    #[arg(long = "address-type", requires = "match_address")]
+    pub address_type: Option<AddressType>,
+
+    /// Сколько первых адресов каждого кандидата сверять с --match-address
+    #[arg(long, default_value_t = 20, requires = "match_address")]
+    pub count: u32,
+
+    /// Пароль BIP39 (25-е слово) для --match-address
+    #[arg(long, env = "MNEM_PASSPHRASE", hide_env_values = true, default_value = "")]
+    pub passphrase: String,
+
+    /// Сеть адресов для --match-address: bitcoin, testnet, signet, regtest
+    #[arg(long, default_value = "bitcoin")]
+    pub network: Network,
+
+    /// Не показывать индикатор прогресса перебора двух слов (по умолчанию
+    /// он выводится в stderr, если это терминал)
+    #[arg(long, default_value = "false")]
+    pub no_progress: bool,
+}
+
+/// Кандидат и, с `--match-address`, совпавший с целевым адрес.
+struct Found {
+    /// Позиция подставленного слова (первого из двух) с нуля.
+    position: usize,
+    candidate: Candidate,
+    address: Option<DerivedAddress>,
+}
+
+/// Сверка кандидатов с известными адресами (`--match-address`): первые
+/// `count` адресов внешней цепочки аккаунта 0.
+struct AddressMatcher {
+    targets: HashSet<String>,
+    address_type: AddressType,
+    chain: DerivationPath,
+    network: Network,
+    passphrase: String,
+    count: u32,
+}
+
+impl AddressMatcher {
+    fn new(args: &RecoverArgs, path: &Path) -> Result<Self, String> {
+        let targets = load_targets(path);
+        if targets.is_empty() {
+            return Err(tr!(
+                "в файле {:?} нет адресов",
+                "the file {:?} contains no addresses",
+                path
+            ));
+        }
+        let address_type = match args.address_type {
+            Some(address_type) => address_type,
+            None => {
+                let mut detected = targets.iter().map(|address| detect_address_type(address));
+                let first = detected.next().flatten();
+                match first {
+                    Some(address_type) if detected.all(|other| other == first) => address_type,
+                    _ => {
+                        return Err(tr!(
+                            "не удалось определить тип адресов, укажите --address-type",
+                            "cannot infer the address type, specify --address-type"
+                        ))
+                    }
+                }
+            }
+        };
+        let coin = match address_type {
+            AddressType::Ethereum => Coin::Ethereum,
+            AddressType::Tron => Coin::Tron,
+            _ => Coin::Bitcoin,
+        };
+        let chain = account_path(address_type.purpose(), coin.coin_type(args.network), 0)
+            .map_err(|e| e.localized())?
+            .child(0.into());
+        Ok(AddressMatcher {
+            targets,
+            address_type,
+            chain,
+            network: args.network,
+            passphrase: args.passphrase.clone(),
+            count: args.count,
+        })
+    }
+
+    /// Первый из адресов кандидата, который есть среди целевых.
+    fn find(&self, candidate: &Candidate) -> Option<DerivedAddress> {
+        let addresses = match self.address_type {
+            // Cardano Icarus строит ключ из энтропии, а не из BIP39 seed
+            AddressType::Cardano => {
+                let root = CardanoKey::icarus(candidate.entropy.as_bytes(), &self.passphrase);
+                derive_cardano_addresses(&root, self.network, 0, 0, 0, self.count)
+            }
+            AddressType::Solana => {
+                derive_solana_addresses(&mnemonic_to_seed(&candidate.mnemonic, &self.passphrase), 0, self.count)
+            }
+            address_type => {
+                let seed = mnemonic_to_seed(&candidate.mnemonic, &self.passphrase);
+                let root = RootKeys::from_seed(&seed, self.network);
+                derive_addresses(&root.xprv, self.network, &self.chain, address_type, 0, self.count)
+            }
+        };
+        addresses
+            .ok()?
+            .into_iter()
+            .find(|derived| self.targets.contains(&normalize_address(&derived.address)))
+    }
+}
+
+/// Тип адреса по его виду; `None` для адресов Solana и нераспознанных.
+fn detect_address_type(address: &str) -> Option<AddressType> {
+    let lower = address.to_lowercase();
+    let segwit = ["bc1", "tb1", "bcrt1"].iter().find_map(|hrp| lower.strip_prefix(hrp));
+    if let Some(program) = segwit {
+        return match program.chars().next() {
+            Some('q') => Some(AddressType::P2wpkh),
+            Some('p') => Some(AddressType::P2tr),
+            _ => None,
+        };
+    }
+    if lower.starts_with("0x") {
+        return Some(AddressType::Ethereum);
+    }
+    if lower.starts_with("addr") {
+        return Some(AddressType::Cardano);
+    }
+    match address.chars().next()? {
+        '1' | 'm' | 'n' => Some(AddressType::P2pkh),
+        '3' | '2' => Some(AddressType::P2shP2wpkh),
+        'T' => Some(AddressType::Tron),
+        _ => None,
+    }
+}
+
+pub fn run(args: &RecoverArgs) {
+    let prompt = if args.try_swaps || args.last_word {
+        tr!("Введите мнемоническую фразу:", "Enter the mnemonic phrase:")
+    } else if args.complete.is_some() {
+        tr!("Введите известные первые слова фразы:", "Enter the known first words of the phrase:")
+    } else if args.anywhere {
+        tr!(
+            "Введите мнемоническую фразу без пропущенного слова:",
+            "Enter the mnemonic phrase without the missing word:"
+        )
+    } else {
+        tr!(
+            "Введите мнемоническую фразу ('?' на месте неизвестных слов):",
+            "Enter the mnemonic phrase ('?' in place of the unknown words):"
+        )
+    };
+    let mnemonic = load_lines(None, args.mnemonic.as_deref(), &prompt, false, false).remove(0);
+
+    let options = Options {
+        language: args.language,
+        wordlist: args.wordlist.as_deref().map(|path| Arc::new(load_wordlist(path))),
+        ..Options::default()
+    };
+
+    if args.try_swaps {
+        run_swaps(args, &mnemonic, &options);
+        return;
+    }
+    if let Some(word_count) = args.complete {
+        run_completions(args, word_count, &mnemonic, &options);
+        return;
+    }
+
+    let templates = if args.last_word {
+        last_word_templates(&mnemonic, &options)
+    } else if args.anywhere {
+        insertion_templates(&mnemonic, &options)
+    } else {
+        parse_missing_word(&mnemonic, &options)
+    };
+    let templates = match templates {
+        Ok(templates) => templates,
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e.localized()));
+            std::process::exit(EXIT_FAILED);
+        }
+    };
+
+    let matcher = args.match_address.as_deref().map(|path| match AddressMatcher::new(args, path) {
+        Ok(matcher) => matcher,
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e));
+            std::process::exit(EXIT_USAGE);
+        }
+    });
+
+    let pairs = templates[0].positions().len() > 1;
+    let progress = (pairs && !args.no_progress && !quiet() && std::io::stderr().is_terminal())
+        .then(|| SearchProgress::start((templates.len() * WORDLIST_SIZE * WORDLIST_SIZE) as u64));
+
+    let mut found: Vec<Found> = templates
+        .iter()
+        .flat_map(|template| search(template, matcher.as_ref(), progress.as_ref()))
+        .collect();
+    if args.anywhere {
+        // Слово, равное соседнему, даёт ту же фразу при вставке до и после него
+        let mut seen = HashSet::new();
+        found.retain(|found| seen.insert((found.candidate.mnemonic.clone(), found.candidate.entropy.to_hex())));
+    }
+    if let Some(progress) = progress {
+        progress.finish();
+    }
+
+    let lines: Vec<String> = found
+        .iter()
+        .map(|found| {
+            let mut line = format!("{}\t{}", found.candidate.mnemonic, found.candidate.entropy.to_hex());
+            if args.anywhere {
+                line = format!("{}\t{}", found.position + 1, line);
+            }
+            match &found.address {
+                Some(derived) => format!("{}\t{}\t{}", line, derived.path, derived.address),
+                None => line,
+            }
+        })
+        .collect();
+    write_lines(args, &lines);
+
+    if !quiet() && args.anywhere {
+        let summary = if matcher.is_some() {
+            tr!(
+                "Вставок с известным адресом — {}",
+                "Insertions with a known address — {}",
+                found.len()
+            )
+        } else {
+            tr!(
+                "Вставок с верной контрольной суммой — {}",
+                "Insertions with a valid checksum — {}",
+                found.len()
+            )
+        };
+        info!("{}", summary);
+    } else if !quiet() {
+        let positions: Vec<String> = templates[0]
+            .positions()
+            .iter()
+            .map(|position| (position + 1).to_string())
+            .collect();
+        let words = if pairs {
+            tr!("Слова {}", "Words {}", positions.join(", "))
+        } else {
+            tr!("Слово {}", "Word {}", positions[0])
+        };
+        let summary = if matcher.is_some() {
+            tr!(
+                "{}: кандидатов с известным адресом — {}",
+                "{}: candidates with a known address — {}",
+                words,
+                found.len()
+            )
+        } else {
+            tr!(
+                "{}: кандидатов с верной контрольной суммой — {}",
+                "{}: candidates with a valid checksum — {}",
+                words,
+                found.len()
+            )
+        };
+        info!("{}", summary);
+    }
+
+    if found.is_empty() {
+        std::process::exit(EXIT_FAILED);
+    }
+}
+
+/// Перебирает все слова словаря на местах пропусков шаблона параллельно,
+/// сохраняя порядок, и оставляет кандидатов, прошедших `matcher`.
+fn search(template: &MissingWord, matcher: Option<&AddressMatcher>, progress: Option<&SearchProgress>) -> Vec<Found> {
+    let position = template.position();
+    let accept = |candidate: Candidate| match matcher {
+        Some(matcher) => matcher.find(&candidate).map(|address| Found {
+            position,
+            candidate,
+            address: Some(address),
+        }),
+        None => Some(Found {
+            position,
+            candidate,
+            address: None,
+        }),
+    };
+    if template.positions().len() == 1 {
+        return (0..WORDLIST_SIZE as u16)
+            .into_par_iter()
+            .filter_map(|index| template.candidate(index))
+            .filter_map(accept)
+            .collect();
+    }
+    // Два пропуска: параллельно по первому слову, второе — внутри
+    (0..WORDLIST_SIZE as u16)
+        .into_par_iter()
+        .flat_map_iter(|first| {
+            let found: Vec<Found> = (0..WORDLIST_SIZE as u16)
+                .filter_map(|second| template.candidate_with(&[first, second]))
+                .filter_map(accept)
+                .collect();
+            if let Some(progress) = progress {
+                progress.advance(WORDLIST_SIZE as u64, found.len());
+            }
+            found
+        })
+        .collect()
+}
+
+/// Перестановки пар слов; в выводе перед мнемоникой — номера переставленных слов.
+fn run_swaps(args: &RecoverArgs, mnemonic: &str, options: &Options) {
+    let swaps = match try_swaps(mnemonic, options, args.full_swaps) {
+        Ok(swaps) => swaps,
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e.localized()));
+            std::process::exit(EXIT_FAILED);
+        }
+    };
+
+    let lines: Vec<String> = swaps
+        .iter()
+        .map(|swap| {
+            format!(
+                "{}-{}\t{}\t{}",
+                swap.first + 1,
+                swap.second + 1,
+                swap.candidate.mnemonic,
+                swap.candidate.entropy.to_hex()
+            )
+        })
+        .collect();
+    write_lines(args, &lines);
+
+    if !quiet() {
+        info!(
+            "{}",
+            tr!(
+                "Перестановок с верной контрольной суммой — {}",
+                "Swaps with a valid checksum — {}",
+                swaps.len()
+            )
+        );
+    }
+    if swaps.is_empty() {
+        std::process::exit(EXIT_FAILED);
+    }
+}
+
+/// Продолжения начала фразы (`--complete`): выводятся по мере перебора,
+/// не больше `--limit`; с `--count-only` выводится только их число.
+fn run_completions(args: &RecoverArgs, word_count: usize, prefix: &str, options: &Options) {
+    let completions = match completions(prefix, word_count, options) {
+        Ok(completions) => completions,
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e.localized()));
+            std::process::exit(EXIT_FAILED);
+        }
+    };
+
+    // Во всех словарях свободных бит одинаково; 2^256 в u128 не помещается
+    let free_bits = completions[0].free_bits();
+    let total = completions
+        .iter()
+        .map(Completions::total)
+        .try_fold(0u128, |sum, total| sum.checked_add(total?));
+    let total_text = match (total, completions.len()) {
+        (Some(total), _) => total.to_string(),
+        (None, 1) => format!("2^{}", free_bits),
+        (None, dictionaries) => format!("{} × 2^{}", dictionaries, free_bits),
+    };
+    if args.count_only {
+        println!("{}", total_text);
+        return;
+    }
+
+    let mut output: Box<dyn Write> = match &args.output_file {
+        Some(path) => match fs::File::create(path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при создании файла {:?}: {}",
+                        "Failed to create file {:?}: {}",
+                        path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        },
+        None => Box::new(BufWriter::new(std::io::stdout())),
+    };
+    let mut written: u64 = 0;
+    for candidate in completions.into_iter().flatten().take(args.limit as usize) {
+        if let Err(e) = writeln!(output, "{}\t{}", candidate.mnemonic, candidate.entropy.to_hex()) {
+            error!(
+                "{}",
+                tr!("Ошибка при записи результатов: {}", "Failed to write results: {}", e)
+            );
+            std::process::exit(EXIT_IO);
+        }
+        written += 1;
+    }
+    if let Err(e) = output.flush() {
+        error!(
+            "{}",
+            tr!("Ошибка при записи результатов: {}", "Failed to write results: {}", e)
+        );
+        std::process::exit(EXIT_IO);
+    }
+
+    if quiet() {
+        return;
+    }
+    if let Some(output_path) = &args.output_file {
+        println!(
+            "{}",
+            tr!(
+                "✓ Кандидаты сохранены в файл: {:?}",
+                "✓ Candidates saved to file: {:?}",
+                output_path
+            )
+        );
+    }
+    info!(
+        "{}",
+        tr!(
+            "Продолжений с верной контрольной суммой: {}, выведено: {}",
+            "Completions with a valid checksum: {}, written: {}",
+            total_text,
+            written
+        )
+    );
+    if total.is_none_or(|total| u128::from(written) < total) {
+        warn!(
+            "{}",
+            tr!(
+                "Выведены не все продолжения: увеличьте --limit или укажите больше слов",
+                "Not all completions were written: raise --limit or give more words"
+            )
+        );
+    }
+}
+
+/// Выводит кандидатов в файл или в консоль.
+fn write_lines(args: &RecoverArgs, lines: &[String]) {
+    match &args.output_file {
+        Some(output_path) => match fs::File::create(output_path) {
+            Ok(mut file) => {
+                for line in lines {
+                    if let Err(e) = writeln!(file, "{}", line) {
+                        error!(
+                            "{}",
+                            tr!(
+                                "Ошибка при записи в файл {:?}: {}",
+                                "Failed to write file {:?}: {}",
+                                output_path,
+                                e
+                            )
+                        );
+                        std::process::exit(EXIT_IO);
+                    }
+                }
+                if !quiet() {
+                    println!(
+                        "{}",
+                        tr!(
+                            "✓ Кандидаты сохранены в файл: {:?}",
+                            "✓ Candidates saved to file: {:?}",
+                            output_path
+                        )
+                    );
+                }
+            }
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Ошибка при создании файла {:?}: {}",
+                        "Failed to create file {:?}: {}",
+                        output_path,
+                        e
+                    )
+                );
+                std::process::exit(EXIT_IO);
+            }
+        },
+        None => {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use super::derive::save_lines;
+use super::i18n::Localized;
+use super::input::{load_lines, stdin_piped};
+use super::{batch_exit_code, parse_language, quiet, report_timing, shown, EXIT_USAGE};
+use clap::{Args, Subcommand};
+use log::error;
+use mnem_to_entropy::{
+    decode, encode_in, seedxor_combine, seedxor_split, Language, Options, MAX_SEEDXOR_PARTS,
+};
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Subcommand, Debug)]
+pub enum SeedXorCommand {
+    /// Разделить мнемонику на доли SeedXOR (каждая — верная мнемоника BIP39)
+    Split(SplitArgs),
+    /// Собрать мнемонику из всех долей SeedXOR
+    Combine(CombineArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SplitArgs {
+    /// Разделяемая мнемоника
+    #[arg(short, long, env = "MNEM_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Файл с мнемониками (одна фраза на строку)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Option<PathBuf>,
+
+    /// Файл для сохранения долей: номер строки и доли через табуляцию
+    /// (такой файл принимает seedxor combine --input)
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Число долей (2–16)
+    #[arg(short, long, default_value_t = 3)]
+    pub parts: usize,
+
+    /// Использовать только словарь указанного языка; доли пишутся на языке фразы
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+}
+
+#[derive(Args, Debug)]
+pub struct CombineArgs {
+    /// Доля SeedXOR (указывается для каждой доли)
+    #[arg(short, long)]
+    pub mnemonic: Vec<String>,
+
+    /// Файл с наборами долей: по набору на строку, доли через табуляцию
+    /// (числовая колонка номера строки, как в выводе split, пропускается)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT", conflicts_with = "mnemonic")]
+    pub input_file: Option<PathBuf>,
+
+    /// Файл для сохранения результата: номер строки, энтропия и мнемоника
+    /// через табуляцию
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Использовать только словарь указанного языка; фраза пишется на языке долей
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+}
+
+pub fn run(command: &SeedXorCommand) {
+    match command {
+        SeedXorCommand::Split(args) => run_split(args),
+        SeedXorCommand::Combine(args) => run_combine(args),
+    }
+}
+
+/// Энтропия и язык фразы; для пользовательского словаря — английский.
+fn decode_phrase(mnemonic: &str, options: &Options) -> Result<(Vec<u8>, Language), String> {
+    let decoded = decode(mnemonic, options).map_err(|e| e.localized())?;
+    Ok((
+        decoded.entropy.into_bytes(),
+        decoded.language.unwrap_or(Language::English),
+    ))
+}
+
+fn split_mnemonic(mnemonic: &str, args: &SplitArgs, options: &Options) -> Result<Vec<String>, String> {
+    let (entropy, language) = decode_phrase(mnemonic, options)?;
+    let shares = seedxor_split(&entropy, args.parts).map_err(|e| e.localized())?;
+    Ok(shares
+        .iter()
+        .map(|share| encode_in(language, share).expect("длина доли проверена"))
+        .collect())
+}
+
+fn run_split(args: &SplitArgs) {
+    if !(2..=MAX_SEEDXOR_PARTS).contains(&args.parts) {
+        error!(
+            "{}",
+            tr!(
+                "Ошибка: число долей должно быть от 2 до {}",
+                "Error: the number of parts must be 2 to {}",
+                MAX_SEEDXOR_PARTS
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+    let mnemonics = load_lines(
+        args.input_file.as_deref(),
+        args.mnemonic.as_deref(),
+        &tr!("Введите мнемоническую фразу:", "Enter the mnemonic phrase:"),
+        args.output_file.is_some(),
+        false,
+    );
+    let options = Options {
+        language: args.language,
+        ..Options::default()
+    };
+
+    let started = Instant::now();
+    let results: Vec<Result<Vec<String>, String>> = mnemonics
+        .iter()
+        .map(|mnemonic| split_mnemonic(mnemonic, args, &options))
+        .collect();
+    report_timing(started, results.len());
+
+    let mut lines = Vec::new();
+    let mut error_count = 0;
+    for (idx, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(shares) => {
+                if args.output_file.is_none() && quiet() {
+                    // Наборы долей разделены пустой строкой
+                    if idx > 0 {
+                        println!();
+                    }
+                    for share in &shares {
+                        println!("{}", share);
+                    }
+                } else if args.output_file.is_none() {
+                    println!("{}", tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1));
+                    for (number, share) in shares.iter().enumerate() {
+                        println!(
+                            "{}",
+                            tr!(
+                                "Доля {}/{}: {}",
+                                "Part {}/{}: {}",
+                                number + 1,
+                                shares.len(),
+                                shown(share)
+                            )
+                        );
+                    }
+                }
+                lines.push(format!("{}\t{}", idx + 1, shares.join("\t")));
+            }
+            Err(message) => {
+                if !quiet() {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                error_count += 1;
+            }
+        }
+    }
+
+    if let Some(output_path) = &args.output_file {
+        let saved = tr!(
+            "✓ Доли SeedXOR сохранены в файл: {:?}",
+            "✓ SeedXOR parts saved to file: {:?}",
+            output_path
+        );
+        save_lines(output_path, &lines, &saved, mnemonics.len() - error_count, error_count);
+    }
+    std::process::exit(batch_exit_code(mnemonics.len() - error_count, error_count));
+}
+
+/// Наборы долей: из `--mnemonic` — один набор, из `--input` — по набору
+/// на строку, иначе один набор из stdin до пустой строки.
+fn load_sets(args: &CombineArgs) -> Vec<Vec<String>> {
+    if let Some(path) = &args.input_file {
+        return load_lines(Some(path), None, "", args.output_file.is_some(), false)
+            .iter()
+            .map(|line| {
+                line.split('\t')
+                    .map(str::trim)
+                    .filter(|share| !share.is_empty() && !share.chars().all(|c| c.is_ascii_digit()))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .collect();
+    }
+    if !args.mnemonic.is_empty() {
+        return vec![args.mnemonic.clone()];
+    }
+
+    if !quiet() && !stdin_piped() {
+        println!(
+            "{}",
+            tr!(
+                "Введите доли SeedXOR (по одной на строку, пустая строка — конец ввода):",
+                "Enter SeedXOR parts (one per line, an empty line ends the input):"
+            )
+        );
+    }
+    let mut shares = Vec::new();
+    for line in std::io::stdin().lock().lines() {
+        let line = line.expect("Не удалось прочитать ввод");
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        shares.push(line.to_string());
+    }
+    vec![shares]
+}
+
+/// Энтропия и мнемоника из набора долей; фраза — на языке первой доли.
+fn combine_set(shares: &[String], options: &Options) -> Result<(Vec<u8>, String), String> {
+    let mut entropies = Vec::with_capacity(shares.len());
+    let mut language = None;
+    for (number, share) in shares.iter().enumerate() {
+        let (entropy, share_language) = decode_phrase(share, options)
+            .map_err(|e| tr!("доля {}: {}", "part {}: {}", number + 1, e))?;
+        language.get_or_insert(share_language);
+        entropies.push(entropy);
+    }
+    let refs: Vec<&[u8]> = entropies.iter().map(Vec::as_slice).collect();
+    let entropy = seedxor_combine(&refs).map_err(|e| e.localized())?;
+    let mnemonic = encode_in(language.unwrap_or(Language::English), &entropy).expect("длина проверена");
+    Ok((entropy, mnemonic))
+}
+
+fn run_combine(args: &CombineArgs) {
+    let sets = load_sets(args);
+    let options = Options {
+        language: args.language,
+        ..Options::default()
+    };
+
+    let mut lines = Vec::new();
+    let mut error_count = 0;
+    for (idx, shares) in sets.iter().enumerate() {
+        match combine_set(shares, &options) {
+            Ok((entropy, mnemonic)) => {
+                if args.output_file.is_none() && quiet() {
+                    println!("{}", mnemonic);
+                } else if args.output_file.is_none() {
+                    println!("{}", tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1));
+                    println!("{}", tr!("Долей: {}", "Parts: {}", shares.len()));
+                    println!("{}", tr!("Энтропия: {}", "Entropy: {}", hex::encode(&entropy)));
+                    println!("{}", tr!("Мнемоническая фраза: {}", "Mnemonic: {}", shown(&mnemonic)));
+                }
+                lines.push(format!("{}\t{}\t{}", idx + 1, hex::encode(&entropy), mnemonic));
+            }
+            Err(message) => {
+                if !quiet() {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                error_count += 1;
+            }
+        }
+    }
+
+    if let Some(output_path) = &args.output_file {
+        let saved = tr!(
+            "✓ Собранные фразы сохранены в файл: {:?}",
+            "✓ Combined mnemonics saved to file: {:?}",
+            output_path
+        );
+        save_lines(output_path, &lines, &saved, sets.len() - error_count, error_count);
+    }
+    std::process::exit(batch_exit_code(sets.len() - error_count, error_count));
+}
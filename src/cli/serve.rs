@@ -0,0 +1,268 @@
+//! HTTP API (`serve`, функция `server`): `POST /decode` с одной мнемоникой
+//! или пачкой, ответ — JSON; `GET /metrics` — метрики Prometheus.
+
+use super::decode::{load_wordlist, ErrorKind};
+use super::i18n::Localized;
+use super::metrics::Metrics;
+use super::{parse_language, EXIT_IO};
+use clap::Args;
+use log::{error, info, warn};
+use mnem_to_entropy::{decode, language_name, Language, Options};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// Адрес и порт для входящих соединений
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub bind: String,
+
+    /// Наибольший размер тела запроса в байтах
+    #[arg(long, value_name = "BYTES", default_value = "1048576")]
+    pub max_body: usize,
+
+    /// Наибольшее число мнемоник в одном запросе
+    #[arg(long, value_name = "N", default_value = "10000")]
+    pub max_batch: usize,
+
+    /// Словарь по умолчанию; запрос может указать свой язык в поле "language"
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Пользовательский словарь из 2048 слов для всех запросов
+    #[arg(long, value_name = "FILE", conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+}
+
+/// Тело `POST /decode`: либо `mnemonic`, либо `mnemonics`.
+#[derive(Deserialize)]
+struct DecodeRequest {
+    mnemonic: Option<String>,
+    mnemonics: Option<Vec<String>>,
+    #[serde(default)]
+    ignore_checksum: bool,
+    language: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DecodeResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entropy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BatchResponse {
+    results: Vec<DecodeResult>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Ответ с кодом состояния, типом и телом.
+struct Reply {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Reply {
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        Reply {
+            status,
+            content_type: "application/json",
+            body: serde_json::to_string(value).expect("ответ сериализуется в JSON"),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Reply::json(status, &ErrorResponse { error: message.into() })
+    }
+}
+
+pub fn run(args: &ServeArgs) {
+    let server = match Server::http(args.bind.as_str()) {
+        Ok(server) => server,
+        Err(e) => {
+            error!(
+                "{}",
+                tr!("Не удалось открыть {}: {}", "Failed to bind {}: {}", args.bind, e)
+            );
+            std::process::exit(EXIT_IO);
+        }
+    };
+    let options = Options {
+        language: args.language,
+        wordlist: args.wordlist.as_deref().map(|path| Arc::new(load_wordlist(path))),
+        ..Options::default()
+    };
+    info!(
+        "{}",
+        tr!(
+            "Сервер слушает http://{} (POST /decode, GET /metrics)",
+            "Server listening on http://{} (POST /decode, GET /metrics)",
+            args.bind
+        )
+    );
+
+    let metrics = Metrics::default();
+    for mut request in server.incoming_requests() {
+        let path = match route(&request) {
+            "/decode" => "/decode",
+            "/metrics" => "/metrics",
+            _ => "other",
+        };
+        let started = Instant::now();
+        let reply = handle(&mut request, args, &options, &metrics);
+        if path == "/decode" {
+            metrics.latency(started.elapsed());
+        }
+        metrics.request(path, reply.status);
+        let header = Header::from_bytes(&b"Content-Type"[..], reply.content_type.as_bytes())
+            .expect("заголовок корректен");
+        let response = Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(header);
+        if let Err(e) = request.respond(response) {
+            warn!(
+                "{}",
+                tr!("Ошибка при отправке ответа: {}", "Failed to send the response: {}", e)
+            );
+        }
+    }
+}
+
+/// Путь запроса без строки параметров: tiny_http отдаёт их вместе в `url()`.
+fn route(request: &Request) -> &str {
+    request.url().split('?').next().unwrap_or_default()
+}
+
+fn handle(request: &mut Request, args: &ServeArgs, options: &Options, metrics: &Metrics) -> Reply {
+    let path = route(request);
+    if path == "/metrics" {
+        if *request.method() != Method::Get {
+            return Reply::error(405, tr!("ожидается метод GET", "expected method GET"));
+        }
+        return Reply {
+            status: 200,
+            content_type: "text/plain; version=0.0.4",
+            body: metrics.render(),
+        };
+    }
+    if path != "/decode" {
+        return Reply::error(
+            404,
+            tr!(
+                "неизвестный путь; доступны POST /decode и GET /metrics",
+                "unknown path; available: POST /decode and GET /metrics"
+            ),
+        );
+    }
+    if *request.method() != Method::Post {
+        return Reply::error(405, tr!("ожидается метод POST", "expected method POST"));
+    }
+    if request.body_length().is_some_and(|length| length > args.max_body) {
+        return Reply::error(
+            413,
+            tr!("тело запроса больше {} байт", "request body is larger than {} bytes", args.max_body),
+        );
+    }
+
+    // Длина может быть не указана (chunked), поэтому читаем не больше предела + 1
+    let mut body = Vec::new();
+    let limit = args.max_body as u64 + 1;
+    if let Err(e) = request.as_reader().take(limit).read_to_end(&mut body) {
+        return Reply::error(
+            400,
+            tr!("не удалось прочитать тело запроса: {}", "failed to read the request body: {}", e),
+        );
+    }
+    if body.len() > args.max_body {
+        return Reply::error(
+            413,
+            tr!("тело запроса больше {} байт", "request body is larger than {} bytes", args.max_body),
+        );
+    }
+
+    let decode_request: DecodeRequest = match serde_json::from_slice(&body) {
+        Ok(decode_request) => decode_request,
+        Err(e) => return Reply::error(400, tr!("неверный JSON: {}", "invalid JSON: {}", e)),
+    };
+
+    let mut options = Options {
+        ignore_checksum: decode_request.ignore_checksum,
+        ..options.clone()
+    };
+    if let Some(name) = &decode_request.language {
+        if options.wordlist.is_some() {
+            return Reply::error(
+                400,
+                tr!(
+                    "сервер использует пользовательский словарь; поле language недопустимо",
+                    "the server uses a custom wordlist; the language field is not allowed"
+                ),
+            );
+        }
+        match parse_language(name) {
+            Ok(language) => options.language = Some(language),
+            Err(message) => return Reply::error(400, message),
+        }
+    }
+
+    match (decode_request.mnemonic, decode_request.mnemonics) {
+        (Some(mnemonic), None) => Reply::json(200, &decode_one(&mnemonic, &options, metrics)),
+        (None, Some(mnemonics)) if mnemonics.len() > args.max_batch => Reply::error(
+            413,
+            tr!(
+                "в запросе больше {} мнемоник",
+                "the request has more than {} mnemonics",
+                args.max_batch
+            ),
+        ),
+        (None, Some(mnemonics)) => {
+            let results = mnemonics
+                .par_iter()
+                .map(|mnemonic| decode_one(mnemonic, &options, metrics))
+                .collect();
+            Reply::json(200, &BatchResponse { results })
+        }
+        _ => Reply::error(
+            400,
+            tr!(
+                "нужно ровно одно из полей: mnemonic или mnemonics",
+                "exactly one of the fields is required: mnemonic or mnemonics"
+            ),
+        ),
+    }
+}
+
+fn decode_one(mnemonic: &str, options: &Options, metrics: &Metrics) -> DecodeResult {
+    match decode(mnemonic, options) {
+        Ok(decoded) => {
+            metrics.success();
+            DecodeResult {
+                entropy: Some(decoded.entropy.to_hex()),
+                language: Some(decoded.language.map(language_name).unwrap_or("custom")),
+                error: None,
+            }
+        }
+        Err(e) => {
+            metrics.failure(ErrorKind::from(&e).name());
+            DecodeResult {
+                entropy: None,
+                language: None,
+                error: Some(e.localized()),
+            }
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use super::derive::save_lines;
+use super::i18n::Localized;
+use super::input::{load_lines, stdin_piped};
+use super::{batch_exit_code, parse_language, quiet, report_timing, shown};
+use clap::{Args, Subcommand};
+use mnem_to_entropy::{decode, encode_in, shamir_combine, shamir_split, Language, Options, ShamirShare};
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Subcommand, Debug)]
+pub enum ShamirCommand {
+    /// Разделить энтропию на hex-доли по схеме Шамира
+    Split(SplitArgs),
+    /// Собрать энтропию из hex-долей (не меньше порога)
+    Combine(CombineArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SplitArgs {
+    /// Энтропия в hex (128–256 бит)
+    #[arg(short, long, conflicts_with_all = ["mnemonic", "input_file"])]
+    pub entropy: Option<String>,
+
+    /// Мнемоника BIP39, энтропия которой делится на доли
+    #[arg(short, long, env = "MNEM_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Файл с секретами по одному на строку: hex-энтропия или мнемоника
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Option<PathBuf>,
+
+    /// Файл для сохранения долей: номер строки и доли через табуляцию
+    /// (такой файл принимает shamir combine --input)
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Сколько долей нужно для сборки
+    #[arg(short, long, default_value_t = 2)]
+    pub threshold: usize,
+
+    /// Число долей
+    #[arg(short = 'n', long, default_value_t = 3)]
+    pub shares: usize,
+
+    /// Декодировать мнемоники только по словарю указанного языка
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+}
+
+#[derive(Args, Debug)]
+pub struct CombineArgs {
+    /// Доля в hex (указывается для каждой доли)
+    #[arg(short, long)]
+    pub share: Vec<String>,
+
+    /// Файл с наборами долей: по набору на строку, доли через табуляцию
+    /// (колонка номера строки, как в выводе split, пропускается)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT", conflicts_with = "share")]
+    pub input_file: Option<PathBuf>,
+
+    /// Файл для сохранения результата: номер строки, энтропия и мнемоника
+    /// через табуляцию
+    #[arg(short = 'o', long = "output", env = "MNEM_OUTPUT")]
+    pub output_file: Option<PathBuf>,
+
+    /// Язык выводимой мнемоники (по умолчанию английский)
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+}
+
+pub fn run(command: &ShamirCommand) {
+    match command {
+        ShamirCommand::Split(args) => run_split(args),
+        ShamirCommand::Combine(args) => run_combine(args),
+    }
+}
+
+/// Энтропия секрета: строка из одних hex-символов читается как энтропия,
+/// иначе — как мнемоника.
+fn secret_entropy(secret: &str, options: &Options) -> Result<Vec<u8>, String> {
+    let secret = secret.trim();
+    if !secret.is_empty() && secret.chars().all(|c| c.is_ascii_hexdigit()) {
+        return hex::decode(secret).map_err(|e| tr!("Неверный hex: {}", "Invalid hex: {}", e));
+    }
+    decode(secret, options)
+        .map(|decoded| decoded.entropy.into_bytes())
+        .map_err(|e| e.localized())
+}
+
+fn split_secret(secret: &str, args: &SplitArgs, options: &Options) -> Result<Vec<String>, String> {
+    let entropy = secret_entropy(secret, options)?;
+    let shares = shamir_split(&entropy, args.threshold, args.shares).map_err(|e| e.localized())?;
+    Ok(shares.iter().map(ShamirShare::to_hex).collect())
+}
+
+fn run_split(args: &SplitArgs) {
+    let secrets = match &args.entropy {
+        Some(entropy) => vec![entropy.clone()],
+        None => load_lines(
+            args.input_file.as_deref(),
+            args.mnemonic.as_deref(),
+            &tr!(
+                "Введите энтропию в hex или мнемоническую фразу:",
+                "Enter the entropy in hex or the mnemonic phrase:"
+            ),
+            args.output_file.is_some(),
+            false,
+        ),
+    };
+    let options = Options {
+        language: args.language,
+        ..Options::default()
+    };
+
+    let started = Instant::now();
+    let results: Vec<Result<Vec<String>, String>> = secrets
+        .iter()
+        .map(|secret| split_secret(secret, args, &options))
+        .collect();
+    report_timing(started, results.len());
+
+    let mut lines = Vec::new();
+    let mut error_count = 0;
+    for (idx, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(shares) => {
+                if args.output_file.is_none() && quiet() {
+                    // Наборы долей разделены пустой строкой
+                    if idx > 0 {
+                        println!();
+                    }
+                    for share in &shares {
+                        println!("{}", share);
+                    }
+                } else if args.output_file.is_none() {
+                    println!("{}", tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1));
+                    println!(
+                        "{}",
+                        tr!(
+                            "Порог: {} из {}",
+                            "Threshold: {} of {}",
+                            args.threshold,
+                            args.shares
+                        )
+                    );
+                    for (number, share) in shares.iter().enumerate() {
+                        println!("{}", tr!("Доля {}: {}", "Share {}: {}", number + 1, share));
+                    }
+                }
+                lines.push(format!("{}\t{}", idx + 1, shares.join("\t")));
+            }
+            Err(message) => {
+                if !quiet() {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                error_count += 1;
+            }
+        }
+    }
+
+    if let Some(output_path) = &args.output_file {
+        let saved = tr!(
+            "✓ Доли сохранены в файл: {:?}",
+            "✓ Shares saved to file: {:?}",
+            output_path
+        );
+        save_lines(output_path, &lines, &saved, secrets.len() - error_count, error_count);
+    }
+    std::process::exit(batch_exit_code(secrets.len() - error_count, error_count));
+}
+
+/// Наборы долей: из `--share` — один набор, из `--input` — по набору
+/// на строку, иначе один набор из stdin до пустой строки.
+fn load_sets(args: &CombineArgs) -> Vec<Vec<String>> {
+    if let Some(path) = &args.input_file {
+        return load_lines(Some(path), None, "", args.output_file.is_some(), false)
+            .iter()
+            .map(|line| {
+                // Номер строки — короткое число, а доля не короче 44 hex-символов
+                // и в usize не помещается
+                line.split('\t')
+                    .map(str::trim)
+                    .filter(|share| !share.is_empty() && share.parse::<usize>().is_err())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .collect();
+    }
+    if !args.share.is_empty() {
+        return vec![args.share.clone()];
+    }
+
+    if !quiet() && !stdin_piped() {
+        println!(
+            "{}",
+            tr!(
+                "Введите доли (по одной на строку, пустая строка — конец ввода):",
+                "Enter the shares (one per line, an empty line ends the input):"
+            )
+        );
+    }
+    let mut shares = Vec::new();
+    for line in std::io::stdin().lock().lines() {
+        let line = line.expect("Не удалось прочитать ввод");
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        shares.push(line.to_string());
+    }
+    vec![shares]
+}
+
+/// Энтропия и мнемоника из набора долей.
+fn combine_set(shares: &[String], language: Language) -> Result<(Vec<u8>, String), String> {
+    let shares = shares
+        .iter()
+        .enumerate()
+        .map(|(number, share)| {
+            share
+                .parse::<ShamirShare>()
+                .map_err(|e| tr!("доля {}: {}", "share {}: {}", number + 1, e.localized()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let entropy = shamir_combine(&shares).map_err(|e| e.localized())?;
+    let mnemonic = encode_in(language, &entropy).map_err(|e| e.localized())?;
+    Ok((entropy, mnemonic))
+}
+
+fn run_combine(args: &CombineArgs) {
+    let sets = load_sets(args);
+    let language = args.language.unwrap_or(Language::English);
+
+    let mut lines = Vec::new();
+    let mut error_count = 0;
+    for (idx, shares) in sets.iter().enumerate() {
+        match combine_set(shares, language) {
+            Ok((entropy, mnemonic)) => {
+                if args.output_file.is_none() && quiet() {
+                    println!("{}", hex::encode(&entropy));
+                } else if args.output_file.is_none() {
+                    println!("{}", tr!("\n=== Результат {} ===", "\n=== Result {} ===", idx + 1));
+                    println!("{}", tr!("Долей: {}", "Shares: {}", shares.len()));
+                    println!("{}", tr!("Энтропия: {}", "Entropy: {}", hex::encode(&entropy)));
+                    println!("{}", tr!("Мнемоническая фраза: {}", "Mnemonic: {}", shown(&mnemonic)));
+                }
+                lines.push(format!("{}\t{}\t{}", idx + 1, hex::encode(&entropy), mnemonic));
+            }
+            Err(message) => {
+                if !quiet() {
+                    eprintln!("{}", tr!("\n=== Ошибка {} ===", "\n=== Error {} ===", idx + 1));
+                    eprintln!("{}", tr!("Ошибка: {}", "Error: {}", message));
+                }
+                error_count += 1;
+            }
+        }
+    }
+
+    if let Some(output_path) = &args.output_file {
+        let saved = tr!(
+            "✓ Собранная энтропия сохранена в файл: {:?}",
+            "✓ Combined entropy saved to file: {:?}",
+            output_path
+        );
+        save_lines(output_path, &lines, &saved, sets.len() - error_count, error_count);
+    }
+    std::process::exit(batch_exit_code(sets.len() - error_count, error_count));
+}
@@ -0,0 +1,231 @@
+use super::i18n::Localized;
+use super::input::read_text;
+use super::{quiet, EXIT_FAILED, EXIT_IO, EXIT_USAGE};
+use clap::{ArgGroup, Args, Subcommand};
+use log::error;
+use mnem_to_entropy::slip39::{combine, split, GroupSpec};
+use mnem_to_entropy::{decode, Options};
+use std::io::BufRead;
+use std::path::PathBuf;
+
+#[derive(Subcommand, Debug)]
+pub enum Slip39Command {
+    /// Восстановить мастер-секрет из долей SLIP-39
+    Combine(CombineArgs),
+    /// Разделить энтропию или мнемонику BIP39 на доли SLIP-39
+    Split(SplitArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CombineArgs {
+    /// Доля SLIP-39 (можно указать несколько раз)
+    #[arg(short, long)]
+    pub mnemonic: Vec<String>,
+
+    /// Файл с долями, одна доля на строку (можно указать несколько раз)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Vec<PathBuf>,
+
+    /// Пароль, которым зашифрован мастер-секрет
+    #[arg(long, env = "MNEM_SLIP39_PASSPHRASE", hide_env_values = true, default_value = "")]
+    pub passphrase: String,
+}
+
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("source").required(true).args(["entropy", "mnemonic"])))]
+pub struct SplitArgs {
+    /// Мастер-секрет в hex (не меньше 128 бит, чётное число байт)
+    #[arg(short, long)]
+    pub entropy: Option<String>,
+
+    /// Мнемоника BIP39, энтропия которой станет мастер-секретом
+    #[arg(short, long)]
+    pub mnemonic: Option<String>,
+
+    /// Группа в виде ПОРОГ/КОЛИЧЕСТВО, например 2/3 (можно указать несколько раз)
+    #[arg(short, long = "group", value_parser = parse_group, default_value = "2/3")]
+    pub groups: Vec<GroupSpec>,
+
+    /// Сколько групп нужно для восстановления
+    #[arg(long, default_value = "1")]
+    pub group_threshold: u8,
+
+    /// Пароль для шифрования мастер-секрета
+    #[arg(long, env = "MNEM_SLIP39_PASSPHRASE", hide_env_values = true, default_value = "")]
+    pub passphrase: String,
+
+    /// Показатель числа итераций PBKDF2 (10000 << e)
+    #[arg(long, default_value = "1")]
+    pub iteration_exponent: u8,
+
+    /// Создать расширяемую резервную копию
+    #[arg(long, default_value = "false")]
+    pub extendable: bool,
+}
+
+/// Парсер значения `--group` вида `2/3` или `2of3`.
+fn parse_group(value: &str) -> Result<GroupSpec, String> {
+    let (threshold, count) = value
+        .split_once('/')
+        .or_else(|| value.split_once("of"))
+        .ok_or_else(|| {
+            tr!(
+                "ожидается ПОРОГ/КОЛИЧЕСТВО, получено '{}'",
+                "expected THRESHOLD/COUNT, got '{}'",
+                value
+            )
+        })?;
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<u8>()
+            .map_err(|_| {
+                tr!(
+                    "неверное число '{}' в группе '{}'",
+                    "invalid number '{}' in group '{}'",
+                    s,
+                    value
+                )
+            })
+    };
+    Ok(GroupSpec {
+        threshold: parse(threshold)?,
+        count: parse(count)?,
+    })
+}
+
+pub fn run(command: &Slip39Command) {
+    match command {
+        Slip39Command::Combine(args) => run_combine(args),
+        Slip39Command::Split(args) => run_split(args),
+    }
+}
+
+/// Собирает доли из аргументов и файлов; если не задано ничего,
+/// читает их из stdin до пустой строки.
+fn load_shares(args: &CombineArgs) -> Vec<String> {
+    let mut shares = args.mnemonic.clone();
+
+    for path in &args.input_file {
+        match read_text(path) {
+            Ok(content) => shares.extend(
+                content
+                    .lines()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty()),
+            ),
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!("Ошибка при чтении файла {:?}: {}", "Failed to read file {:?}: {}", path, e)
+                );
+                std::process::exit(EXIT_IO);
+            }
+        }
+    }
+
+    if shares.is_empty() {
+        if !quiet() {
+            println!(
+                "{}",
+                tr!(
+                    "Введите доли SLIP-39 (по одной на строку, пустая строка — конец ввода):",
+                    "Enter SLIP-39 shares (one per line, an empty line ends the input):"
+                )
+            );
+        }
+        for line in std::io::stdin().lock().lines() {
+            let line = line.expect("Не удалось прочитать ввод");
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            shares.push(line.to_string());
+        }
+    }
+
+    shares
+}
+
+fn run_combine(args: &CombineArgs) {
+    let shares = load_shares(args);
+    let refs: Vec<&str> = shares.iter().map(String::as_str).collect();
+
+    match combine(&refs, &args.passphrase) {
+        Ok(secret) if quiet() => println!("{}", hex::encode(secret)),
+        Ok(secret) => {
+            println!("{}", tr!("Долей: {}", "Shares: {}", shares.len()));
+            println!("{}", tr!("Мастер-секрет: {}", "Master secret: {}", hex::encode(secret)));
+        }
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e.localized()));
+            std::process::exit(EXIT_FAILED);
+        }
+    }
+}
+
+fn run_split(args: &SplitArgs) {
+    let secret = match (&args.entropy, &args.mnemonic) {
+        (Some(entropy_hex), _) => hex::decode(entropy_hex.trim())
+            .map_err(|e| tr!("Неверный hex: {}", "Invalid hex: {}", e)),
+        (None, Some(mnemonic)) => decode(mnemonic, &Options::default())
+            .map(|decoded| decoded.entropy.into_bytes())
+            .map_err(|e| e.localized()),
+        (None, None) => unreachable!("clap требует --entropy или --mnemonic"),
+    };
+    let secret = secret.unwrap_or_else(|e| {
+        error!("{}", tr!("Ошибка: {}", "Error: {}", e));
+        std::process::exit(EXIT_FAILED);
+    });
+
+    let groups = match split(
+        &secret,
+        &args.passphrase,
+        args.group_threshold,
+        &args.groups,
+        args.extendable,
+        args.iteration_exponent,
+    ) {
+        Ok(groups) => groups,
+        Err(e) => {
+            error!("{}", tr!("Ошибка: {}", "Error: {}", e.localized()));
+            std::process::exit(EXIT_USAGE);
+        }
+    };
+
+    // С --quiet — только доли; группы разделены пустой строкой
+    if quiet() {
+        for (index, shares) in groups.iter().enumerate() {
+            if index > 0 {
+                println!();
+            }
+            for share in shares {
+                println!("{}", share);
+            }
+        }
+        return;
+    }
+    println!(
+        "{}",
+        tr!(
+            "Для восстановления нужно групп: {} из {}",
+            "Groups needed to recover: {} of {}",
+            args.group_threshold,
+            groups.len()
+        )
+    );
+    for (index, (spec, shares)) in args.groups.iter().zip(&groups).enumerate() {
+        println!(
+            "{}",
+            tr!(
+                "\n=== Группа {} ({} из {}) ===",
+                "\n=== Group {} ({} of {}) ===",
+                index + 1,
+                spec.threshold,
+                spec.count
+            )
+        );
+        for share in shares {
+            println!("{}", share);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Вывод результатов в базу SQLite (`--output-sqlite`, функция `sqlite`).
+
+use super::database::{Backend, OnConflict, Row};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+pub struct SqliteBackend {
+    connection: Connection,
+    insert: String,
+}
+
+impl SqliteBackend {
+    pub fn open(path: &Path, table: &str, on_conflict: OnConflict) -> rusqlite::Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                mnemonic_hash TEXT PRIMARY KEY,
+                entropy TEXT,
+                status TEXT NOT NULL,
+                error TEXT,
+                timestamp INTEGER NOT NULL
+            )",
+            table
+        ))?;
+        let verb = match on_conflict {
+            OnConflict::Update => "INSERT OR REPLACE",
+            OnConflict::Ignore => "INSERT OR IGNORE",
+        };
+        Ok(SqliteBackend {
+            connection,
+            insert: format!(
+                "{} INTO {} (mnemonic_hash, entropy, status, error, timestamp) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                verb, table
+            ),
+        })
+    }
+}
+
+impl Backend for SqliteBackend {
+    fn insert(&mut self, rows: &[Row]) -> Result<(), Box<dyn std::error::Error>> {
+        let transaction = self.connection.transaction()?;
+        {
+            let mut statement = transaction.prepare_cached(&self.insert)?;
+            for row in rows {
+                statement.execute(params![
+                    row.mnemonic_hash,
+                    row.entropy,
+                    row.status(),
+                    row.error,
+                    row.timestamp
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+//! Статистика прогона `decode` (`--stats`, `--stats-json`): итоги, ошибки
+//! по категориям, распределение по числу слов и языкам, скорость.
+
+use super::EXIT_IO;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Счётчики, накапливаемые по мере записи результатов.
+#[derive(Serialize, Default)]
+pub struct Stats {
+    pub total: usize,
+    pub success: usize,
+    pub failed: usize,
+    /// Повторы, отброшенные `--dedupe` (в `total` не входят).
+    pub duplicates: usize,
+    /// Строки, пропущенные `--filter-words` (в `total` не входят).
+    pub filtered: usize,
+    /// Строки, не вошедшие в выборку `--sample` (в `total` не входят).
+    pub unsampled: usize,
+    /// Ошибки по категориям: `unknown_words`, `word_count`, `checksum` и т.д.
+    pub errors: BTreeMap<&'static str, usize>,
+    /// Число строк по количеству слов (для успешных и ошибочных).
+    pub word_counts: BTreeMap<usize, usize>,
+    /// Успешные фразы по словарям: язык или `custom`.
+    pub languages: BTreeMap<&'static str, usize>,
+    /// Все строки по определённому языку, включая ошибочные и `unknown`
+    /// (с `--language-report`).
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub detected_languages: BTreeMap<&'static str, usize>,
+    pub elapsed_seconds: f64,
+    pub lines_per_second: f64,
+}
+
+impl Stats {
+    pub fn success(&mut self, words: usize, dictionary: &'static str) {
+        self.total += 1;
+        self.success += 1;
+        *self.word_counts.entry(words).or_default() += 1;
+        *self.languages.entry(dictionary).or_default() += 1;
+    }
+
+    pub fn failure(&mut self, words: usize, kind: &'static str) {
+        self.total += 1;
+        self.failed += 1;
+        *self.word_counts.entry(words).or_default() += 1;
+        *self.errors.entry(kind).or_default() += 1;
+    }
+
+    /// Учитывает язык строки для `--language-report`.
+    pub fn detected(&mut self, language: &'static str) {
+        *self.detected_languages.entry(language).or_default() += 1;
+    }
+
+    /// Доли строк по языкам, от самого частого: `92.0% english, 8.0% unknown`;
+    /// `None` без `--language-report`.
+    pub fn language_breakdown(&self) -> Option<String> {
+        let total: usize = self.detected_languages.values().sum();
+        if total == 0 {
+            return None;
+        }
+        let mut shares: Vec<(&str, usize)> =
+            self.detected_languages.iter().map(|(&language, &count)| (language, count)).collect();
+        shares.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        Some(
+            shares
+                .iter()
+                .map(|(language, count)| {
+                    format!("{:.1}% {}", *count as f64 * 100.0 / total as f64, language)
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+
+    /// Фиксирует время прогона и скорость.
+    pub fn finish(&mut self, elapsed: Duration) {
+        self.elapsed_seconds = elapsed.as_secs_f64();
+        self.lines_per_second = if self.elapsed_seconds > 0.0 {
+            self.total as f64 / self.elapsed_seconds
+        } else {
+            0.0
+        };
+    }
+
+    /// Выводит статистику в stderr, чтобы не смешивать её с результатами.
+    pub fn print(&self) {
+        eprintln!("{}", tr!("\n📊 Статистика", "\n📊 Statistics"));
+        eprintln!(
+            "{}",
+            tr!(
+                "  Строк: {}, успешно: {}, с ошибкой: {}",
+                "  Lines: {}, succeeded: {}, failed: {}",
+                self.total,
+                self.success,
+                self.failed
+            )
+        );
+        if self.duplicates > 0 {
+            eprintln!(
+                "{}",
+                tr!("  Повторов отброшено: {}", "  Duplicates dropped: {}", self.duplicates)
+            );
+        }
+        if self.filtered > 0 {
+            eprintln!(
+                "{}",
+                tr!(
+                    "  Пропущено по числу слов: {}",
+                    "  Skipped by word count: {}",
+                    self.filtered
+                )
+            );
+        }
+        if self.unsampled > 0 {
+            eprintln!(
+                "{}",
+                tr!("  Не вошло в выборку: {}", "  Left out of the sample: {}", self.unsampled)
+            );
+        }
+        if !self.errors.is_empty() {
+            eprintln!("{}", tr!("  Ошибки: {}", "  Errors: {}", join(&self.errors)));
+        }
+        if !self.word_counts.is_empty() {
+            eprintln!("{}", tr!("  Число слов: {}", "  Word counts: {}", join(&self.word_counts)));
+        }
+        if !self.languages.is_empty() {
+            eprintln!("{}", tr!("  Языки: {}", "  Languages: {}", join(&self.languages)));
+        }
+        if let Some(breakdown) = self.language_breakdown() {
+            eprintln!("{}", tr!("  Языки всех строк: {}", "  Languages of all lines: {}", breakdown));
+        }
+        eprintln!(
+            "{}",
+            tr!(
+                "  Время: {:.3} с, {:.0} строк в секунду",
+                "  Time: {:.3} s, {:.0} lines per second",
+                self.elapsed_seconds,
+                self.lines_per_second
+            )
+        );
+    }
+
+    /// Записывает статистику в файл JSON.
+    pub fn write_json(&self, path: &Path) {
+        let json = serde_json::to_string_pretty(self).expect("статистика сериализуется в JSON");
+        if let Err(e) = fs::write(path, json + "\n") {
+            eprintln!(
+                "{}",
+                tr!(
+                    "Ошибка при записи статистики в файл {:?}: {}",
+                    "Failed to write statistics to file {:?}: {}",
+                    path,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    }
+}
+
+/// `ключ: число` через запятую.
+fn join<K: std::fmt::Display>(counts: &BTreeMap<K, usize>) -> String {
+    counts
+        .iter()
+        .map(|(key, count)| format!("{}: {}", key, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
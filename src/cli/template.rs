@@ -0,0 +1,173 @@
+//! Шаблон строки вывода (`--template`): текст с подстановками `{поле}`.
+//! В шаблоне понимаются `\t`, `\n` и `\\`, а `{{` и `}}` дают фигурные скобки.
+
+use std::str::FromStr;
+
+/// Поле результата, доступное в шаблоне.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    /// Номер строки входа (с единицы).
+    Index,
+    /// Входной файл при нескольких файлах, иначе пусто.
+    File,
+    /// Номер строки во входном файле при нескольких файлах, иначе пусто.
+    FileLine,
+    /// Фраза как во входе (с `--redact` — скрытая).
+    Mnemonic,
+    /// Число слов фразы.
+    Words,
+    Entropy,
+    Language,
+    Fingerprint,
+    MasterFingerprint,
+    Xprv,
+    Xpub,
+    Indices,
+    Bits,
+    /// Исправленные опечатки (с `--autocorrect`).
+    Corrected,
+    /// Предупреждения о качестве энтропии (с `--check-entropy`).
+    Warnings,
+}
+
+impl Field {
+    const ALL: [Field; 15] = [
+        Field::Index,
+        Field::File,
+        Field::FileLine,
+        Field::Mnemonic,
+        Field::Words,
+        Field::Entropy,
+        Field::Language,
+        Field::Fingerprint,
+        Field::MasterFingerprint,
+        Field::Xprv,
+        Field::Xpub,
+        Field::Indices,
+        Field::Bits,
+        Field::Corrected,
+        Field::Warnings,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::Index => "index",
+            Field::File => "file",
+            Field::FileLine => "file_line",
+            Field::Mnemonic => "mnemonic",
+            Field::Words => "words",
+            Field::Entropy => "entropy",
+            Field::Language => "language",
+            Field::Fingerprint => "fingerprint",
+            Field::MasterFingerprint => "master_fingerprint",
+            Field::Xprv => "xprv",
+            Field::Xpub => "xpub",
+            Field::Indices => "indices",
+            Field::Bits => "bits",
+            Field::Corrected => "corrected",
+            Field::Warnings => "warnings",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Piece {
+    Text(String),
+    Field(Field),
+}
+
+/// Разобранный шаблон строки вывода.
+#[derive(Clone, Debug)]
+pub struct Template(Vec<Piece>);
+
+impl Template {
+    /// Есть ли в шаблоне поле.
+    pub fn uses(&self, field: Field) -> bool {
+        self.0.iter().any(|piece| matches!(piece, Piece::Field(used) if *used == field))
+    }
+
+    /// Строка по шаблону; значения полей даёт `value`.
+    pub fn render(&self, mut value: impl FnMut(Field) -> String) -> String {
+        let mut line = String::new();
+        for piece in &self.0 {
+            match piece {
+                Piece::Text(text) => line.push_str(text),
+                Piece::Field(field) => line.push_str(&value(*field)),
+            }
+        }
+        line
+    }
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => text.push('\t'),
+                    Some('n') => text.push('\n'),
+                    Some('\\') => text.push('\\'),
+                    Some(other) => {
+                        text.push('\\');
+                        text.push(other);
+                    }
+                    None => text.push('\\'),
+                },
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        return Err(tr!(
+                            "незакрытая '{{' в шаблоне; для скобки пишите '{{{{'",
+                            "unclosed '{{' in the template; write '{{{{' for a brace"
+                        ));
+                    }
+                    let field = Field::ALL.into_iter().find(|field| field.name() == name).ok_or_else(|| {
+                        let names: Vec<&str> = Field::ALL.iter().map(|field| field.name()).collect();
+                        tr!(
+                            "неизвестное поле {{{}}}; допустимые: {}",
+                            "unknown field {{{}}}; allowed: {}",
+                            name,
+                            names.join(", ")
+                        )
+                    })?;
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    pieces.push(Piece::Field(field));
+                }
+                '}' => {
+                    return Err(tr!(
+                        "одиночная '}}' в шаблоне; для скобки пишите '}}}}'",
+                        "single '}}' in the template; write '}}}}' for a brace"
+                    ))
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(Template(pieces))
+    }
+}
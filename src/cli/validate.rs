@@ -0,0 +1,181 @@
+use super::decode::load_wordlist;
+use super::i18n::Localized;
+use super::input::{decompress, load_lines, open_reader, text_reader, Compression};
+use super::{batch_exit_code, parse_language, quiet, report_timing};
+use super::pipeline::{self, Selection, Source};
+use clap::Args;
+use log::error;
+use mnem_to_entropy::{decode, is_aezeed, language_name, lenient_mnemonic, sanitize_mnemonic, Sanitize, DecodeError, Language, Options};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+#[derive(Args, Debug)]
+pub struct ValidateArgs {
+    /// Мнемоническая фраза
+    #[arg(short, long, env = "MNEM_MNEMONIC", hide_env_values = true)]
+    pub mnemonic: Option<String>,
+
+    /// Файл с мнемониками (одна фраза на строку)
+    #[arg(short = 'i', long = "input", env = "MNEM_INPUT")]
+    pub input_file: Option<PathBuf>,
+
+    /// Проверять только по словарю указанного языка
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Пользовательский словарь из 2048 слов (одно слово на строку)
+    #[arg(long, value_name = "FILE", conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+
+    /// Терпимый разбор переписанных фраз: нумерация, запятые и лишние пробелы
+    #[arg(long, default_value = "false")]
+    pub lenient: bool,
+
+    /// Очищать строки перед проверкой: all (по умолчанию) или этапы через
+    /// запятую — bom, zero-width, quotes, crlf, punctuation, lowercase
+    #[arg(long, value_name = "STEPS", num_args = 0..=1, default_missing_value = "all")]
+    pub sanitize: Option<Sanitize>,
+}
+
+/// Итог проверки одной фразы. Ни слова фразы, ни энтропия не сохраняются.
+pub(super) struct Verdict {
+    pub word_count: usize,
+    /// Словарь, по которому распознана фраза.
+    pub dictionary: Option<&'static str>,
+    /// Состояние контрольной суммы: `None`, если до проверки не дошло.
+    pub checksum: Option<bool>,
+    /// Причина отказа; `None` для прошедшей проверку фразы.
+    pub failure: Option<String>,
+}
+
+pub(super) fn validate(mnemonic_str: &str, options: &Options) -> Verdict {
+    let word_count = mnemonic_str.split_whitespace().count();
+    let dictionary_name =
+        |language: Option<Language>| Some(language.map(language_name).unwrap_or("custom"));
+
+    match decode(mnemonic_str, options) {
+        Ok(decoded) => Verdict {
+            word_count,
+            dictionary: dictionary_name(decoded.language),
+            checksum: Some(true),
+            failure: None,
+        },
+        Err(_) if is_aezeed(mnemonic_str) => Verdict {
+            word_count,
+            dictionary: Some("aezeed"),
+            checksum: Some(true),
+            failure: None,
+        },
+        // Сами неверные слова не выводим: проверка не должна раскрывать фразу
+        Err(DecodeError::InvalidWords(words)) => Verdict {
+            word_count,
+            dictionary: None,
+            checksum: None,
+            failure: Some(tr!(
+                "слов не из словаря: {}",
+                "words not in the wordlist: {}",
+                words.len()
+            )),
+        },
+        Err(e @ DecodeError::BadWordCount(_)) => Verdict {
+            word_count,
+            dictionary: None,
+            checksum: None,
+            failure: Some(e.localized()),
+        },
+        Err(DecodeError::InvalidChecksum(language)) => Verdict {
+            word_count,
+            dictionary: dictionary_name(language),
+            checksum: Some(false),
+            failure: Some(tr!("неверная контрольная сумма", "invalid checksum")),
+        },
+    }
+}
+
+pub fn run(args: &ValidateArgs) {
+    let source = match &args.input_file {
+        Some(path) => {
+            let (reader, _) = decompress(open_reader(path), Compression::Auto);
+            Source::reader(text_reader(reader, &format!("{:?}", path)), b'\n')
+        }
+        None => {
+            let lines = load_lines(
+                None,
+                args.mnemonic.as_deref(),
+                &tr!("Введите мнемоническую фразу:", "Enter the mnemonic phrase:"),
+                false,
+                false,
+            );
+            Source::Lines(lines.into_iter().map(Cow::Owned).collect())
+        }
+    };
+
+    let options = Options {
+        language: args.language,
+        wordlist: args.wordlist.as_deref().map(|path| Arc::new(load_wordlist(path))),
+        ..Options::default()
+    };
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let started = Instant::now();
+    // С --quiet — только строки таблицы, без заголовка и итога
+    if !quiet() {
+        println!("line\tstatus\twords\tlanguage\tchecksum\treason");
+    }
+    pipeline::run(
+        source,
+        0,
+        Selection::default(),
+        |_, _, mnemonic_str| {
+            let mut text = Cow::Borrowed(mnemonic_str);
+            if let Some(sanitize) = &args.sanitize {
+                text = Cow::Owned(sanitize_mnemonic(&text, sanitize).text);
+            }
+            if args.lenient {
+                text = Cow::Owned(lenient_mnemonic(&text));
+            }
+            validate(&text, &options)
+        },
+        |idx, _, _, verdict| {
+            let checksum = match verdict.checksum {
+                Some(true) => "ok",
+                Some(false) => "invalid",
+                None => "-",
+            };
+            let status = if verdict.failure.is_none() {
+                passed += 1;
+                "pass"
+            } else {
+                failed += 1;
+                "fail"
+            };
+            println!(
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                idx + 1,
+                status,
+                verdict.word_count,
+                verdict.dictionary.unwrap_or("-"),
+                checksum,
+                verdict.failure.as_deref().unwrap_or("")
+            );
+        },
+    );
+    report_timing(started, passed + failed);
+
+    if !quiet() {
+        error!(
+            "{}",
+            tr!(
+                "Проверено: {}, прошли: {}, не прошли: {}",
+                "Checked: {}, passed: {}, failed: {}",
+                passed + failed,
+                passed,
+                failed
+            )
+        );
+    }
+    std::process::exit(batch_exit_code(passed, failed));
+}
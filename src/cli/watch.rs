@@ -0,0 +1,152 @@
+//! Режим каталога-приёмника: новые файлы обрабатываются по мере появления.
+
+use super::decode::{self, encrypts_output, DecodeArgs};
+use super::{quiet, EXIT_FAILED, EXIT_IO, EXIT_USAGE};
+use log::{error, warn};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// Как часто просматривается каталог.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub fn run(args: &DecodeArgs) {
+    let (Some(watch_dir), Some(output_dir)) = (&args.watch, &args.watch_output) else {
+        return;
+    };
+    if !watch_dir.is_dir() {
+        error!("{}", tr!("Каталог {:?} не найден", "Directory {:?} not found", watch_dir));
+        std::process::exit(EXIT_IO);
+    }
+    if let Err(e) = fs::create_dir_all(output_dir) {
+        error!(
+            "{}",
+            tr!(
+                "Ошибка при создании каталога {:?}: {}",
+                "Failed to create directory {:?}: {}",
+                output_dir,
+                e
+            )
+        );
+        std::process::exit(EXIT_IO);
+    }
+    // Иначе результаты сами оказались бы новыми входными файлами
+    if fs::canonicalize(watch_dir).ok() == fs::canonicalize(output_dir).ok() {
+        error!(
+            "{}",
+            tr!(
+                "--watch и --watch-output должны быть разными каталогами",
+                "--watch and --watch-output must be different directories"
+            )
+        );
+        std::process::exit(EXIT_USAGE);
+    }
+
+    if !quiet() {
+        println!(
+            "{}",
+            tr!(
+                "👁 Слежение за каталогом {:?}, результаты в {:?} (Ctrl+C для выхода)",
+                "👁 Watching directory {:?}, results in {:?} (Ctrl+C to quit)",
+                watch_dir,
+                output_dir
+            )
+        );
+    }
+
+    // Размер файла при прошлом просмотре: файл берётся в обработку, только
+    // когда размер перестал меняться, то есть запись в него закончена
+    let mut pending: HashMap<PathBuf, u64> = HashMap::new();
+    let mut done: HashSet<PathBuf> = HashSet::new();
+    loop {
+        for (path, size) in scan(watch_dir) {
+            if done.contains(&path) {
+                continue;
+            }
+            let (output, error_log) = output_paths(args, &path, output_dir);
+            // Файлы, обработанные до перезапуска, повторно не разбираются
+            if output.exists() {
+                done.insert(path);
+                continue;
+            }
+            if pending.get(&path) != Some(&size) {
+                pending.insert(path, size);
+                continue;
+            }
+
+            pending.remove(&path);
+            if !quiet() {
+                println!("→ {:?}", path);
+            }
+            let file_args = DecodeArgs {
+                input_file: vec![PathBuf::from(glob::Pattern::escape(&path.to_string_lossy()))],
+                output_file: Some(output),
+                error_log: Some(error_log),
+                watch: None,
+                watch_output: None,
+                ..args.clone()
+            };
+            if decode::convert(&file_args) == EXIT_FAILED {
+                warn!(
+                    "{}",
+                    tr!(
+                        "⚠️  Файл {:?} не дал ни одного результата",
+                        "⚠️  File {:?} produced no results",
+                        path
+                    )
+                );
+            }
+            done.insert(path);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Обычные файлы каталога с их размерами, по имени. Скрытые файлы
+/// (обычно недокачанные) пропускаются.
+fn scan(dir: &Path) -> Vec<(PathBuf, u64)> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "Ошибка при чтении каталога {:?}: {}",
+                    "Failed to read directory {:?}: {}",
+                    dir,
+                    e
+                )
+            );
+            std::process::exit(EXIT_IO);
+        }
+    };
+    let mut files: Vec<(PathBuf, u64)> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| (entry.path(), metadata.len()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Пути результата и лога ошибок для входного файла: FILE.out и FILE.errors,
+/// с расширением сжатия при --compress-output и .age при --encrypt-output.
+fn output_paths(args: &DecodeArgs, input: &Path, output_dir: &Path) -> (PathBuf, PathBuf) {
+    let name = input.file_name().unwrap_or_default().to_string_lossy();
+    let mut suffix = args
+        .compress_output
+        .map(|compression| format!(".{}", compression.extension()))
+        .unwrap_or_default();
+    if encrypts_output(args) {
+        suffix.push_str(".age");
+    }
+    (
+        output_dir.join(format!("{}.out{}", name, suffix)),
+        output_dir.join(format!("{}.errors{}", name, suffix)),
+    )
+}
@@ -0,0 +1,159 @@
+//! Подкоманда `wordlist`: поиск слов в словарях BIP39 — по началу слова,
+//! регулярному выражению или похожему написанию. Помогает восстанавливать
+//! фразу по неполным записям.
+
+use super::decode::load_wordlist;
+use super::{parse_language, quiet, EXIT_FAILED, EXIT_USAGE};
+use clap::{Args, Subcommand};
+use log::{error, info};
+use mnem_to_entropy::{
+    language_name, normalize_mnemonic, typo_cost, Language, Wordlist, ALL_LANGUAGES,
+    MAX_TYPO_DISTANCE, WORDLIST_SIZE,
+};
+use regex::RegexBuilder;
+use std::path::PathBuf;
+use unicode_normalization::UnicodeNormalization;
+
+#[derive(Subcommand, Debug)]
+pub enum WordlistCommand {
+    /// Найти слова по началу, регулярному выражению (--regex) или похожему
+    /// написанию (--fuzzy): слово, индекс (десятичный и двоичный) и язык
+    Search(SearchArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct SearchArgs {
+    /// Начало слова; с --regex — регулярное выражение, с --fuzzy — слово
+    /// с возможными опечатками
+    pub pattern: String,
+
+    /// Искать по регулярному выражению без учёта регистра (без ^ и $
+    /// совпадение в любом месте слова)
+    #[arg(long, default_value = "false", conflicts_with = "fuzzy")]
+    pub regex: bool,
+
+    /// Искать похожие слова: не больше двух правок, замена соседней клавишей
+    /// и перестановка соседних букв — половина правки; ближайшие первыми
+    #[arg(long, default_value = "false")]
+    pub fuzzy: bool,
+
+    /// Искать только в словаре указанного языка (по умолчанию во всех)
+    #[arg(long, value_parser = parse_language)]
+    pub language: Option<Language>,
+
+    /// Искать в пользовательском словаре из 2048 слов (одно слово на строку)
+    #[arg(long, value_name = "FILE", conflicts_with = "language")]
+    pub wordlist: Option<PathBuf>,
+
+    /// Вывести не больше N слов
+    #[arg(long, value_name = "N")]
+    pub limit: Option<usize>,
+}
+
+/// Словарь, в котором идёт поиск.
+enum Dictionary {
+    Language(Language),
+    Custom(Wordlist),
+}
+
+impl Dictionary {
+    fn word(&self, index: u16) -> &str {
+        match self {
+            Dictionary::Language(language) => language.word_list()[index as usize],
+            Dictionary::Custom(wordlist) => wordlist.word(index),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Dictionary::Language(language) => language_name(*language),
+            Dictionary::Custom(_) => "custom",
+        }
+    }
+}
+
+/// Найденное слово; `cost` — цена опечатки для --fuzzy, иначе 0.
+struct Match<'a> {
+    cost: usize,
+    dictionary: &'a Dictionary,
+    index: u16,
+}
+
+pub fn run(command: &WordlistCommand) {
+    match command {
+        WordlistCommand::Search(args) => run_search(args),
+    }
+}
+
+fn run_search(args: &SearchArgs) {
+    let dictionaries: Vec<Dictionary> = match (&args.wordlist, args.language) {
+        (Some(path), _) => vec![Dictionary::Custom(load_wordlist(path))],
+        (None, Some(language)) => vec![Dictionary::Language(language)],
+        (None, None) => ALL_LANGUAGES.iter().copied().map(Dictionary::Language).collect(),
+    };
+
+    // Слова словарей хранятся в NFKD, в нём же сравниваются начало слова
+    // и опечатки. Регулярное выражение компилируется как есть: приведение
+    // к нижнему регистру испортило бы экранирования вроде \D и \P{..}
+    let pattern = normalize_mnemonic(args.pattern.trim()).to_lowercase();
+    let regex = args.regex.then(|| {
+        match RegexBuilder::new(&args.pattern).case_insensitive(true).build() {
+            Ok(regex) => regex,
+            Err(e) => {
+                error!(
+                    "{}",
+                    tr!(
+                        "Неверное регулярное выражение: {}",
+                        "Invalid regular expression: {}",
+                        e
+                    )
+                );
+                std::process::exit(EXIT_USAGE);
+            }
+        }
+    });
+    let cost = |word: &str| -> Option<usize> {
+        if let Some(regex) = &regex {
+            // Буквы с диакритикой в выражении обычно набраны в NFC
+            (regex.is_match(word) || regex.is_match(&word.nfc().collect::<String>())).then_some(0)
+        } else if args.fuzzy {
+            Some(typo_cost(&pattern, word)).filter(|&cost| cost <= 2 * MAX_TYPO_DISTANCE)
+        } else {
+            word.starts_with(&pattern).then_some(0)
+        }
+    };
+
+    let mut matches: Vec<Match> = dictionaries
+        .iter()
+        .flat_map(|dictionary| {
+            (0..WORDLIST_SIZE as u16).filter_map(move |index| {
+                cost(dictionary.word(index)).map(|cost| Match {
+                    cost,
+                    dictionary,
+                    index,
+                })
+            })
+        })
+        .collect();
+    // Сортировка устойчива: при равной цене сохраняется порядок языков и индексов
+    if args.fuzzy {
+        matches.sort_by_key(|found| found.cost);
+    }
+    matches.truncate(args.limit.unwrap_or(usize::MAX));
+
+    for found in &matches {
+        println!(
+            "{}\t{}\t{:011b}\t{}",
+            found.dictionary.word(found.index),
+            found.index,
+            found.index,
+            found.dictionary.name()
+        );
+    }
+    if !quiet() {
+        info!("{}", tr!("Найдено слов: {}", "Words found: {}", matches.len()));
+    }
+    if matches.is_empty() {
+        std::process::exit(EXIT_FAILED);
+    }
+}
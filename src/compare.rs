@@ -0,0 +1,99 @@
+//! Пословное сравнение двух мнемоник — для сверки двух чуть разных
+//! записей одной резервной копии.
+
+use crate::{decode, levenshtein, normalize_mnemonic, Decoded, DecodeError, Options, MAX_TYPO_DISTANCE};
+
+/// Чем различаются слова на одной позиции.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordChange {
+    /// Опечатка: расстояние Левенштейна не больше [`MAX_TYPO_DISTANCE`].
+    Typo { distance: usize },
+    /// Слова переставлены: слова позиций `position` и `with` в одной фразе
+    /// стоят в другой в обратном порядке.
+    Transposed { with: usize },
+    /// Слово есть только в одной из фраз (фразы разной длины).
+    Missing,
+    /// Просто другое слово.
+    Different,
+}
+
+/// Различие на одной позиции.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordDifference {
+    /// Позиция во фразах (с нуля).
+    pub position: usize,
+    /// Слово первой фразы; `None`, если она короче.
+    pub left: Option<String>,
+    /// Слово второй фразы; `None`, если она короче.
+    pub right: Option<String>,
+    pub change: WordChange,
+}
+
+/// Результат сравнения двух фраз.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MnemonicDiff {
+    /// Число слов первой и второй фразы.
+    pub word_counts: (usize, usize),
+    /// Позиции, на которых слова различаются, по возрастанию.
+    pub differences: Vec<WordDifference>,
+    /// Результат декодирования первой фразы.
+    pub left: Result<Decoded, DecodeError>,
+    /// Результат декодирования второй фразы.
+    pub right: Result<Decoded, DecodeError>,
+}
+
+impl MnemonicDiff {
+    /// Обе фразы декодируются в одну и ту же энтропию — например, одна
+    /// фраза на разных языках.
+    pub fn same_entropy(&self) -> bool {
+        match (&self.left, &self.right) {
+            (Ok(left), Ok(right)) => left.entropy == right.entropy,
+            _ => false,
+        }
+    }
+}
+
+/// Сравнивает фразы пословно (после NFKD): различающиеся позиции
+/// с признаком опечатки или перестановки и энтропия каждой фразы.
+pub fn diff_mnemonics(left: &str, right: &str, options: &Options) -> MnemonicDiff {
+    let (left, right) = (normalize_mnemonic(left), normalize_mnemonic(right));
+    let left_words: Vec<&str> = left.split_whitespace().collect();
+    let right_words: Vec<&str> = right.split_whitespace().collect();
+
+    let differing: Vec<usize> = (0..left_words.len().max(right_words.len()))
+        .filter(|&position| left_words.get(position) != right_words.get(position))
+        .collect();
+    let differences = differing
+        .iter()
+        .map(|&position| {
+            let (left_word, right_word) = (left_words.get(position), right_words.get(position));
+            let transposed = differing.iter().copied().find(|&other| {
+                other != position
+                    && left_word.is_some()
+                    && left_word == right_words.get(other)
+                    && left_words.get(other) == right_word
+            });
+            let change = match (left_word, right_word, transposed) {
+                (None, _, _) | (_, None, _) => WordChange::Missing,
+                (_, _, Some(with)) => WordChange::Transposed { with },
+                (Some(left_word), Some(right_word), None) => match levenshtein(left_word, right_word) {
+                    distance if distance <= MAX_TYPO_DISTANCE => WordChange::Typo { distance },
+                    _ => WordChange::Different,
+                },
+            };
+            WordDifference {
+                position,
+                left: left_word.map(|word| word.to_string()),
+                right: right_word.map(|word| word.to_string()),
+                change,
+            }
+        })
+        .collect();
+
+    MnemonicDiff {
+        word_counts: (left_words.len(), right_words.len()),
+        differences,
+        left: decode(&left, options),
+        right: decode(&right, options),
+    }
+}
@@ -0,0 +1,300 @@
+//! Деривация адресов по BIP44/49/84/86: Bitcoin и Ethereum; Solana, Cardano
+//! и Substrate — в модулях `solana`, `cardano` и `substrate`.
+
+use crate::keys::secp;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv, Xpub};
+use bitcoin::{Address, CompressedPublicKey, Network};
+use sha3::{Digest, Keccak256};
+use std::fmt;
+use std::str::FromStr;
+
+/// Монета, для которой выводятся адреса.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coin {
+    Bitcoin,
+    /// Ethereum и EVM-сети (BSC, Polygon, Avalanche C-Chain, Arbitrum,
+    /// Optimism, Base): у всех путь `m/44'/60'/...` и одинаковые адреса.
+    Ethereum,
+    /// Tron: путь `m/44'/195'/...`, адреса Base58Check `T...`.
+    Tron,
+    Solana,
+    Cardano,
+    /// Polkadot и другие сети Substrate: ключи sr25519/ed25519 и адреса SS58
+    /// (модуль `substrate`), а не пути BIP44.
+    Polkadot,
+}
+
+impl Coin {
+    /// Тип монеты BIP44 (SLIP-44) с учётом сети.
+    pub fn coin_type(self, network: Network) -> u32 {
+        match self {
+            Coin::Bitcoin => bitcoin_coin_type(network),
+            Coin::Ethereum => 60,
+            Coin::Tron => 195,
+            Coin::Solana => crate::solana::SOLANA_COIN_TYPE,
+            Coin::Cardano => crate::cardano::CARDANO_COIN_TYPE,
+            Coin::Polkadot => crate::substrate::POLKADOT_COIN_TYPE,
+        }
+    }
+}
+
+impl FromStr for Coin {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "btc" | "bitcoin" => Ok(Coin::Bitcoin),
+            "eth" | "ethereum" => Ok(Coin::Ethereum),
+            // EVM-сети используют ключи и адреса Ethereum, как MetaMask
+            "bsc" | "bnb" | "polygon" | "matic" | "pol" | "avax" | "avalanche" | "arbitrum" | "arb"
+            | "optimism" | "op" | "base" => Ok(Coin::Ethereum),
+            "trx" | "tron" => Ok(Coin::Tron),
+            "sol" | "solana" => Ok(Coin::Solana),
+            "ada" | "cardano" => Ok(Coin::Cardano),
+            "dot" | "polkadot" | "substrate" => Ok(Coin::Polkadot),
+            _ => Err(format!(
+                "неизвестная монета '{}'; допустимые: btc, eth (и EVM-сети bsc, polygon, avax, arbitrum, optimism, base), trx, sol, ada, dot",
+                s
+            )),
+        }
+    }
+}
+
+/// Тип адреса Bitcoin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    /// Legacy, BIP44 (`1...`).
+    P2pkh,
+    /// SegWit внутри P2SH, BIP49 (`3...`).
+    P2shP2wpkh,
+    /// Native SegWit, BIP84 (`bc1q...`).
+    P2wpkh,
+    /// Taproot, BIP86 (`bc1p...`).
+    P2tr,
+    /// Адрес Ethereum с контрольной суммой EIP-55 (`0x...`).
+    Ethereum,
+    /// Адрес Tron: байт 0x41 и хэш как у Ethereum в Base58Check (`T...`).
+    Tron,
+    /// Адрес Solana: открытый ключ Ed25519 в Base58. Выводится из seed
+    /// по SLIP-0010 ([`derive_solana_addresses`](crate::derive_solana_addresses)),
+    /// а не из ключа BIP32.
+    Solana,
+    /// Базовый адрес Cardano (CIP-19, `addr1...`). Выводится из энтропии
+    /// фразы по схеме Icarus ([`derive_cardano_addresses`](crate::derive_cardano_addresses)).
+    Cardano,
+}
+
+impl AddressType {
+    /// Номер purpose из BIP44-пути для этого типа адреса.
+    pub fn purpose(self) -> u32 {
+        match self {
+            AddressType::P2pkh | AddressType::Ethereum | AddressType::Tron | AddressType::Solana => 44,
+            AddressType::P2shP2wpkh => 49,
+            AddressType::P2wpkh => 84,
+            AddressType::P2tr => 86,
+            AddressType::Cardano => crate::cardano::CARDANO_PURPOSE,
+        }
+    }
+
+    /// Тип адреса по номеру purpose (44/49/84/86).
+    pub fn from_purpose(purpose: u32) -> Option<Self> {
+        match purpose {
+            44 => Some(AddressType::P2pkh),
+            49 => Some(AddressType::P2shP2wpkh),
+            84 => Some(AddressType::P2wpkh),
+            86 => Some(AddressType::P2tr),
+            _ => None,
+        }
+    }
+
+    /// Тип адреса по первому элементу пути (`m/84'/...` → P2WPKH).
+    pub fn from_path(path: &DerivationPath) -> Option<Self> {
+        match path.as_ref().first()? {
+            ChildNumber::Hardened { index } => Self::from_purpose(*index),
+            ChildNumber::Normal { .. } => None,
+        }
+    }
+}
+
+impl FromStr for AddressType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "p2pkh" | "legacy" => Ok(AddressType::P2pkh),
+            "p2sh-p2wpkh" | "p2sh" | "nested" => Ok(AddressType::P2shP2wpkh),
+            "p2wpkh" | "segwit" => Ok(AddressType::P2wpkh),
+            "p2tr" | "taproot" => Ok(AddressType::P2tr),
+            "eth" | "ethereum" => Ok(AddressType::Ethereum),
+            "trx" | "tron" => Ok(AddressType::Tron),
+            "sol" | "solana" => Ok(AddressType::Solana),
+            "ada" | "cardano" => Ok(AddressType::Cardano),
+            _ => Err(format!(
+                "неизвестный тип адреса '{}'; допустимые: p2pkh, p2sh-p2wpkh, p2wpkh, p2tr, ethereum, tron, solana, cardano",
+                s
+            )),
+        }
+    }
+}
+
+/// Ошибка деривации.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeriveError {
+    /// Ошибка BIP32 (недопустимый индекс, деривация и т.п.).
+    Bip32(bitcoin::bip32::Error),
+    /// Обычный (не hardened) индекс в пути SLIP-0010 для Ed25519.
+    NonHardened(ChildNumber),
+    /// Тип адреса не выводится из ключа BIP32 (Solana, Cardano).
+    Unsupported(AddressType),
+    /// Число слов дочерней фразы BIP85 не из 12/15/18/21/24.
+    BadWordCount(usize),
+}
+
+impl fmt::Display for DeriveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeriveError::Bip32(e) => write!(f, "Ошибка деривации BIP32: {}", e),
+            DeriveError::NonHardened(index) => write!(
+                f,
+                "Ed25519 допускает только hardened-индексы, а в пути есть {}",
+                index
+            ),
+            DeriveError::Unsupported(address_type) => write!(
+                f,
+                "Адреса {:?} выводятся не из ключа BIP32",
+                address_type
+            ),
+            DeriveError::BadWordCount(words) => write!(
+                f,
+                "BIP85 выводит фразы из 12/15/18/21/24 слов, а не из {}",
+                words
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeriveError {}
+
+impl From<bitcoin::bip32::Error> for DeriveError {
+    fn from(e: bitcoin::bip32::Error) -> Self {
+        DeriveError::Bip32(e)
+    }
+}
+
+/// Адрес вместе с полным путём деривации.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedAddress {
+    pub path: DerivationPath,
+    pub address: String,
+}
+
+/// Путь аккаунта в стиле BIP44: `m/purpose'/coin_type'/account'`.
+pub fn account_path(purpose: u32, coin_type: u32, account: u32) -> Result<DerivationPath, DeriveError> {
+    Ok(DerivationPath::from(vec![
+        ChildNumber::from_hardened_idx(purpose)?,
+        ChildNumber::from_hardened_idx(coin_type)?,
+        ChildNumber::from_hardened_idx(account)?,
+    ]))
+}
+
+/// Расширенный публичный ключ аккаунта по пути `account` от корня
+/// (например `m/84'/0'/0'`): по нему видны все адреса аккаунта, но не ключи.
+pub fn account_xpub(root: &Xpriv, account: &DerivationPath) -> Result<Xpub, DeriveError> {
+    let secp = secp();
+    Ok(Xpub::from_priv(secp, &root.derive_priv(secp, account)?))
+}
+
+/// Тип монеты BIP44 для сети: 0 для mainnet, 1 для всех тестовых сетей.
+pub fn bitcoin_coin_type(network: Network) -> u32 {
+    match network {
+        Network::Bitcoin => 0,
+        _ => 1,
+    }
+}
+
+/// Выводит `count` адресов `base/start .. base/(start+count-1)`.
+///
+/// `base` — путь цепочки, например `m/84'/0'/0'/0`; индексы адресов
+/// добавляются к нему как обычные (не hardened) элементы.
+pub fn derive_addresses(
+    root: &Xpriv,
+    network: Network,
+    base: &DerivationPath,
+    address_type: AddressType,
+    start: u32,
+    count: u32,
+) -> Result<Vec<DerivedAddress>, DeriveError> {
+    if matches!(address_type, AddressType::Solana | AddressType::Cardano) {
+        return Err(DeriveError::Unsupported(address_type));
+    }
+    let secp = secp();
+    let chain = Xpub::from_priv(secp, &root.derive_priv(secp, base)?);
+
+    (start..start.saturating_add(count))
+        .map(|index| {
+            let child_number = ChildNumber::from_normal_idx(index)?;
+            let child = chain.derive_pub(secp, &[child_number])?;
+            Ok(DerivedAddress {
+                path: base.child(child_number),
+                address: encode_address(&child, network, address_type),
+            })
+        })
+        .collect()
+}
+
+fn encode_address(key: &Xpub, network: Network, address_type: AddressType) -> String {
+    match address_type {
+        AddressType::Ethereum => return eip55_checksum(&ethereum_hash(key)),
+        AddressType::Tron => return tron_address(key),
+        _ => {}
+    }
+
+    let public_key = CompressedPublicKey(key.public_key);
+    let address = match address_type {
+        AddressType::P2pkh => Address::p2pkh(public_key, network),
+        AddressType::P2shP2wpkh => Address::p2shwpkh(&public_key, network),
+        AddressType::P2wpkh => Address::p2wpkh(&public_key, network),
+        AddressType::P2tr => {
+            let (internal_key, _) = key.public_key.x_only_public_key();
+            Address::p2tr(secp(), internal_key, None, network)
+        }
+        AddressType::Ethereum | AddressType::Tron | AddressType::Solana | AddressType::Cardano => {
+            unreachable!("обработано выше")
+        }
+    };
+    address.to_string()
+}
+
+/// Адрес Ethereum: последние 20 байт Keccak-256 от несжатого публичного ключа.
+fn ethereum_hash(key: &Xpub) -> [u8; 20] {
+    let uncompressed = key.public_key.serialize_uncompressed();
+    let hash = Keccak256::digest(&uncompressed[1..]);
+    hash[12..].try_into().expect("срез 20 байт")
+}
+
+/// Адрес Tron: префикс 0x41 и хэш адреса Ethereum в Base58Check.
+fn tron_address(key: &Xpub) -> String {
+    let mut payload = vec![0x41];
+    payload.extend_from_slice(&ethereum_hash(key));
+    bitcoin::base58::encode_check(&payload)
+}
+
+/// Записывает 20-байтовый адрес в hex с контрольной суммой EIP-55:
+/// буква в верхнем регистре, если соответствующий полубайт Keccak-256
+/// от hex-строки адреса не меньше 8.
+pub fn eip55_checksum(address: &[u8]) -> String {
+    let lower = hex::encode(address);
+    let hash = Keccak256::digest(lower.as_bytes());
+
+    let mut out = String::with_capacity(2 + lower.len());
+    out.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+        if nibble >= 8 {
+            out.push(c.to_ascii_uppercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,80 @@
+//! Дескрипторы вывода Bitcoin (BIP380–386) для импорта в Bitcoin Core или Sparrow.
+
+use crate::derive::AddressType;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv};
+
+/// Алфавит входа контрольной суммы дескриптора (BIP380).
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+
+/// Алфавит самой контрольной суммы (как в bech32).
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Строит дескриптор цепочки `chain` от корневого ключа с контрольной суммой,
+/// например `wpkh(xprv.../84h/0h/0h/0/*)#...`; адреса цепочки — `chain/i`.
+///
+/// Для Ethereum, Tron, Solana и Cardano дескрипторов нет — возвращается `None`.
+pub fn output_descriptor(root: &Xpriv, chain: &DerivationPath, address_type: AddressType) -> Option<String> {
+    let path: String = chain
+        .as_ref()
+        .iter()
+        .map(|child| match child {
+            ChildNumber::Normal { index } => format!("/{}", index),
+            ChildNumber::Hardened { index } => format!("/{}h", index),
+        })
+        .collect();
+    let key = format!("{}{}/*", root, path);
+    let descriptor = match address_type {
+        AddressType::P2pkh => format!("pkh({})", key),
+        AddressType::P2shP2wpkh => format!("sh(wpkh({}))", key),
+        AddressType::P2wpkh => format!("wpkh({})", key),
+        AddressType::P2tr => format!("tr({})", key),
+        AddressType::Ethereum | AddressType::Tron | AddressType::Solana | AddressType::Cardano => {
+            return None
+        }
+    };
+    let checksum = descriptor_checksum(&descriptor)?;
+    Some(format!("{}#{}", descriptor, checksum))
+}
+
+/// Контрольная сумма дескриптора из 8 символов (BIP380); `None`, если в
+/// дескрипторе есть символы вне алфавита.
+pub fn descriptor_checksum(descriptor: &str) -> Option<String> {
+    let mut symbols = Vec::with_capacity(descriptor.len() * 4 / 3 + 9);
+    let mut groups = Vec::with_capacity(3);
+    for c in descriptor.chars() {
+        let value = INPUT_CHARSET.find(c)? as u64;
+        symbols.push(value & 31);
+        groups.push(value >> 5);
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+    match groups.as_slice() {
+        [a] => symbols.push(*a),
+        [a, b] => symbols.push(a * 3 + b),
+        _ => {}
+    }
+    symbols.extend([0; 8]);
+
+    let checksum = polymod(&symbols) ^ 1;
+    Some(
+        (0..8)
+            .map(|i| CHECKSUM_CHARSET[(checksum >> (5 * (7 - i)) & 31) as usize] as char)
+            .collect(),
+    )
+}
+
+fn polymod(symbols: &[u64]) -> u64 {
+    const GENERATOR: [u64; 5] = [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+    symbols.iter().fold(1, |c, &value| {
+        let top = c >> 35;
+        let c = (c & 0x7ffffffff) << 5 ^ value;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| top >> i & 1 == 1)
+            .fold(c, |c, (_, generator)| c ^ generator)
+    })
+}
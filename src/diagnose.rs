@@ -0,0 +1,91 @@
+//! Пословная диагностика мнемоники: подробное продолжение [`analyze_mnemonic`].
+
+use crate::recover::{best_dictionary, Dictionary};
+use crate::{
+    analyze_mnemonic, decode, languages_containing, levenshtein, normalize_mnemonic, DecodeError,
+    Language, Options, WORDLIST_SIZE,
+};
+
+/// Сколько ближайших слов словаря показывать для неизвестного слова.
+pub const NEAREST_LIMIT: usize = 5;
+
+/// Диагностика одного слова.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordDiagnostic {
+    /// Позиция во фразе (с нуля).
+    pub position: usize,
+    /// Слово как во фразе.
+    pub word: String,
+    /// Индекс в выбранном словаре; `None`, если слова в нём нет.
+    pub index: Option<u16>,
+    /// Ближайшие по расстоянию Левенштейна слова словаря (только для неизвестных слов).
+    pub nearest: Vec<String>,
+    /// Словари BIP39, в которых встречается слово.
+    pub languages: Vec<Language>,
+}
+
+/// Пословный отчёт о фразе.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// Словарь, в котором нашлось больше всего слов фразы;
+    /// `None` для пользовательского словаря.
+    pub language: Option<Language>,
+    /// Отчёт по каждому слову.
+    pub words: Vec<WordDiagnostic>,
+    /// Причина, по которой фраза не декодируется; `None`, если она верна.
+    pub error: Option<DecodeError>,
+}
+
+/// Пословная диагностика фразы относительно словарей, разрешённых в `options`.
+pub fn diagnose_mnemonic(mnemonic_str: &str, options: &Options) -> Diagnosis {
+    let mnemonic_str = &*normalize_mnemonic(mnemonic_str);
+    let words: Vec<&str> = mnemonic_str.split_whitespace().collect();
+    let dictionary = best_dictionary(&words, options);
+
+    let strict = Options {
+        ignore_checksum: false,
+        ..options.clone()
+    };
+    let error = decode(mnemonic_str, &strict)
+        .err()
+        .map(|_| analyze_mnemonic(mnemonic_str, &strict));
+
+    let words = words
+        .iter()
+        .enumerate()
+        .map(|(position, word)| {
+            let index = dictionary.find_word(word);
+            WordDiagnostic {
+                position,
+                word: word.to_string(),
+                index,
+                nearest: match index {
+                    Some(_) => Vec::new(),
+                    None => nearest_words(&dictionary, word),
+                },
+                languages: languages_containing(&[word]),
+            }
+        })
+        .collect();
+
+    Diagnosis {
+        language: dictionary.language(),
+        words,
+        error,
+    }
+}
+
+/// Слова словаря на минимальном расстоянии от `word`, не больше [`NEAREST_LIMIT`].
+fn nearest_words(dictionary: &Dictionary, word: &str) -> Vec<String> {
+    let distances: Vec<usize> = (0..WORDLIST_SIZE as u16)
+        .map(|index| levenshtein(word, dictionary.word(index)))
+        .collect();
+    let best = distances.iter().copied().min().unwrap_or(0);
+    distances
+        .iter()
+        .enumerate()
+        .filter(|(_, &distance)| distance == best)
+        .take(NEAREST_LIMIT)
+        .map(|(index, _)| dictionary.word(index as u16).to_string())
+        .collect()
+}
@@ -0,0 +1,274 @@
+//! Энтропия из бросков игральной кости или монеты — для тех, кто создаёт
+//! seed вручную и хочет проверить результат.
+//!
+//! Биты извлекаются без смещения: исходы броска делятся на блоки размером
+//! в степень двойки (для d6 — 4 + 2, для d20 — 16 + 4), и бросок даёт номер
+//! исхода внутри своего блока. При честной кости внутри блока все исходы
+//! равновероятны, поэтому каждый бит равномерен, а остаток от деления
+//! (как у `бросок mod 4`) не искажает распределение.
+
+use crate::VALID_WORD_COUNTS;
+use std::fmt;
+use std::str::FromStr;
+
+/// Источник случайности.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Die {
+    /// Шестигранная кость: цифры 1–6, разделители не обязательны.
+    D6,
+    /// Двадцатигранная кость: числа 1–20 через пробел или запятую.
+    D20,
+    /// Монета: `H` (орёл) и `T` (решка).
+    Coin,
+}
+
+impl Die {
+    /// Число равновероятных исходов броска.
+    pub fn sides(self) -> u32 {
+        match self {
+            Die::D6 => 6,
+            Die::D20 => 20,
+            Die::Coin => 2,
+        }
+    }
+
+    /// Среднее число бит на бросок при извлечении без смещения.
+    pub fn bits_per_roll(self) -> f64 {
+        let sides = self.sides();
+        let bits: u32 = (0..sides).map(|value| roll_bits(value, sides).len() as u32).sum();
+        f64::from(bits) / f64::from(sides)
+    }
+
+    /// Броски из текста в виде номеров исходов 0..sides.
+    fn parse_rolls(self, text: &str) -> Result<Vec<u32>, DiceError> {
+        let separator = |c: char| c.is_whitespace() || matches!(c, ',' | ';' | '-');
+        match self {
+            Die::D6 => text
+                .chars()
+                .filter(|&c| !separator(c))
+                .map(|c| match c.to_digit(10) {
+                    Some(value @ 1..=6) => Ok(value - 1),
+                    _ => Err(DiceError::InvalidRoll(c.to_string())),
+                })
+                .collect(),
+            Die::D20 => text
+                .split(separator)
+                .filter(|token| !token.is_empty())
+                .map(|token| match token.parse::<u32>() {
+                    Ok(value @ 1..=20) => Ok(value - 1),
+                    _ => Err(DiceError::InvalidRoll(token.to_string())),
+                })
+                .collect(),
+            Die::Coin => text
+                .chars()
+                .filter(|&c| !separator(c))
+                .map(|c| match c.to_ascii_uppercase() {
+                    'H' => Ok(1),
+                    'T' => Ok(0),
+                    _ => Err(DiceError::InvalidRoll(c.to_string())),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for Die {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "d6" | "6" => Ok(Die::D6),
+            "d20" | "20" => Ok(Die::D20),
+            "coin" | "d2" | "2" => Ok(Die::Coin),
+            _ => Err(format!("неизвестный источник '{}'; допустимые: d6, d20, coin", s)),
+        }
+    }
+}
+
+/// Энтропия из бросков и сколько бросков на неё ушло.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceEntropy {
+    pub entropy: Vec<u8>,
+    /// Броски, биты которых вошли в энтропию.
+    pub rolls_used: usize,
+    /// Все распознанные броски; лишние в конце не используются.
+    pub rolls_total: usize,
+}
+
+/// Ошибка разбора бросков.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiceError {
+    /// Значение вне граней кости или посторонний символ.
+    InvalidRoll(String),
+    /// Длина энтропии не из 128/160/192/224/256 бит.
+    BadLength(usize),
+    /// Бросков не хватило: получено `bits` бит, нужно ещё около `more` бросков.
+    NotEnoughRolls { bits: usize, needed: usize, more: usize },
+}
+
+impl fmt::Display for DiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiceError::InvalidRoll(roll) => write!(f, "Неверный бросок '{}'", roll),
+            DiceError::BadLength(bits) => write!(
+                f,
+                "Неверная длина энтропии: {} бит (BIP39 требует 128/160/192/224/256 бит)",
+                bits
+            ),
+            DiceError::NotEnoughRolls { bits, needed, more } => write!(
+                f,
+                "Бросков не хватает: получено {} бит из {}, нужно ещё около {} бросков",
+                bits, needed, more
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DiceError {}
+
+/// Собирает `bits` бит энтропии из бросков `rolls`. С `von_neumann` поток бит
+/// дополнительно проходит корректор фон Неймана (пары 01 → 0, 10 → 1, 00 и 11
+/// отбрасываются): он убирает смещение нечестной монеты или кости ценой
+/// примерно вчетверо большего числа бросков.
+pub fn dice_to_entropy(rolls: &str, die: Die, bits: usize, von_neumann: bool) -> Result<DiceEntropy, DiceError> {
+    if !VALID_WORD_COUNTS.iter().any(|words| words * 32 / 3 == bits) {
+        return Err(DiceError::BadLength(bits));
+    }
+    let values = die.parse_rolls(rolls)?;
+
+    let mut output = Vec::with_capacity(bits);
+    let mut pending: Option<bool> = None;
+    let mut rolls_used = 0;
+    for &value in &values {
+        if output.len() == bits {
+            break;
+        }
+        rolls_used += 1;
+        for bit in roll_bits(value, die.sides()) {
+            if output.len() == bits {
+                break;
+            }
+            if !von_neumann {
+                output.push(bit);
+            } else if let Some(first) = pending.take() {
+                if first != bit {
+                    output.push(first);
+                }
+            } else {
+                pending = Some(bit);
+            }
+        }
+    }
+
+    if output.len() < bits {
+        let rate = die.bits_per_roll() / if von_neumann { 4.0 } else { 1.0 };
+        return Err(DiceError::NotEnoughRolls {
+            bits: output.len(),
+            needed: bits,
+            more: ((bits - output.len()) as f64 / rate).ceil() as usize,
+        });
+    }
+
+    let mut entropy = vec![0u8; bits / 8];
+    for (i, bit) in output.into_iter().enumerate() {
+        entropy[i / 8] |= u8::from(bit) << (7 - i % 8);
+    }
+    Ok(DiceEntropy {
+        entropy,
+        rolls_used,
+        rolls_total: values.len(),
+    })
+}
+
+/// Биты одного броска `value` из `sides` исходов: исходы делятся на блоки
+/// по убыванию степеней двойки, бросок даёт номер внутри своего блока.
+fn roll_bits(value: u32, sides: u32) -> Vec<bool> {
+    let (mut value, mut remaining) = (value, sides);
+    loop {
+        let width = 31 - remaining.leading_zeros();
+        let block = 1 << width;
+        if value < block {
+            return (0..width).rev().map(|i| value >> i & 1 == 1).collect();
+        }
+        value -= block;
+        remaining -= block;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(value: u32, sides: u32) -> String {
+        roll_bits(value, sides).into_iter().map(|bit| if bit { '1' } else { '0' }).collect()
+    }
+
+    #[test]
+    fn d6_splits_into_four_and_two() {
+        let table: Vec<String> = (0..6).map(|value| bits(value, 6)).collect();
+        assert_eq!(table, ["00", "01", "10", "11", "0", "1"]);
+        assert!((Die::D6.bits_per_roll() - 10.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn d20_splits_into_sixteen_and_four() {
+        assert_eq!(bits(0, 20), "0000");
+        assert_eq!(bits(15, 20), "1111");
+        assert_eq!(bits(16, 20), "00");
+        assert_eq!(bits(19, 20), "11");
+        assert!((Die::D20.bits_per_roll() - 3.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn d6_rolls_give_exact_entropy() {
+        let result = dice_to_entropy(&"123456".repeat(13), Die::D6, 128, false).unwrap();
+        assert_eq!(hex::encode(&result.entropy), "1b46d1b46d1b46d1b46d1b46d1b46d1b");
+        assert_eq!(result.rolls_used, 76);
+        assert_eq!(result.rolls_total, 78);
+    }
+
+    #[test]
+    fn d20_rolls_give_exact_entropy() {
+        let rolls = "1 17 20 16 8 13 5 19 12 2 ".repeat(4);
+        let result = dice_to_entropy(&rolls, Die::D20, 128, false).unwrap();
+        assert_eq!(hex::encode(&result.entropy), "03f7c4ac40fdf12b103f7c4ac40fdf12");
+        assert_eq!(result.rolls_used, 38);
+        assert_eq!(result.rolls_total, 40);
+    }
+
+    #[test]
+    fn coin_flips_give_exact_entropy() {
+        let result = dice_to_entropy(&"HT".repeat(64), Die::Coin, 128, false).unwrap();
+        assert_eq!(result.entropy, vec![0xaa; 16]);
+        assert_eq!(result.rolls_used, 128);
+    }
+
+    #[test]
+    fn von_neumann_drops_equal_pairs() {
+        // HT → 1, TH → 0, HH и TT отбрасываются
+        let result = dice_to_entropy(&"HTTHHHTT".repeat(64), Die::Coin, 128, true).unwrap();
+        assert_eq!(result.entropy, vec![0xaa; 16]);
+        assert_eq!(result.rolls_used, 508);
+    }
+
+    #[test]
+    fn not_enough_rolls_estimates_more() {
+        assert_eq!(
+            dice_to_entropy("1111111111", Die::D6, 128, false),
+            Err(DiceError::NotEnoughRolls { bits: 20, needed: 128, more: 65 })
+        );
+        assert_eq!(
+            dice_to_entropy(&"HT".repeat(10), Die::Coin, 128, true),
+            Err(DiceError::NotEnoughRolls { bits: 10, needed: 128, more: 472 })
+        );
+    }
+
+    #[test]
+    fn invalid_rolls_are_rejected() {
+        assert_eq!(
+            dice_to_entropy("1237", Die::D6, 128, false),
+            Err(DiceError::InvalidRoll("7".into()))
+        );
+        assert_eq!(dice_to_entropy("21", Die::D20, 128, false), Err(DiceError::InvalidRoll("21".into())));
+    }
+}
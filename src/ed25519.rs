@@ -0,0 +1,410 @@
+//! Минимальная арифметика кривой edwards25519: открытый ключ по скаляру,
+//! запись точки в Ristretto и скаляры по модулю порядка группы.
+//!
+//! Нужна только для вывода адресов (Solana, Cardano, Substrate), поэтому
+//! реализовано умножение базовой точки на скаляр без подписи и проверки. Умножение не работает
+//! за постоянное время — утилита выполняется локально и ключи никуда не отправляет.
+
+use sha2::{Digest, Sha512};
+
+/// Маска 51-битного разряда.
+const MASK: u64 = (1 << 51) - 1;
+
+/// Элемент поля GF(2^255 - 19): пять разрядов по 51 бит.
+#[derive(Clone, Copy)]
+pub(crate) struct Field([u64; 5]);
+
+impl Field {
+    const ZERO: Field = Field([0; 5]);
+    const ONE: Field = Field([1, 0, 0, 0, 0]);
+
+    /// 2 * d, где d = -121665/121666 — параметр кривой.
+    const D2: Field = Field([
+        0x69b9426b2f159,
+        0x35050762add7a,
+        0x3cf44c0038052,
+        0x6738cc7407977,
+        0x2406d9dc56dff,
+    ]);
+
+    /// Квадратный корень из -1: 2^((p-1)/4).
+    const SQRT_M1: Field = Field([
+        0x61b274a0ea0b0,
+        0x0d5a5fc8f189d,
+        0x7ef5e9cbd0c60,
+        0x78595a6804c9e,
+        0x2b8324804fc1d,
+    ]);
+
+    /// 1/sqrt(a - d) для a = -1 (константа Ristretto).
+    const INVSQRT_A_MINUS_D: Field = Field([
+        0x0fdaa805d40ea,
+        0x2eb482e57d339,
+        0x007610274bc58,
+        0x6510b613dc8ff,
+        0x786c8905cfaff,
+    ]);
+
+    /// Переносы между разрядами; старший перенос возвращается умноженным на 19.
+    fn carry(mut limbs: [u64; 5]) -> Field {
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= MASK;
+        }
+        limbs[0] += 19 * (limbs[4] >> 51);
+        limbs[4] &= MASK;
+        Field(limbs)
+    }
+
+    pub(crate) fn add(&self, other: &Field) -> Field {
+        Field::carry(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+
+    pub(crate) fn sub(&self, other: &Field) -> Field {
+        // Прибавляется 4p, чтобы разность не ушла в минус
+        const P4: [u64; 5] = [
+            0x1fffffffffffb4,
+            0x1ffffffffffffc,
+            0x1ffffffffffffc,
+            0x1ffffffffffffc,
+            0x1ffffffffffffc,
+        ];
+        Field::carry(std::array::from_fn(|i| self.0[i] + P4[i] - other.0[i]))
+    }
+
+    pub(crate) fn mul(&self, other: &Field) -> Field {
+        let a = self.0.map(u128::from);
+        let b = other.0.map(u128::from);
+        let b19 = b.map(|limb| limb * 19);
+        let wide = [
+            a[0] * b[0] + a[1] * b19[4] + a[2] * b19[3] + a[3] * b19[2] + a[4] * b19[1],
+            a[0] * b[1] + a[1] * b[0] + a[2] * b19[4] + a[3] * b19[3] + a[4] * b19[2],
+            a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + a[3] * b19[4] + a[4] * b19[3],
+            a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0] + a[4] * b19[4],
+            a[0] * b[4] + a[1] * b[3] + a[2] * b[2] + a[3] * b[1] + a[4] * b[0],
+        ];
+        let mut limbs = [0u64; 5];
+        let mut carry = 0u128;
+        for i in 0..5 {
+            let value = wide[i] + carry;
+            limbs[i] = value as u64 & MASK;
+            carry = value >> 51;
+        }
+        // Старший перенос может не поместиться в u64 после умножения на 19
+        let low = u128::from(limbs[0]) + carry * 19;
+        limbs[0] = low as u64 & MASK;
+        limbs[1] += (low >> 51) as u64;
+        Field::carry(limbs)
+    }
+
+    pub(crate) fn square(&self) -> Field {
+        self.mul(self)
+    }
+
+    fn neg(&self) -> Field {
+        Field::ZERO.sub(self)
+    }
+
+    /// «Отрицательный» элемент — нечётный в канонической записи.
+    fn is_negative(&self) -> bool {
+        self.to_bytes()[0] & 1 == 1
+    }
+
+    fn abs(&self) -> Field {
+        if self.is_negative() {
+            self.neg()
+        } else {
+            *self
+        }
+    }
+
+    fn equals(&self, other: &Field) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+
+    /// Неотрицательный sqrt(u/v) или sqrt(i*u/v) и признак того, что u/v — квадрат.
+    fn sqrt_ratio_i(u: &Field, v: &Field) -> (bool, Field) {
+        // (p-5)/8 = 2^252 - 3
+        let mut exponent = [0xff; 32];
+        exponent[0] = 0xfd;
+        exponent[31] = 0x0f;
+        let v3 = v.square().mul(v);
+        let v7 = v3.square().mul(v);
+        let r = u.mul(&v3).mul(&u.mul(&v7).pow(&exponent));
+        let check = v.mul(&r.square());
+
+        let correct_sign = check.equals(u);
+        let flipped_sign = check.equals(&u.neg());
+        let flipped_sign_i = check.equals(&u.neg().mul(&Field::SQRT_M1));
+        let r = if flipped_sign || flipped_sign_i {
+            r.mul(&Field::SQRT_M1)
+        } else {
+            r
+        };
+        (correct_sign || flipped_sign, r.abs())
+    }
+
+    /// Возведение в степень; показатель — 32 байта little-endian.
+    fn pow(&self, exponent: &[u8; 32]) -> Field {
+        (0..256).rev().fold(Field::ONE, |acc, bit| {
+            let acc = acc.square();
+            if exponent[bit / 8] >> (bit % 8) & 1 == 1 {
+                acc.mul(self)
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Обратный элемент: a^(p-2).
+    pub(crate) fn invert(&self) -> Field {
+        let mut exponent = [0xff; 32];
+        exponent[0] = 0xeb;
+        exponent[31] = 0x7f;
+        self.pow(&exponent)
+    }
+
+    /// Каноническое представление: 32 байта little-endian, значение меньше p.
+    pub(crate) fn to_bytes(self) -> [u8; 32] {
+        let mut limbs = Field::carry(self.0).0;
+        // Вычитаем p, если значение не меньше p
+        let mut q = (limbs[0] + 19) >> 51;
+        for limb in &limbs[1..] {
+            q = (limb + q) >> 51;
+        }
+        limbs[0] += 19 * q;
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= MASK;
+        }
+        limbs[4] &= MASK;
+
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let bit = i * 8;
+            let (limb, shift) = (bit / 51, bit % 51);
+            let mut value = limbs[limb] >> shift;
+            if shift > 43 && limb < 4 {
+                value |= limbs[limb + 1] << (51 - shift);
+            }
+            *byte = value as u8;
+        }
+        bytes
+    }
+}
+
+/// Точка кривой в расширенных координатах (X : Y : Z : T), x = X/Z, y = Y/Z, xy = T/Z.
+#[derive(Clone, Copy)]
+pub(crate) struct Point {
+    x: Field,
+    y: Field,
+    z: Field,
+    t: Field,
+}
+
+impl Point {
+    const IDENTITY: Point = Point {
+        x: Field::ZERO,
+        y: Field::ONE,
+        z: Field::ONE,
+        t: Field::ZERO,
+    };
+
+    /// Базовая точка B (y = 4/5, x чётный).
+    pub(crate) const BASE: Point = Point {
+        x: Field([
+            0x62d608f25d51a,
+            0x412a4b4f6592a,
+            0x75b7171a4b31d,
+            0x1ff60527118fe,
+            0x216936d3cd6e5,
+        ]),
+        y: Field([
+            0x6666666666658,
+            0x4cccccccccccc,
+            0x1999999999999,
+            0x3333333333333,
+            0x6666666666666,
+        ]),
+        z: Field::ONE,
+        t: Field([
+            0x68ab3a5b7dda3,
+            0x00eea2a5eadbb,
+            0x2af8df483c27e,
+            0x332b375274732,
+            0x67875f0fd78b7,
+        ]),
+    };
+
+    /// Сложение (формулы add-2008-hwcd-3, годятся и для удвоения).
+    pub(crate) fn add(&self, other: &Point) -> Point {
+        let a = self.y.sub(&self.x).mul(&other.y.sub(&other.x));
+        let b = self.y.add(&self.x).mul(&other.y.add(&other.x));
+        let c = self.t.mul(&Field::D2).mul(&other.t);
+        let d = self.z.add(&self.z).mul(&other.z);
+        let (e, f, g, h) = (b.sub(&a), d.sub(&c), d.add(&c), b.add(&a));
+        Point {
+            x: e.mul(&f),
+            y: g.mul(&h),
+            z: f.mul(&g),
+            t: e.mul(&h),
+        }
+    }
+
+    /// Умножение на скаляр (32 байта little-endian, без приведения по модулю).
+    pub(crate) fn mul(&self, scalar: &[u8; 32]) -> Point {
+        (0..256).rev().fold(Point::IDENTITY, |acc, bit| {
+            let acc = acc.add(&acc);
+            if scalar[bit / 8] >> (bit % 8) & 1 == 1 {
+                acc.add(self)
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Запись Ristretto255 (как в curve25519-dalek): 32 байта, одинаковые
+    /// для точек, отличающихся на элемент кручения.
+    pub(crate) fn compress_ristretto(&self) -> [u8; 32] {
+        let u1 = self.z.add(&self.y).mul(&self.z.sub(&self.y));
+        let u2 = self.x.mul(&self.y);
+        let (_, invsqrt) = Field::sqrt_ratio_i(&Field::ONE, &u1.mul(&u2.square()));
+        let den1 = invsqrt.mul(&u1);
+        let den2 = invsqrt.mul(&u2);
+        let z_inv = den1.mul(&den2).mul(&self.t);
+
+        let rotate = self.t.mul(&z_inv).is_negative();
+        let (x, y, den_inv) = if rotate {
+            (
+                self.y.mul(&Field::SQRT_M1),
+                self.x.mul(&Field::SQRT_M1),
+                den1.mul(&Field::INVSQRT_A_MINUS_D),
+            )
+        } else {
+            (self.x, self.y, den2)
+        };
+        let y = if x.mul(&z_inv).is_negative() { y.neg() } else { y };
+        den_inv.mul(&self.z.sub(&y)).abs().to_bytes()
+    }
+
+    /// Сжатая запись по RFC 8032: y и знак x в старшем бите.
+    pub(crate) fn compress(&self) -> [u8; 32] {
+        let z_inv = self.z.invert();
+        let x = self.x.mul(&z_inv).to_bytes();
+        let mut bytes = self.y.mul(&z_inv).to_bytes();
+        bytes[31] |= (x[0] & 1) << 7;
+        bytes
+    }
+}
+
+/// Открытый ключ Ed25519 (RFC 8032) по 32-байтовому закрытому ключу:
+/// скаляр — первая половина SHA-512 от ключа с обнулёнными младшими
+/// тремя битами и установленным битом 254.
+pub(crate) fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    let hash = Sha512::digest(secret);
+    let mut scalar: [u8; 32] = hash[..32].try_into().expect("половина SHA-512 — 32 байта");
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+    scalar_public_key(&scalar)
+}
+
+/// Открытый ключ по готовому скаляру: сжатая точка scalar * B.
+pub(crate) fn scalar_public_key(scalar: &[u8; 32]) -> [u8; 32] {
+    Point::BASE.mul(scalar).compress()
+}
+
+/// Порядок подгруппы ℓ = 2^252 + 27742317777372353535851937790883648493,
+/// разряды по 64 бита little-endian.
+const ORDER: [u64; 4] = [0x5812631a5cf5d3ed, 0x14def9dea2f79cd6, 0, 0x1000000000000000];
+
+/// Скаляр по модулю ℓ из числа little-endian любой длины (например, 64 байта
+/// вызова Merlin): биты добавляются со старшего, после каждого — вычитание ℓ.
+pub(crate) fn scalar_reduce(bytes: &[u8]) -> [u8; 32] {
+    let mut value = [0u64; 4];
+    for bit in (0..bytes.len() * 8).rev() {
+        // value = 2 * value + бит; value < ℓ < 2^253, переполнения нет
+        for i in (1..4).rev() {
+            value[i] = value[i] << 1 | value[i - 1] >> 63;
+        }
+        value[0] = value[0] << 1 | u64::from(bytes[bit / 8] >> (bit % 8) & 1);
+        if !less_than(&value, &ORDER) {
+            value = subtract(&value, &ORDER);
+        }
+    }
+    let mut out = [0u8; 32];
+    for (chunk, limb) in out.chunks_mut(8).zip(value) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    out
+}
+
+/// Сумма скаляров по модулю ℓ.
+pub(crate) fn scalar_add(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut sum = [0u8; 33];
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let value = u16::from(a[i]) + u16::from(b[i]) + carry;
+        sum[i] = value as u8;
+        carry = value >> 8;
+    }
+    sum[32] = carry as u8;
+    scalar_reduce(&sum)
+}
+
+fn less_than(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    a.iter().rev().cmp(b.iter().rev()) == std::cmp::Ordering::Less
+}
+
+fn subtract(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut out = [0u64; 4];
+    let mut borrow = false;
+    for i in 0..4 {
+        let (value, under1) = a[i].overflowing_sub(b[i]);
+        let (value, under2) = value.overflowing_sub(u64::from(borrow));
+        out[i] = value;
+        borrow = under1 || under2;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public_hex(secret: &str) -> String {
+        let secret: [u8; 32] = hex::decode(secret).unwrap().try_into().unwrap();
+        hex::encode(public_key(&secret))
+    }
+
+    /// RFC 8032, раздел 7.1, TEST 1–3: закрытый ключ → открытый.
+    #[test]
+    fn rfc8032_public_keys() {
+        assert_eq!(
+            public_hex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        assert_eq!(
+            public_hex("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb"),
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"
+        );
+        assert_eq!(
+            public_hex("c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7"),
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025"
+        );
+    }
+
+    #[test]
+    fn scalar_add_wraps_at_order() {
+        let mut order_minus_one = [0u8; 32];
+        for (chunk, limb) in order_minus_one.chunks_mut(8).zip(ORDER) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        order_minus_one[0] -= 1;
+        let mut two = [0u8; 32];
+        two[0] = 2;
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        assert_eq!(scalar_add(&order_minus_one, &two), one);
+    }
+}
@@ -0,0 +1,158 @@
+//! C ABI (функция `ffi`) для кошельков и утилит на C/C++.
+//! Заголовок `include/mnem_to_entropy.h` генерируется cbindgen (см. `cbindgen.toml`)
+//! и хранится в репозитории.
+
+use crate::{decode, language_from_name, language_name, DecodeError, Options};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// Код результата [`mnem_decode`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MnemStatus {
+    Ok = 0,
+    /// Слова не из словаря.
+    InvalidWords = 1,
+    /// Число слов не 12/15/18/21/24.
+    BadWordCount = 2,
+    /// Контрольная сумма не сходится.
+    InvalidChecksum = 3,
+    /// NULL, не UTF-8 или неизвестный язык в параметрах.
+    InvalidArgument = 4,
+    /// Внутренняя ошибка библиотеки.
+    Internal = 5,
+}
+
+/// Параметры декодирования. Вместо указателя на них можно передать NULL.
+#[repr(C)]
+pub struct MnemOptions {
+    /// Декодировать фразу с неверной контрольной суммой.
+    pub ignore_checksum: bool,
+    /// Язык словаря (english, japanese, ...); NULL — автоопределение.
+    pub language: *const c_char,
+}
+
+/// Результат [`mnem_decode`]; освобождается только через [`mnem_free_result`].
+#[repr(C)]
+pub struct MnemResult {
+    pub status: MnemStatus,
+    /// Энтропия; NULL при ошибке.
+    pub entropy: *mut u8,
+    pub entropy_len: usize,
+    /// Энтропия в hex, строка с нулём в конце; NULL при ошибке.
+    pub entropy_hex: *mut c_char,
+    /// Словарь, по которому распознана фраза; NULL при ошибке.
+    pub language: *mut c_char,
+    /// Описание ошибки; NULL при успехе.
+    pub error: *mut c_char,
+}
+
+/// Декодирует мнемонику в энтропию. Всегда возвращает результат (не NULL),
+/// который нужно освободить [`mnem_free_result`].
+///
+/// # Safety
+///
+/// `mnemonic` — NULL или строка с нулём в конце; `options` — NULL или
+/// указатель на корректную [`MnemOptions`], `language` в которой — NULL или
+/// строка с нулём в конце.
+#[no_mangle]
+pub unsafe extern "C" fn mnem_decode(
+    mnemonic: *const c_char,
+    options: *const MnemOptions,
+) -> *mut MnemResult {
+    // Паника не должна раскручиваться через границу C
+    let result = panic::catch_unwind(AssertUnwindSafe(|| decode_result(mnemonic, options)))
+        .unwrap_or_else(|_| failure(MnemStatus::Internal, "внутренняя ошибка библиотеки"));
+    Box::into_raw(Box::new(result))
+}
+
+/// Освобождает результат [`mnem_decode`] вместе со всеми его строками.
+///
+/// # Safety
+///
+/// `result` — NULL или указатель, полученный от [`mnem_decode`] и ещё не
+/// освобождённый.
+#[no_mangle]
+pub unsafe extern "C" fn mnem_free_result(result: *mut MnemResult) {
+    if result.is_null() {
+        return;
+    }
+    let result = Box::from_raw(result);
+    if !result.entropy.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            result.entropy,
+            result.entropy_len,
+        )));
+    }
+    for text in [result.entropy_hex, result.language, result.error] {
+        if !text.is_null() {
+            drop(CString::from_raw(text));
+        }
+    }
+}
+
+unsafe fn decode_result(mnemonic: *const c_char, options: *const MnemOptions) -> MnemResult {
+    let Some(mnemonic) = text_argument(mnemonic) else {
+        return failure(MnemStatus::InvalidArgument, "мнемоника не задана или не в UTF-8");
+    };
+    let mut decode_options = Options::default();
+    if let Some(options) = options.as_ref() {
+        decode_options.ignore_checksum = options.ignore_checksum;
+        if !options.language.is_null() {
+            let language = text_argument(options.language).and_then(language_from_name);
+            match language {
+                Some(language) => decode_options.language = Some(language),
+                None => return failure(MnemStatus::InvalidArgument, "неизвестный язык"),
+            }
+        }
+    }
+
+    match decode(mnemonic, &decode_options) {
+        Ok(decoded) => {
+            let entropy = decoded.entropy.as_bytes().to_vec().into_boxed_slice();
+            let entropy_len = entropy.len();
+            MnemResult {
+                status: MnemStatus::Ok,
+                entropy: Box::into_raw(entropy) as *mut u8,
+                entropy_len,
+                entropy_hex: c_string(&decoded.entropy.to_hex()),
+                language: c_string(decoded.language.map(language_name).unwrap_or("custom")),
+                error: ptr::null_mut(),
+            }
+        }
+        Err(e) => {
+            let status = match e {
+                DecodeError::InvalidWords(_) => MnemStatus::InvalidWords,
+                DecodeError::BadWordCount(_) => MnemStatus::BadWordCount,
+                DecodeError::InvalidChecksum(_) => MnemStatus::InvalidChecksum,
+            };
+            failure(status, &e.to_string())
+        }
+    }
+}
+
+unsafe fn text_argument<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        return None;
+    }
+    CStr::from_ptr(text).to_str().ok()
+}
+
+fn failure(status: MnemStatus, message: &str) -> MnemResult {
+    MnemResult {
+        status,
+        entropy: ptr::null_mut(),
+        entropy_len: 0,
+        entropy_hex: ptr::null_mut(),
+        language: ptr::null_mut(),
+        error: c_string(message),
+    }
+}
+
+/// Строка для C; нулевые байты внутри (их не бывает в наших строках) отбрасываются.
+fn c_string(text: &str) -> *mut c_char {
+    CString::new(text.replace('\0', ""))
+        .expect("нулевые байты удалены")
+        .into_raw()
+}
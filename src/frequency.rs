@@ -0,0 +1,146 @@
+//! Частотный анализ набора мнемоник: как часто встречается каждое слово
+//! словаря — в целом и на каждой позиции фразы. У фраз из случайной энтропии
+//! индексы слов распределены равномерно; заметное отклонение (критерий χ²)
+//! выдаёт генераторы с плохим источником случайности или сгенерированные
+//! по шаблону наборы.
+
+use crate::WORDLIST_SIZE;
+
+/// Критерий χ² согласия с равномерным распределением по словарю.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    /// Значение статистики χ².
+    pub statistic: f64,
+    /// Число степеней свободы (размер словаря минус один).
+    pub degrees_of_freedom: u32,
+    /// Нормальное приближение Уилсона–Хилферти: сколько стандартных
+    /// отклонений статистика выше ожидаемой. Больше 3 — распределение
+    /// почти наверняка не равномерное.
+    pub z_score: f64,
+    /// Число наблюдений (слов).
+    pub samples: u64,
+}
+
+impl ChiSquared {
+    /// χ² для счётчиков по всем словам словаря.
+    pub fn of(counts: &[u64]) -> Self {
+        let samples: u64 = counts.iter().sum();
+        let expected = samples as f64 / counts.len() as f64;
+        let statistic = if samples == 0 {
+            0.0
+        } else {
+            counts
+                .iter()
+                .map(|&count| {
+                    let deviation = count as f64 - expected;
+                    deviation * deviation / expected
+                })
+                .sum()
+        };
+        let degrees_of_freedom = counts.len() as u32 - 1;
+        let k = degrees_of_freedom as f64;
+        let z_score = if samples == 0 {
+            0.0
+        } else {
+            ((statistic / k).cbrt() - (1.0 - 2.0 / (9.0 * k))) / (2.0 / (9.0 * k)).sqrt()
+        };
+        ChiSquared {
+            statistic,
+            degrees_of_freedom,
+            z_score,
+            samples,
+        }
+    }
+
+    /// Ожидаемое число появлений каждого слова при равномерном распределении.
+    /// Критерий надёжен, когда оно не меньше 5.
+    pub fn expected(&self) -> f64 {
+        self.samples as f64 / (self.degrees_of_freedom + 1) as f64
+    }
+}
+
+/// Накопленные частоты индексов слов по набору фраз.
+#[derive(Debug, Clone)]
+pub struct FrequencyAnalysis {
+    phrases: u64,
+    counts: Vec<u64>,
+    /// Частоты отдельно для каждой позиции во фразе.
+    positions: Vec<Vec<u64>>,
+}
+
+impl Default for FrequencyAnalysis {
+    fn default() -> Self {
+        FrequencyAnalysis {
+            phrases: 0,
+            counts: vec![0; WORDLIST_SIZE],
+            positions: Vec::new(),
+        }
+    }
+}
+
+impl FrequencyAnalysis {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Учитывает фразу, заданную индексами слов.
+    pub fn add(&mut self, indices: &[u16]) {
+        self.phrases += 1;
+        if self.positions.len() < indices.len() {
+            self.positions.resize(indices.len(), vec![0; WORDLIST_SIZE]);
+        }
+        for (position, &index) in indices.iter().enumerate() {
+            self.counts[index as usize] += 1;
+            self.positions[position][index as usize] += 1;
+        }
+    }
+
+    /// Число учтённых фраз.
+    pub fn phrases(&self) -> u64 {
+        self.phrases
+    }
+
+    /// Число учтённых слов.
+    pub fn words(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Число появлений каждого индекса словаря.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Самые частые индексы с числом появлений, по убыванию.
+    pub fn most_common(&self, limit: usize) -> Vec<(u16, u64)> {
+        let mut ranked: Vec<(u16, u64)> = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(index, &count)| (index as u16, count))
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked.truncate(limit);
+        ranked
+    }
+
+    /// Число позиций: длина самой длинной учтённой фразы.
+    pub fn positions(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Частоты индексов на позиции `position` (с нуля).
+    pub fn position_counts(&self, position: usize) -> &[u64] {
+        &self.positions[position]
+    }
+
+    /// Критерий χ² по всем словам набора.
+    pub fn chi_squared(&self) -> ChiSquared {
+        ChiSquared::of(&self.counts)
+    }
+
+    /// Критерий χ² для слов на позиции `position`.
+    pub fn position_chi_squared(&self, position: usize) -> ChiSquared {
+        ChiSquared::of(&self.positions[position])
+    }
+}
@@ -0,0 +1,85 @@
+//! Арифметика в GF(256) с многочленом Rijndael (x^8 + x^4 + x^3 + x + 1)
+//! и интерполяция Лагранжа для схемы Шамира.
+
+const fn tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut poly: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = poly as u8;
+        log[poly as usize] = i as u8;
+        // Умножение на образующую 3 = x + 1
+        poly ^= poly << 1;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11b;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+const TABLES: ([u8; 255], [u8; 256]) = tables();
+const EXP: [u8; 255] = TABLES.0;
+const LOG: [u8; 256] = TABLES.1;
+
+/// Значение в точке `x` многочлена, проходящего через точки `shares`
+/// (побайтно для каждого байта значения).
+///
+/// Все значения должны иметь одинаковую длину, координаты `x` — различаться.
+pub fn interpolate(shares: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some((_, value)) = shares.iter().find(|(share_x, _)| *share_x == x) {
+        return value.to_vec();
+    }
+
+    let log = |v: u8| LOG[v as usize] as i64;
+    let log_prod: i64 = shares.iter().map(|(share_x, _)| log(share_x ^ x)).sum();
+
+    let mut result = vec![0u8; shares[0].1.len()];
+    for (share_x, value) in shares {
+        // log базисного многочлена Лагранжа в точке x; log(0) == 0 компенсирует
+        // слагаемое share_x ^ share_x в сумме по всем точкам.
+        let others: i64 = shares.iter().map(|(other_x, _)| log(share_x ^ other_x)).sum();
+        let log_basis = (log_prod - log(share_x ^ x) - others).rem_euclid(255);
+
+        for (out, &byte) in result.iter_mut().zip(value.iter()) {
+            if byte != 0 {
+                *out ^= EXP[((log(byte) + log_basis) % 255) as usize];
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mul(a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        EXP[(LOG[a as usize] as usize + LOG[b as usize] as usize) % 255]
+    }
+
+    #[test]
+    fn multiplication_matches_fips_197() {
+        // FIPS-197, раздел 4.2: {57} • {83} = {c1}, {57} • {13} = {fe}
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        for a in 1..=255u8 {
+            assert_eq!(EXP[LOG[a as usize] as usize], a);
+        }
+    }
+
+    #[test]
+    fn interpolation_recovers_polynomial() {
+        // f(x) = 0x2a + 0x17·x + 0x05·x² побайтно в одном байте
+        let f = |x: u8| 0x2a ^ mul(0x17, x) ^ mul(0x05, mul(x, x));
+        let points: Vec<(u8, Vec<u8>)> = [3u8, 7, 200].iter().map(|&x| (x, vec![f(x)])).collect();
+        let shares: Vec<(u8, &[u8])> = points.iter().map(|(x, value)| (*x, value.as_slice())).collect();
+        for x in [0u8, 1, 2, 3, 100, 255] {
+            assert_eq!(interpolate(&shares, x), vec![f(x)], "x = {}", x);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Буквы-двойники (homoglyphs): кириллические и греческие символы, которые
+//! выглядят как латинские (`е` и `e`, `о` и `o`). Фразы, скопированные
+//! из документов в русской раскладке, часто содержат их вперемешку
+//! с латиницей, и такие слова не находятся в словарях BIP39.
+
+use crate::recover::dictionaries;
+use crate::{normalize_mnemonic, Options};
+use std::fmt;
+
+/// Символ-двойник в слове фразы.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Confusable {
+    /// Позиция слова во фразе (с нуля).
+    pub position: usize,
+    /// Символ из фразы.
+    pub found: char,
+    /// Латинская буква, на которую он похож.
+    pub latin: char,
+}
+
+impl fmt::Display for Confusable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "слово {}: «{}» (U+{:04X}) вместо латинской «{}»",
+            self.position + 1,
+            self.found,
+            self.found as u32,
+            self.latin
+        )
+    }
+}
+
+/// Фраза после замены двойников.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dehomoglyphed {
+    /// Фраза с латинскими буквами вместо двойников.
+    pub mnemonic: String,
+    /// Заменённые символы.
+    pub replaced: Vec<Confusable>,
+}
+
+/// Латинская буква, на которую похож символ, или `None`.
+pub fn latin_lookalike(c: char) -> Option<char> {
+    Some(match c {
+        // Кириллица
+        'а' => 'a',
+        'в' => 'b',
+        'е' => 'e',
+        'к' => 'k',
+        'м' => 'm',
+        'н' => 'h',
+        'о' => 'o',
+        'р' => 'p',
+        'с' => 'c',
+        'т' => 't',
+        'у' => 'y',
+        'х' => 'x',
+        'і' => 'i',
+        'ј' => 'j',
+        'ѕ' => 's',
+        'ԁ' => 'd',
+        'ԛ' => 'q',
+        'ԝ' => 'w',
+        'һ' => 'h',
+        'ӏ' => 'l',
+        'А' => 'A',
+        'В' => 'B',
+        'Е' => 'E',
+        'К' => 'K',
+        'М' => 'M',
+        'Н' => 'H',
+        'О' => 'O',
+        'Р' => 'P',
+        'С' => 'C',
+        'Т' => 'T',
+        'У' => 'Y',
+        'Х' => 'X',
+        'І' => 'I',
+        'Ј' => 'J',
+        'Ѕ' => 'S',
+        // Греческий
+        'α' => 'a',
+        'ι' => 'i',
+        'κ' => 'k',
+        'ν' => 'v',
+        'ο' => 'o',
+        'ρ' => 'p',
+        'υ' => 'u',
+        'Α' => 'A',
+        'Β' => 'B',
+        'Ε' => 'E',
+        'Ζ' => 'Z',
+        'Η' => 'H',
+        'Ι' => 'I',
+        'Κ' => 'K',
+        'Μ' => 'M',
+        'Ν' => 'N',
+        'Ο' => 'O',
+        'Ρ' => 'P',
+        'Τ' => 'T',
+        'Υ' => 'Y',
+        'Χ' => 'X',
+        // Латинские буквы других алфавитов
+        'ı' => 'i',
+        'ɡ' => 'g',
+        'ɑ' => 'a',
+        _ => return None,
+    })
+}
+
+/// Двойники в словах фразы, которых нет ни в одном разрешённом в `options`
+/// словаре. Слова из словарей не проверяются: в них двойников быть не может.
+pub fn find_confusables(mnemonic: &str, options: &Options) -> Vec<Confusable> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let dictionaries = dictionaries(options);
+    mnemonic
+        .split_whitespace()
+        .enumerate()
+        .filter(|(_, word)| dictionaries.iter().all(|dictionary| dictionary.find_word(word).is_none()))
+        .flat_map(|(position, word)| {
+            word.chars().filter_map(move |found| {
+                latin_lookalike(found).map(|latin| Confusable { position, found, latin })
+            })
+        })
+        .collect()
+}
+
+/// Заменяет двойники латинскими буквами в словах, которых нет в словарях,
+/// если после замены слово находится в одном из разрешённых в `options`
+/// словарей. `None`, если заменять нечего.
+pub fn fix_homoglyphs(mnemonic: &str, options: &Options) -> Option<Dehomoglyphed> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let dictionaries = dictionaries(options);
+    let known = |word: &str| dictionaries.iter().any(|dictionary| dictionary.find_word(word).is_some());
+
+    let mut replaced = Vec::new();
+    let words: Vec<String> = mnemonic
+        .split_whitespace()
+        .enumerate()
+        .map(|(position, word)| {
+            if known(word) {
+                return word.to_string();
+            }
+            let fixed: String = word.chars().map(|c| latin_lookalike(c).unwrap_or(c)).collect();
+            if fixed == word || !known(&fixed) {
+                return word.to_string();
+            }
+            replaced.extend(word.chars().filter_map(|found| {
+                latin_lookalike(found).map(|latin| Confusable { position, found, latin })
+            }));
+            fixed
+        })
+        .collect();
+
+    (!replaced.is_empty()).then(|| Dehomoglyphed {
+        mnemonic: words.join(" "),
+        replaced,
+    })
+}
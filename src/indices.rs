@@ -0,0 +1,149 @@
+//! Мнемоника как последовательность 11-битных индексов слов словаря.
+
+use crate::{normalize_mnemonic, Language, Options, Wordlist, VALID_WORD_COUNTS, WORDLIST_SIZE};
+use bitcoin_hashes::{sha256, Hash};
+use std::fmt;
+
+/// Ошибка разбора строки индексов.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndicesError {
+    /// Элемент строки не является числом.
+    InvalidNumber(String),
+    /// Индекс не меньше размера словаря.
+    OutOfRange(u64),
+}
+
+impl fmt::Display for IndicesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndicesError::InvalidNumber(item) => write!(f, "Не число: {:?}", item),
+            IndicesError::OutOfRange(index) => write!(
+                f,
+                "Индекс вне словаря: {} (допустимо 0–{})",
+                index,
+                WORDLIST_SIZE - 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IndicesError {}
+
+/// Ошибка разбора битовой строки энтропии.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitsError {
+    /// Символ, отличный от `0`, `1`, `|` и пробелов.
+    InvalidChar(char),
+    /// Длина без контрольной суммы не из 128/160/192/224/256 бит
+    /// (и вместе с ней не из 132/165/198/231/264).
+    BadLength(usize),
+    /// Биты контрольной суммы не совпали с SHA-256 от энтропии.
+    ChecksumMismatch { expected: String, actual: String },
+}
+
+impl fmt::Display for BitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitsError::InvalidChar(c) => write!(f, "Недопустимый символ в битовой строке: {:?}", c),
+            BitsError::BadLength(bits) => write!(
+                f,
+                "Неверная длина энтропии: {} бит (BIP39 требует 128/160/192/224/256 бит)",
+                bits
+            ),
+            BitsError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Контрольная сумма не совпадает: указано {}, должно быть {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BitsError {}
+
+/// Индексы слов фразы в пользовательском словаре из `options`
+/// или в словаре `language`. `None`, если какого-то слова там нет.
+pub fn word_indices(mnemonic_str: &str, language: Option<Language>, options: &Options) -> Option<Vec<u16>> {
+    let mnemonic_str = &*normalize_mnemonic(mnemonic_str);
+    let words = mnemonic_str.split_whitespace();
+    match (&options.wordlist, language) {
+        (Some(wordlist), _) => words.map(|word| wordlist.find_word(word)).collect(),
+        (None, Some(language)) => words.map(|word| language.find_word(word)).collect(),
+        (None, None) => None,
+    }
+}
+
+/// Собирает фразу из строки индексов (через пробелы или запятые)
+/// по пользовательскому словарю, а без него — по словарю `language`.
+pub fn mnemonic_from_indices(
+    text: &str,
+    language: Language,
+    wordlist: Option<&Wordlist>,
+) -> Result<String, IndicesError> {
+    let words = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let index: u64 = item
+                .parse()
+                .map_err(|_| IndicesError::InvalidNumber(item.to_string()))?;
+            if index >= WORDLIST_SIZE as u64 {
+                return Err(IndicesError::OutOfRange(index));
+            }
+            Ok(match wordlist {
+                Some(wordlist) => wordlist.word(index as u16),
+                None => language.word_list()[index as usize],
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(words.join(" "))
+}
+
+/// Битовая строка фразы с отделёнными битами контрольной суммы: `ENT|CS`.
+/// Длина контрольной суммы — ENT/32 бит, как в BIP39.
+pub fn bit_string(indices: &[u16]) -> String {
+    let bits: String = indices.iter().map(|index| format!("{:011b}", index)).collect();
+    let checksum_bits = bits.len() / 33;
+    let (entropy, checksum) = bits.split_at(bits.len() - checksum_bits);
+    format!("{}|{}", entropy, checksum)
+}
+
+/// Энтропия из битовой строки — обратное к [`bit_string`]. Принимается
+/// `ENT`, `ENT|CS` или все 11*N бит фразы подряд; пробелы игнорируются.
+/// Если биты контрольной суммы указаны, они сверяются с SHA-256 от энтропии.
+pub fn entropy_from_bits(text: &str) -> Result<Vec<u8>, BitsError> {
+    let bits: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some(c) = bits.chars().find(|c| !matches!(c, '0' | '1' | '|')) {
+        return Err(BitsError::InvalidChar(c));
+    }
+
+    let (entropy_bits, checksum) = match bits.split_once('|') {
+        Some((entropy, checksum)) => (entropy.to_string(), Some(checksum.to_string())),
+        None if VALID_WORD_COUNTS.iter().any(|words| words * 11 == bits.len()) => {
+            let (entropy, checksum) = bits.split_at(bits.len() - bits.len() / 33);
+            (entropy.to_string(), Some(checksum.to_string()))
+        }
+        None => (bits, None),
+    };
+    if let Some(c) = checksum.as_deref().and_then(|checksum| checksum.chars().find(|&c| c == '|')) {
+        return Err(BitsError::InvalidChar(c));
+    }
+    if !VALID_WORD_COUNTS.iter().any(|words| words * 32 / 3 == entropy_bits.len()) {
+        return Err(BitsError::BadLength(entropy_bits.len()));
+    }
+
+    let entropy: Vec<u8> = entropy_bits
+        .as_bytes()
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, bit| acc << 1 | (bit - b'0')))
+        .collect();
+
+    if let Some(actual) = checksum {
+        let hash = sha256::Hash::hash(&entropy).to_byte_array()[0];
+        let expected = format!("{:08b}", hash)[..entropy_bits.len() / 32].to_string();
+        if actual != expected {
+            return Err(BitsError::ChecksumMismatch { expected, actual });
+        }
+    }
+    Ok(entropy)
+}
@@ -0,0 +1,57 @@
+//! BIP39 seed и корневые ключи BIP32.
+
+use bitcoin::bip32::{Fingerprint, Xpriv, Xpub};
+use bitcoin::secp256k1::{All, Secp256k1};
+use bitcoin::Network;
+use sha2::Sha512;
+use std::sync::OnceLock;
+use unicode_normalization::UnicodeNormalization;
+
+/// Число итераций PBKDF2 по BIP39.
+const SEED_ROUNDS: u32 = 2048;
+
+/// Общий контекст secp256k1: создавать его на каждую мнемонику дорого.
+pub(crate) fn secp() -> &'static Secp256k1<All> {
+    static SECP: OnceLock<Secp256k1<All>> = OnceLock::new();
+    SECP.get_or_init(Secp256k1::new)
+}
+
+/// Вычисляет 64-байтовый BIP39 seed:
+/// PBKDF2-HMAC-SHA512(фраза, "mnemonic" + passphrase, 2048 итераций).
+///
+/// Фраза и пароль приводятся к NFKD, слова склеиваются одним пробелом.
+/// Контрольная сумма не проверяется, поэтому seed можно получить и для
+/// фраз из пользовательского словаря.
+pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> [u8; 64] {
+    let normalized: String = mnemonic.nfkd().collect();
+    let sentence = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
+    let salt: String = format!("mnemonic{}", passphrase).nfkd().collect();
+
+    let mut seed = [0u8; 64];
+    pbkdf2::pbkdf2_hmac::<Sha512>(sentence.as_bytes(), salt.as_bytes(), SEED_ROUNDS, &mut seed);
+    seed
+}
+
+/// Корневые расширенные ключи BIP32 (m).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootKeys {
+    /// Расширенный приватный ключ (xprv/tprv).
+    pub xprv: Xpriv,
+    /// Расширенный публичный ключ (xpub/tpub).
+    pub xpub: Xpub,
+}
+
+impl RootKeys {
+    /// Строит корневые ключи из BIP39 seed.
+    pub fn from_seed(seed: &[u8], network: Network) -> Self {
+        let xprv = Xpriv::new_master(network, seed).expect("seed длиной 64 байта допустим для BIP32");
+        let xpub = Xpub::from_priv(secp(), &xprv);
+        RootKeys { xprv, xpub }
+    }
+
+    /// Отпечаток мастер-ключа BIP32: первые 4 байта HASH160 публичного
+    /// ключа — его показывают аппаратные кошельки.
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.xpub.fingerprint()
+    }
+}
@@ -0,0 +1,89 @@
+//! Языки словарей BIP39 и их текстовые идентификаторы.
+
+use crate::normalize_mnemonic;
+use bip39::Language;
+
+/// Все поддерживаемые языки в порядке перебора при автоопределении.
+/// English идёт первым: это самый распространённый словарь.
+pub const ALL_LANGUAGES: [Language; 10] = [
+    Language::English,
+    Language::Japanese,
+    Language::Spanish,
+    Language::French,
+    Language::Italian,
+    Language::Korean,
+    Language::SimplifiedChinese,
+    Language::TraditionalChinese,
+    Language::Czech,
+    Language::Portuguese,
+];
+
+/// Идентификатор языка, используемый в выводе и аргументах командной строки.
+pub fn language_name(language: Language) -> &'static str {
+    match language {
+        Language::English => "english",
+        Language::Japanese => "japanese",
+        Language::Spanish => "spanish",
+        Language::French => "french",
+        Language::Italian => "italian",
+        Language::Korean => "korean",
+        Language::SimplifiedChinese => "chinese-simplified",
+        Language::TraditionalChinese => "chinese-traditional",
+        Language::Czech => "czech",
+        Language::Portuguese => "portuguese",
+    }
+}
+
+/// Язык по идентификатору (см. [`language_name`]); регистр не учитывается.
+/// Также принимаются короткие синонимы `zh-cn`/`zh-tw` и двухбуквенные коды.
+pub fn language_from_name(name: &str) -> Option<Language> {
+    let name = name.to_lowercase();
+    match name.as_str() {
+        "en" => return Some(Language::English),
+        "ja" | "jp" => return Some(Language::Japanese),
+        "es" => return Some(Language::Spanish),
+        "fr" => return Some(Language::French),
+        "it" => return Some(Language::Italian),
+        "ko" | "kr" => return Some(Language::Korean),
+        "zh-cn" | "zh-hans" => return Some(Language::SimplifiedChinese),
+        "zh-tw" | "zh-hant" => return Some(Language::TraditionalChinese),
+        "cs" | "cz" => return Some(Language::Czech),
+        "pt" => return Some(Language::Portuguese),
+        _ => {}
+    }
+    ALL_LANGUAGES
+        .iter()
+        .copied()
+        .find(|&language| language_name(language) == name)
+}
+
+/// Языки, в словаре которых есть все переданные слова (после NFKD).
+pub fn languages_containing(words: &[&str]) -> Vec<Language> {
+    ALL_LANGUAGES
+        .iter()
+        .copied()
+        .filter(|language| {
+            words
+                .iter()
+                .all(|word| language.find_word(&normalize_mnemonic(word)).is_some())
+        })
+        .collect()
+}
+
+/// Язык фразы по её словам: словарь, в котором нашлось больше всего слов,
+/// если это хотя бы половина фразы; иначе `None`. Подходит и для фраз,
+/// которые не декодируются: опечатки и неверная контрольная сумма не мешают
+/// определению.
+pub fn detect_language(mnemonic: &str) -> Option<Language> {
+    let normalized = normalize_mnemonic(mnemonic);
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    let (language, found) = ALL_LANGUAGES
+        .iter()
+        .map(|&language| {
+            let found = words.iter().filter(|word| language.find_word(word).is_some()).count();
+            (language, found)
+        })
+        .rev()
+        .max_by_key(|&(_, found)| found)?;
+    (found > 0 && found * 2 >= words.len()).then_some(language)
+}
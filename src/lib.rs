@@ -0,0 +1,499 @@
+//! Библиотека для конвертации мнемонических фраз BIP39 обратно в энтропию.
+//!
+//! Основная точка входа — [`decode`]:
+//!
+//! ```no_run
+//! use mnem_to_entropy::{decode, Options};
+//!
+//! let decoded = decode(
+//!     "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+//!     &Options::default(),
+//! ).unwrap();
+//! assert_eq!(decoded.entropy.to_hex(), "00000000000000000000000000000000");
+//! ```
+
+mod aezeed;
+mod bip85;
+mod cardano;
+mod compare;
+mod derive;
+mod descriptor;
+mod diagnose;
+mod dice;
+mod ed25519;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod frequency;
+mod indices;
+mod gf256;
+mod homoglyph;
+mod keys;
+mod language;
+mod merlin;
+#[cfg(feature = "python")]
+mod python;
+mod quality;
+mod recover;
+mod sanitize;
+mod seedxor;
+mod shamir;
+mod solana;
+pub mod slip39;
+mod substrate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod verify;
+mod wordlist;
+
+pub use aezeed::{is_aezeed, AezeedError, AEZEED_WORD_COUNT};
+pub use bip39::Language;
+pub use bip85::{
+    bip85_bip39_path, bip85_entropy, bip85_language_code, bip85_mnemonic, BIP85_APP_BIP39, BIP85_PURPOSE,
+};
+pub use compare::{diff_mnemonics, MnemonicDiff, WordChange, WordDifference};
+pub use cardano::{cardano_path, derive_cardano_addresses, CardanoKey, CARDANO_COIN_TYPE, CARDANO_PURPOSE};
+pub use bitcoin::bip32::{DerivationPath, Fingerprint};
+pub use bitcoin::Network;
+pub use derive::{
+    account_path, account_xpub, bitcoin_coin_type, derive_addresses, eip55_checksum, AddressType, Coin,
+    DeriveError, DerivedAddress,
+};
+pub use descriptor::{descriptor_checksum, output_descriptor};
+pub use dice::{dice_to_entropy, DiceEntropy, DiceError, Die};
+pub use diagnose::{diagnose_mnemonic, Diagnosis, WordDiagnostic, NEAREST_LIMIT};
+pub use frequency::{ChiSquared, FrequencyAnalysis};
+pub use homoglyph::{find_confusables, fix_homoglyphs, latin_lookalike, Confusable, Dehomoglyphed};
+pub use indices::{bit_string, entropy_from_bits, mnemonic_from_indices, word_indices, BitsError, IndicesError};
+pub use keys::{mnemonic_to_seed, RootKeys};
+pub use language::{detect_language, language_from_name, language_name, languages_containing, ALL_LANGUAGES};
+pub use quality::{
+    check_entropy, shannon_entropy, EntropyWarning, LOW_SHANNON_RATIO, MAX_PATTERN_PERIOD,
+    TEST_VECTORS,
+};
+pub use recover::{
+    autocorrect, completions, expand_prefixes, insertion_templates, last_word_templates, levenshtein, parse_missing_word,
+    suggest_words, try_swaps, typo_cost, Autocorrected, Candidate, Completions, MissingWord, RecoverError,
+    Suggestion, Swap, WordCorrection, MAX_PLACEHOLDERS, MAX_TYPO_DISTANCE, MIN_PREFIX_LENGTH, PLACEHOLDERS,
+    SUGGESTION_LIMIT,
+};
+pub use sanitize::{sanitize_mnemonic, Cleanup, Sanitize, Sanitized};
+pub use verify::{verify_round_trip, VerifyError};
+pub use seedxor::{seedxor_combine, seedxor_split, SeedXorError, MAX_SEEDXOR_PARTS};
+pub use shamir::{shamir_combine, shamir_split, ShamirError, ShamirShare, MAX_SHAMIR_SHARES};
+pub use solana::{derive_solana_addresses, slip10_ed25519_public_key, solana_path, SOLANA_COIN_TYPE};
+pub use substrate::{
+    mini_secret_from_entropy, parse_junctions, ss58_encode, substrate_public_key, Junction,
+    SubstrateCrypto, SubstrateError, MAX_SS58_PREFIX, POLKADOT_COIN_TYPE,
+};
+pub use wordlist::{Wordlist, WordlistError, WORDLIST_SIZE};
+
+use base64::Engine;
+use bip39::Mnemonic;
+use bitcoin_hashes::{sha256, Hash};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+use unicode_normalization::{is_nfkd_quick, IsNormalized, UnicodeNormalization};
+
+/// Допустимое количество слов в мнемонике BIP39.
+pub const VALID_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Параметры декодирования мнемоники.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Не проверять контрольную сумму: при неверном чексуме возвращаются
+    /// все 11*N бит мнемоники (включая биты контрольной суммы).
+    pub ignore_checksum: bool,
+    /// Использовать только указанный словарь вместо автоопределения языка.
+    pub language: Option<Language>,
+    /// Пользовательский словарь; если задан, имеет приоритет над `language`.
+    pub wordlist: Option<Arc<Wordlist>>,
+}
+
+impl Options {
+    /// Языки, по словарям которых проверяется фраза.
+    fn languages(&self) -> &[Language] {
+        match &self.language {
+            Some(language) => std::slice::from_ref(language),
+            None => &ALL_LANGUAGES,
+        }
+    }
+}
+
+/// Энтропия, извлечённая из мнемоники.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entropy(Vec<u8>);
+
+impl Entropy {
+    /// Байты энтропии.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Забирает байты энтропии.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Энтропия в виде hex-строки.
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.0)
+    }
+
+    /// Энтропия в Base64 (стандартный алфавит, с дополнением).
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(&self.0)
+    }
+
+    /// Энтропия в Base58 (алфавит Bitcoin, без контрольной суммы).
+    pub fn to_base58(&self) -> String {
+        bitcoin::base58::encode(&self.0)
+    }
+
+    /// Короткий отпечаток: первые 4 байта SHA-256 энтропии в hex. По нему
+    /// результаты сопоставляются в логах, а сама энтропия не раскрывается.
+    pub fn fingerprint(&self) -> String {
+        hex::encode(&sha256::Hash::hash(&self.0).to_byte_array()[..4])
+    }
+}
+
+impl From<Vec<u8>> for Entropy {
+    fn from(bytes: Vec<u8>) -> Self {
+        Entropy(bytes)
+    }
+}
+
+impl AsRef<[u8]> for Entropy {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Результат успешного декодирования.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    /// Извлечённая энтропия.
+    pub entropy: Entropy,
+    /// Язык словаря, по которому распознана фраза;
+    /// `None` для пользовательского словаря.
+    pub language: Option<Language>,
+}
+
+/// Причина, по которой мнемонику не удалось декодировать.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// Слова, которых нет в наиболее подходящем словаре.
+    InvalidWords(Vec<String>),
+    /// Количество слов не входит в [`VALID_WORD_COUNTS`].
+    BadWordCount(usize),
+    /// Все слова из словаря, но контрольная сумма не сходится.
+    /// `None` для пользовательского словаря.
+    InvalidChecksum(Option<Language>),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidWords(words) => write!(
+                f,
+                "Неверные слова (нет в словарях BIP39): {:?}",
+                words.iter().take(3).collect::<Vec<_>>()
+            ),
+            DecodeError::BadWordCount(count) => write!(
+                f,
+                "Неверное количество слов: {} (BIP39 требует 12/15/18/21/24 слов)",
+                count
+            ),
+            DecodeError::InvalidChecksum(Some(language)) => write!(
+                f,
+                "Неверная контрольная сумма BIP39 (язык: {})",
+                language_name(*language)
+            ),
+            DecodeError::InvalidChecksum(None) => {
+                write!(f, "Неверная контрольная сумма BIP39 (пользовательский словарь)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Приводит фразу к NFKD, как требует BIP39: слова словарей хранятся в этой
+/// форме, а набранный текст обычно в NFC (французские и испанские слова
+/// с диакритикой, японская кана с дакутэн). Идеографический пробел U+3000
+/// остаётся разделителем слов, как и обычный.
+pub fn normalize_mnemonic(mnemonic: &str) -> Cow<'_, str> {
+    if is_nfkd_quick(mnemonic.chars()) == IsNormalized::Yes {
+        Cow::Borrowed(mnemonic)
+    } else {
+        Cow::Owned(mnemonic.nfkd().collect())
+    }
+}
+
+/// Приводит переписанную с бумаги фразу к словам через один пробел:
+/// разделителями считаются также запятые, точки с запятой и `|`, а нумерация
+/// вида `1.`, `2)`, `#3`, `(4)`, `05:` и `6.word` отбрасывается. Цифр
+/// в словах BIP39 нет, поэтому слова при этом не меняются.
+pub fn lenient_mnemonic(mnemonic: &str) -> String {
+    mnemonic
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '|' | '、' | '，'))
+        .map(|token| {
+            let token = token.trim_start_matches(['#', '(']);
+            let rest = token.trim_start_matches(|c: char| c.is_ascii_digit());
+            if rest.len() == token.len() {
+                token
+            } else {
+                rest.strip_prefix(['.', ')', ':', '-']).unwrap_or(rest)
+            }
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Декодирует мнемоническую фразу в энтропию.
+///
+/// Если [`Options::language`] не задан, язык определяется автоматически:
+/// фраза проверяется по стандарту BIP39 в каждом из [`ALL_LANGUAGES`],
+/// побеждает первый подошедший. Если проверка не прошла и включён
+/// [`Options::ignore_checksum`], извлекаются все биты фразы без проверки
+/// контрольной суммы. При заданном [`Options::wordlist`] используется
+/// только он. Фраза предварительно приводится к NFKD ([`normalize_mnemonic`]).
+pub fn decode(mnemonic: &str, options: &Options) -> Result<Decoded, DecodeError> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    if let Some(wordlist) = &options.wordlist {
+        return decode_with_wordlist(mnemonic, wordlist, options.ignore_checksum);
+    }
+
+    if let Some(decoded) = try_bip39(mnemonic, options.languages()) {
+        return Ok(decoded);
+    }
+
+    if options.ignore_checksum {
+        decode_ignore_checksum(mnemonic, options)
+    } else {
+        Err(analyze_mnemonic(mnemonic, options))
+    }
+}
+
+/// Проверка BIP39 по словарям `languages`, первый подошедший.
+///
+/// Энтропия собирается из индексов слов именно этого словаря: `bip39::Mnemonic`
+/// заново определяет язык по словам и паникует, если фраза из общих слов
+/// подходит сразу нескольким словарям.
+fn try_bip39(mnemonic_str: &str, languages: &[Language]) -> Option<Decoded> {
+    let words: Vec<&str> = mnemonic_str.split_whitespace().collect();
+    if !VALID_WORD_COUNTS.contains(&words.len()) {
+        return None;
+    }
+    languages.iter().find_map(|&language| {
+        let indices = words
+            .iter()
+            .map(|word| language.find_word(word))
+            .collect::<Option<Vec<u16>>>()?;
+        checked_entropy(&indices).map(|entropy| Decoded {
+            entropy: Entropy(entropy),
+            language: Some(language),
+        })
+    })
+}
+
+/// Все толкования фразы: по каждому из разрешённых в `options` словарей,
+/// где она проходит проверку BIP39, в порядке [`ALL_LANGUAGES`].
+///
+/// Словари пересекаются (например, во французском и английском около сотни
+/// общих слов), и фраза из общих слов может оказаться верной сразу в двух
+/// языках — с разной энтропией, ведь индексы слов в словарях разные.
+/// [`decode`] берёт первый подошедший язык; здесь видны и остальные.
+/// Контрольная сумма проверяется всегда, [`Options::ignore_checksum`] не учитывается.
+pub fn decode_all(mnemonic: &str, options: &Options) -> Vec<Decoded> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    if let Some(wordlist) = &options.wordlist {
+        return decode_with_wordlist(mnemonic, wordlist, false).into_iter().collect();
+    }
+    options
+        .languages()
+        .iter()
+        .filter_map(|&language| try_bip39(mnemonic, &[language]))
+        .collect()
+}
+
+fn decode_with_wordlist(
+    mnemonic_str: &str,
+    wordlist: &Wordlist,
+    ignore_checksum: bool,
+) -> Result<Decoded, DecodeError> {
+    let words: Vec<&str> = mnemonic_str.split_whitespace().collect();
+
+    let invalid_words: Vec<String> = words
+        .iter()
+        .filter(|word| wordlist.find_word(word).is_none())
+        .map(|word| word.to_string())
+        .collect();
+    if !invalid_words.is_empty() {
+        return Err(DecodeError::InvalidWords(invalid_words));
+    }
+
+    if !VALID_WORD_COUNTS.contains(&words.len()) {
+        return Err(DecodeError::BadWordCount(words.len()));
+    }
+
+    let indices: Vec<u16> = words
+        .iter()
+        .map(|word| wordlist.find_word(word).expect("слово есть в словаре"))
+        .collect();
+
+    let entropy = match checked_entropy(&indices) {
+        Some(entropy) => entropy,
+        None if ignore_checksum => indices_to_bytes(&indices),
+        None => return Err(DecodeError::InvalidChecksum(None)),
+    };
+
+    Ok(Decoded {
+        entropy: Entropy(entropy),
+        language: None,
+    })
+}
+
+/// Ошибка при конвертации энтропии в мнемонику.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// Длина энтропии в битах не входит в 128/160/192/224/256.
+    BadEntropyLength(usize),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::BadEntropyLength(bits) => write!(
+                f,
+                "Неверная длина энтропии: {} бит (BIP39 требует 128/160/192/224/256 бит)",
+                bits
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Конвертирует энтропию в мнемоническую фразу BIP39 (English).
+pub fn encode(entropy: &[u8]) -> Result<String, EncodeError> {
+    encode_in(Language::English, entropy)
+}
+
+/// Конвертирует энтропию в мнемоническую фразу BIP39 на указанном языке.
+pub fn encode_in(language: Language, entropy: &[u8]) -> Result<String, EncodeError> {
+    Mnemonic::from_entropy_in(language, entropy)
+        .map(|mnemonic| mnemonic.to_string())
+        .map_err(|_| EncodeError::BadEntropyLength(entropy.len() * 8))
+}
+
+/// Определяет, почему мнемоника не прошла проверку BIP39.
+///
+/// Неверные слова считаются относительно словаря, в котором нашлось больше
+/// всего слов фразы (среди языков, разрешённых в `options`). Пословный
+/// отчёт с индексами и ближайшими словами даёт [`diagnose_mnemonic`].
+pub fn analyze_mnemonic(mnemonic_str: &str, options: &Options) -> DecodeError {
+    let mnemonic_str = &*normalize_mnemonic(mnemonic_str);
+    if let Some(wordlist) = &options.wordlist {
+        return decode_with_wordlist(mnemonic_str, wordlist, false)
+            .err()
+            .unwrap_or(DecodeError::InvalidChecksum(None));
+    }
+
+    let words: Vec<&str> = mnemonic_str.split_whitespace().collect();
+
+    let (language, invalid_words) = options
+        .languages()
+        .iter()
+        .map(|&language| {
+            let invalid: Vec<String> = words
+                .iter()
+                .filter(|word| language.find_word(word).is_none())
+                .map(|word| word.to_string())
+                .collect();
+            (language, invalid)
+        })
+        .min_by_key(|(_, invalid)| invalid.len())
+        .expect("список языков не пуст");
+
+    if !invalid_words.is_empty() {
+        DecodeError::InvalidWords(invalid_words)
+    } else if !VALID_WORD_COUNTS.contains(&words.len()) {
+        DecodeError::BadWordCount(words.len())
+    } else {
+        DecodeError::InvalidChecksum(Some(language))
+    }
+}
+
+/// Извлекает все 11*N бит мнемоники без проверки контрольной суммы.
+///
+/// Используется первый из разрешённых в `options` языков, в словаре которого
+/// есть все слова фразы.
+pub fn decode_ignore_checksum(mnemonic_str: &str, options: &Options) -> Result<Decoded, DecodeError> {
+    let mnemonic_str = &*normalize_mnemonic(mnemonic_str);
+    if let Some(wordlist) = &options.wordlist {
+        return decode_with_wordlist(mnemonic_str, wordlist, true);
+    }
+
+    let words: Vec<&str> = mnemonic_str.split_whitespace().collect();
+
+    let language = match options
+        .languages()
+        .iter()
+        .find(|language| words.iter().all(|word| language.find_word(word).is_some()))
+    {
+        Some(&language) => language,
+        None => return Err(analyze_mnemonic(mnemonic_str, options)),
+    };
+
+    // Проверяем корректное количество слов
+    if !VALID_WORD_COUNTS.contains(&words.len()) {
+        return Err(DecodeError::BadWordCount(words.len()));
+    }
+
+    // Преобразуем слова в индексы
+    let indices: Vec<u16> = words
+        .iter()
+        .map(|word| language.find_word(word).expect("слово есть в словаре"))
+        .collect();
+
+    Ok(Decoded {
+        entropy: Entropy(indices_to_bytes(&indices)),
+        language: Some(language),
+    })
+}
+
+/// Извлекает энтропию из индексов слов, проверяя контрольную сумму BIP39
+/// (первые ENT/32 бит SHA-256 от энтропии). `None`, если чексум не сходится.
+fn checked_entropy(indices: &[u16]) -> Option<Vec<u8>> {
+    let total_bits = indices.len() * 11;
+    let checksum_bits = total_bits / 33;
+    let entropy_bytes = (total_bits - checksum_bits) / 8;
+
+    let bytes = indices_to_bytes(indices);
+    let entropy = bytes[..entropy_bytes].to_vec();
+
+    let expected = sha256::Hash::hash(&entropy).to_byte_array()[0] >> (8 - checksum_bits);
+    let actual = bytes[entropy_bytes] >> (8 - checksum_bits);
+
+    (expected == actual).then_some(entropy)
+}
+
+/// Упаковывает 11-битные индексы слов в байты (старшие биты первыми).
+/// Неполный последний байт дополняется нулями справа.
+fn indices_to_bytes(indices: &[u16]) -> Vec<u8> {
+    let total_bits = indices.len() * 11;
+    let mut bytes = vec![0u8; total_bits.div_ceil(8)];
+
+    for (i, &index) in indices.iter().enumerate() {
+        for j in 0..11 {
+            if index & (1 << (10 - j)) != 0 {
+                let bit_pos = i * 11 + j;
+                bytes[bit_pos / 8] |= 1 << (7 - bit_pos % 8);
+            }
+        }
+    }
+
+    bytes
+}
@@ -1,11 +1,30 @@
-use bip39::{Mnemonic, Language};
-use clap::Parser;
-use std::str::FromStr;
-use std::fs;
-use std::io::Write;
+mod cli;
+
+use clap::{ArgAction, Parser, Subcommand};
+use cli::analyze::AnalyzeArgs;
+use cli::bip85::Bip85Args;
+use cli::decode::DecodeArgs;
+#[cfg(feature = "encryption")]
+use cli::decrypt::DecryptArgs;
+use cli::derive::DeriveArgs;
+use cli::diff::DiffArgs;
+use cli::encode::EncodeArgs;
+use cli::entropy::EntropyCommand;
+use cli::fix::FixArgs;
+use cli::i18n::UiLang;
+use cli::input::{parse_input_encoding, InputEncoding};
+#[cfg(feature = "grpc")]
+use cli::grpc::GrpcArgs;
+use cli::recover::RecoverArgs;
+#[cfg(feature = "server")]
+use cli::serve::ServeArgs;
+use cli::seedxor::SeedXorCommand;
+use cli::shamir::ShamirCommand;
+use cli::slip39::Slip39Command;
+use cli::validate::ValidateArgs;
+use cli::wordlist::WordlistCommand;
+use log::LevelFilter;
 use std::path::PathBuf;
-use rayon::prelude::*;
-use indicatif::{ProgressBar, ProgressStyle};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -13,312 +32,155 @@ use indicatif::{ProgressBar, ProgressStyle};
     about = "Утилита для конвертации мнемонической фразы обратно в энтропию",
     version = "0.1.0"
 )]
-struct Args {
-    #[arg(short, long)]
-    mnemonic: Option<String>,
-
-    #[arg(short = 'i', long = "input")]
-    input_file: Option<PathBuf>,
-
-    #[arg(short = 'o', long = "output")]
-    output_file: Option<PathBuf>,
-
-    #[arg(long, default_value = "true")]
-    hex: bool,
-
-    #[arg(long, default_value = "false")]
-    ignore_checksum: bool,
-
-    #[arg(long)]
-    error_log: Option<PathBuf>,
-
-    #[arg(long, default_value = "false")]
-    skip_invalid: bool,
-
-    #[arg(long, default_value = "false")]
-    verbose_errors: bool,
-}
-
-fn try_bip39_english(mnemonic_str: &str) -> Option<Vec<u8>> {
-    // Пробуем стандартный BIP39 English
-    if let Ok(mnemonic) = Mnemonic::from_str(mnemonic_str) {
-        return Some(mnemonic.to_entropy());
-    }
-    None
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Количество рабочих потоков (по умолчанию RAYON_NUM_THREADS или число ядер)
+    #[arg(long, global = true, value_name = "N")]
+    threads: Option<usize>,
+
+    /// Запретить выгрузку памяти процесса в swap (mlockall), чтобы энтропия
+    /// и seed не попали на диск; только Unix
+    #[arg(long, global = true, default_value = "false")]
+    mlock: bool,
+
+    /// Маскировать фразы в консоли и логах ошибок: первое и последнее слово
+    /// и начало SHA-256
+    #[arg(long, global = true, default_value = "false")]
+    redact: bool,
+
+    /// Выводить только результаты: без заголовков, итогов, подсказок и предупреждений
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Подробный вывод в stderr: -v — время обработки, -vv — ещё и итог каждой строки
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Уровень сообщений в stderr и журнале: off, error, warn, info, debug, trace
+    /// (по умолчанию info; с -q — error, с -v — debug, с -vv — trace)
+    #[arg(long, global = true, value_name = "LEVEL", env = "MNEM_LOG_LEVEL")]
+    log_level: Option<LevelFilter>,
+
+    /// Дописывать сообщения в файл с отметкой времени и уровнем
+    #[arg(long, global = true, value_name = "FILE", env = "MNEM_LOG_FILE")]
+    log_file: Option<PathBuf>,
+
+    /// Язык сообщений (по умолчанию — язык системной локали)
+    #[arg(long, global = true, value_enum, value_name = "LANG")]
+    ui_lang: Option<UiLang>,
+
+    /// Кодировка входных файлов и stdin: auto (по умолчанию — по BOM и
+    /// содержимому) или метка вроде utf-16le, windows-1251, koi8-r
+    #[arg(
+        long,
+        global = true,
+        value_name = "ENC",
+        default_value = "auto",
+        value_parser = parse_input_encoding
+    )]
+    input_encoding: InputEncoding,
+
+    // Без подкоманды утилита декодирует мнемоники в энтропию
+    #[command(flatten)]
+    decode: DecodeArgs,
 }
 
-fn analyze_mnemonic(mnemonic_str: &str) -> String {
-    let words: Vec<&str> = mnemonic_str.split_whitespace().collect();
-    let wordlist = Language::English.word_list();
-    
-    let word_count = words.len();
-    let mut invalid_words = Vec::new();
-    
-    for word in &words {
-        if wordlist.iter().position(|&w| w == *word).is_none() {
-            invalid_words.push(*word);
-        }
-    }
-    
-    if !invalid_words.is_empty() {
-        format!("Неверные слова (не BIP39 English): {:?}. Попробованы все языки BIP39", 
-                invalid_words.iter().take(3).collect::<Vec<_>>())
-    } else if ![12, 15, 18, 21, 24].contains(&word_count) {
-        format!("Неверное количество слов: {} (BIP39 требует 12/15/18/21/24 слов)", word_count)
-    } else {
-        "Неверная контрольная сумма BIP39 (попробованы все языки)".to_string()
-    }
-}
-
-fn decode_mnemonic_ignore_checksum(mnemonic_str: &str) -> Result<Vec<u8>, String> {
-    let words: Vec<&str> = mnemonic_str.split_whitespace().collect();
-    
-    // Получаем словарь BIP39
-    let wordlist = Language::English.word_list();
-    
-    // Преобразуем слова в индексы
-    let mut indices = Vec::new();
-    for word in &words {
-        match wordlist.iter().position(|&w| w == *word) {
-            Some(idx) => indices.push(idx as u16),
-            None => {
-                return Err(analyze_mnemonic(mnemonic_str));
-            }
-        }
-    }
-    
-    // Преобразуем индексы в биты
-    let total_bits = indices.len() * 11;
-    let mut bits = vec![false; total_bits];
-    
-    for (i, &index) in indices.iter().enumerate() {
-        for j in 0..11 {
-            let bit_pos = i * 11 + j;
-            bits[bit_pos] = (index & (1 << (10 - j))) != 0;
-        }
-    }
-    
-    // Проверяем корректное количество слов
-    match words.len() {
-        12 | 15 | 18 | 21 | 24 => {},
-        _ => return Err(format!("Неподдерживаемое количество слов: {}", words.len())),
-    };
-    
-    // Извлекаем энтропию (все биты, включая чексум)
-    // Для режима ignore-checksum мы берем ВСЕ биты
-    let num_bytes = (total_bits + 7) / 8; // Округление вверх
-    let mut entropy = vec![0u8; num_bytes];
-    for (i, chunk) in bits.chunks(8).enumerate() {
-        let mut byte = 0u8;
-        for (j, &bit) in chunk.iter().enumerate() {
-            if bit {
-                byte |= 1 << (7 - j);
-            }
-        }
-        entropy[i] = byte;
-    }
-    
-    Ok(entropy)
-}
-
-fn process_mnemonic(mnemonic_str: &str, hex: bool, ignore_checksum: bool) -> Result<String, String> {
-    // Сначала пробуем стандартный BIP39 English
-    if let Some(entropy) = try_bip39_english(mnemonic_str) {
-        let entropy_str = if hex {
-            hex::encode(&entropy)
-        } else {
-            format!("{:?}", entropy)
-        };
-        return Ok(entropy_str);
-    }
-    
-    // Если не сработало, пробуем ignore_checksum режим
-    let entropy = if ignore_checksum {
-        decode_mnemonic_ignore_checksum(mnemonic_str)?
-    } else {
-        // Возвращаем понятную ошибку
-        return Err(analyze_mnemonic(mnemonic_str));
-    };
-    
-    let entropy_str = if hex {
-        hex::encode(&entropy)
-    } else {
-        format!("{:?}", entropy)
-    };
-    Ok(entropy_str)
-}
-
-enum ProcessResult {
-    Success(String),
-    Error { message: String, mnemonic: String },
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Конвертировать hex-энтропию обратно в мнемоническую фразу BIP39
+    Encode(EncodeArgs),
+    /// Получить энтропию вручную: из бросков кости, монеты или битовой строки
+    Entropy {
+        #[command(subcommand)]
+        command: EntropyCommand,
+    },
+    /// Вывести адреса Bitcoin по BIP44/49/84/86 или произвольному пути
+    Derive(DeriveArgs),
+    /// Вывести дочерние мнемоники BIP85 (m/83696968'/39'/язык'/слова'/индекс') из мастер-фразы
+    Bip85(Bip85Args),
+    /// Подобрать одно или два неизвестных слова мнемоники ('?' или '_') перебором словаря
+    Recover(RecoverArgs),
+    /// Проверить мнемоники, не выводя энтропию (код выхода 1 или 2, если есть невалидные)
+    Validate(ValidateArgs),
+    /// Исправить набор мнемоник (двойники, сокращения, опечатки) и записать
+    /// исправленные фразы вместо энтропии
+    Fix(FixArgs),
+    /// Сравнить две фразы пословно: различия, опечатки, перестановки и совпадение энтропии
+    Diff(DiffArgs),
+    /// Частотный анализ набора мнемоник: частые слова, смещение по позициям, критерий χ²
+    Analyze(AnalyzeArgs),
+    /// HTTP API: POST /decode с мнемоникой или пачкой, ответ в JSON
+    #[cfg(feature = "server")]
+    Serve(ServeArgs),
+    /// gRPC-сервис Decode/Validate/Derive и потоковое декодирование (proto/mnem_to_entropy.proto)
+    #[cfg(feature = "grpc")]
+    ServeGrpc(GrpcArgs),
+    /// Расшифровать файл, записанный с --encrypt-output (age)
+    #[cfg(feature = "encryption")]
+    Decrypt(DecryptArgs),
+    /// SeedXOR (как в Coldcard): доли-мнемоники, XOR которых даёт исходную фразу
+    Seedxor {
+        #[command(subcommand)]
+        command: SeedXorCommand,
+    },
+    /// Схема Шамира над энтропией: пороговые hex-доли без формата SLIP-39
+    Shamir {
+        #[command(subcommand)]
+        command: ShamirCommand,
+    },
+    /// Операции с долями Шамира SLIP-39
+    Slip39 {
+        #[command(subcommand)]
+        command: Slip39Command,
+    },
+    /// Поиск слов в словарях BIP39: по началу, регулярному выражению или опечаткам
+    Wordlist {
+        #[command(subcommand)]
+        command: WordlistCommand,
+    },
 }
 
 fn main() {
-    let args = Args::parse();
-
-    let mnemonics: Vec<String> = if let Some(input_path) = &args.input_file {
-        match fs::read_to_string(input_path) {
-            Ok(content) => {
-                let data: Vec<String> = content.lines()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                
-                if args.output_file.is_some() {
-                    println!("📂 Загружено строк: {}", data.len());
-                }
-                
-                data
-            }
-            Err(e) => {
-                eprintln!("Ошибка при чтении файла {:?}: {}", input_path, e);
-                std::process::exit(1);
-            }
-        }
-    } else if let Some(m) = &args.mnemonic {
-        vec![m.clone()]
-    } else {
-        println!("Введите мнемоническую фразу:");
-        let mut input = String::new();
-        std::io::stdin()
-            .read_line(&mut input)
-            .expect("Не удалось прочитать ввод");
-        vec![input.trim().to_string()]
-    };
-
-    let total_count = mnemonics.len();
-    
-    // Создаём прогресс-бар только если записываем в файл
-    let progress_bar = if args.output_file.is_some() && total_count > 1 {
-        let pb = ProgressBar::new(total_count as u64);
-        pb.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
-                .unwrap()
-                .progress_chars("#>-")
-        );
-        Some(pb)
-    } else {
-        None
-    };
-
-    // Параллельная обработка
-    let results: Vec<(usize, ProcessResult)> = mnemonics
-        .par_iter()
-        .enumerate()
-        .map(|(idx, mnemonic_str)| {
-            let result = match process_mnemonic(mnemonic_str, args.hex, args.ignore_checksum) {
-                Ok(entropy_str) => ProcessResult::Success(entropy_str),
-                Err(e) => ProcessResult::Error { 
-                    message: e, 
-                    mnemonic: mnemonic_str.to_string() 
-                },
-            };
-            
-            if let Some(ref pb) = progress_bar {
-                pb.inc(1);
-            }
-            
-            (idx, result)
-        })
-        .collect();
-
-    if let Some(pb) = progress_bar {
-        pb.finish_and_clear();
-    }
-
-    // Сортируем результаты по индексу для сохранения порядка
-    let mut sorted_results = results;
-    sorted_results.sort_by_key(|(idx, _)| *idx);
-
-    let mut success_results = Vec::new();
-    let mut error_results = Vec::new();
-
-    // Обрабатываем результаты
-    for (idx, result) in sorted_results {
-        match result {
-            ProcessResult::Success(entropy_str) => {
-                if args.output_file.is_none() {
-                    println!("\n=== Результат {} ===", idx + 1);
-                    println!("Мнемоническая фраза: {}", mnemonics[idx]);
-                    println!("Энтропия: {}", entropy_str);
-                }
-                success_results.push(entropy_str);
-            }
-            ProcessResult::Error { message, mnemonic } => {
-                if args.output_file.is_none() {
-                    eprintln!("\n=== Ошибка {} ===", idx + 1);
-                    eprintln!("Мнемоническая фраза: {}", mnemonic);
-                    eprintln!("Ошибка: {}", message);
-                }
-                error_results.push((mnemonic, message));
-            }
-        }
-    }
-
-    if let Some(output_path) = &args.output_file {
-        match fs::File::create(output_path) {
-            Ok(mut file) => {
-                for result in &success_results {
-                    if let Err(e) = writeln!(file, "{}", result) {
-                        eprintln!("Ошибка при записи в файл {:?}: {}", output_path, e);
-                        std::process::exit(1);
-                    }
-                }
-                println!("✓ Результаты сохранены в файл: {:?}", output_path);
-                println!("  Обработано успешно: {} мнемоник", success_results.len());
-                if !error_results.is_empty() {
-                    println!("  Ошибок: {}", error_results.len());
-                }
-            }
-            Err(e) => {
-                eprintln!("Ошибка при создании файла {:?}: {}", output_path, e);
-                std::process::exit(1);
-            }
-        }
-    }
-
-    // Сохраняем ошибки в отдельный файл, если указан
-    if let Some(error_log_path) = &args.error_log {
-        if !error_results.is_empty() {
-            match fs::File::create(error_log_path) {
-                Ok(mut file) => {
-                    for (mnemonic, message) in &error_results {
-                        let line = if args.verbose_errors {
-                            format!("{} | {}", mnemonic, message)
-                        } else {
-                            mnemonic.clone()
-                        };
-                        if let Err(e) = writeln!(file, "{}", line) {
-                            eprintln!("Ошибка при записи в лог ошибок {:?}: {}", error_log_path, e);
-                            std::process::exit(1);
-                        }
-                    }
-                    println!("📝 Лог ошибок сохранён в файл: {:?}", error_log_path);
-                }
-                Err(e) => {
-                    eprintln!("Ошибка при создании файла лога {:?}: {}", error_log_path, e);
-                    std::process::exit(1);
-                }
-            }
-        }
-    }
-
-    // Показываем предупреждение если много ошибок и это не режим skip_invalid
-    if !args.skip_invalid && !error_results.is_empty() {
-        let error_rate = (error_results.len() as f64 / total_count as f64) * 100.0;
-        if error_rate > 50.0 {
-            println!("\n⚠️  ВНИМАНИЕ: {:.1}% мнемоник невалидны!", error_rate);
-            println!("   Возможно это не BIP39 мнемоники (Electrum, Monero и т.д.)");
-            println!("   Используйте --skip-invalid для игнорирования ошибок");
-            println!("   Используйте --error-log FILE для сохранения невалидных мнемоник");
-        }
-    }
-
-    // Завершаем с кодом ошибки только если НЕТ успешных результатов И не установлен skip_invalid
-    if !error_results.is_empty() && success_results.is_empty() && !args.skip_invalid {
-        eprintln!("\n❌ Все мнемоники завершились с ошибкой!");
-        std::process::exit(1);
+    // Ошибка в аргументах — код EXIT_USAGE вместо принятого в clap 2;
+    // --help и --version завершаются с 0
+    let cli = Cli::try_parse().unwrap_or_else(|e| {
+        let code = if e.use_stderr() { cli::EXIT_USAGE } else { 0 };
+        let _ = e.print();
+        std::process::exit(code);
+    });
+    cli::i18n::configure_ui_lang(cli.ui_lang);
+    cli::logging::configure_logging(cli.log_level, cli.quiet, cli.verbose, cli.log_file.as_deref());
+    // До создания пула потоков: стеки рабочих потоков тоже должны быть закреплены
+    cli::lock_memory(cli.mlock);
+    cli::configure_threads(cli.threads);
+    cli::configure_redaction(cli.redact);
+    cli::configure_quiet(cli.quiet);
+    cli::input::configure_input_encoding(cli.input_encoding);
+
+    match &cli.command {
+        Some(Command::Encode(args)) => cli::encode::run(args),
+        Some(Command::Entropy { command }) => cli::entropy::run(command),
+        Some(Command::Derive(args)) => cli::derive::run(args),
+        Some(Command::Bip85(args)) => cli::bip85::run(args),
+        Some(Command::Recover(args)) => cli::recover::run(args),
+        Some(Command::Validate(args)) => cli::validate::run(args),
+        Some(Command::Fix(args)) => cli::fix::run(args),
+        Some(Command::Diff(args)) => cli::diff::run(args),
+        Some(Command::Analyze(args)) => cli::analyze::run(args),
+        #[cfg(feature = "server")]
+        Some(Command::Serve(args)) => cli::serve::run(args),
+        #[cfg(feature = "grpc")]
+        Some(Command::ServeGrpc(args)) => cli::grpc::run(args),
+        #[cfg(feature = "encryption")]
+        Some(Command::Decrypt(args)) => cli::decrypt::run(args),
+        Some(Command::Seedxor { command }) => cli::seedxor::run(command),
+        Some(Command::Shamir { command }) => cli::shamir::run(command),
+        Some(Command::Slip39 { command }) => cli::slip39::run(command),
+        Some(Command::Wordlist { command }) => cli::wordlist::run(command),
+        None if cli.decode.watch.is_some() => cli::watch::run(&cli.decode),
+        None if cli.decode.repl => cli::decode::repl(&cli.decode),
+        None => cli::decode::run(&cli.decode),
     }
 }
-
@@ -0,0 +1,171 @@
+//! Транскрипт Merlin поверх STROBE-128/1600 — ровно то подмножество, что
+//! нужно для HDKD sr25519 (schnorrkel): добавление сообщений и вызовы.
+
+/// Скорость губки STROBE-128 в байтах: 200 - 2 * 128 / 8 - 2.
+const STROBE_R: u8 = 166;
+
+const FLAG_I: u8 = 1;
+const FLAG_A: u8 = 1 << 1;
+const FLAG_C: u8 = 1 << 2;
+const FLAG_M: u8 = 1 << 4;
+
+/// Состояние STROBE-128 с Keccak-f[1600].
+struct Strobe128 {
+    state: [u8; 200],
+    pos: u8,
+    pos_begin: u8,
+    cur_flags: u8,
+}
+
+impl Strobe128 {
+    fn new(protocol_label: &[u8]) -> Self {
+        let mut state = [0u8; 200];
+        state[..6].copy_from_slice(&[1, STROBE_R + 2, 1, 0, 1, 96]);
+        state[6..18].copy_from_slice(b"STROBEv1.0.2");
+        keccak_f(&mut state);
+        let mut strobe = Strobe128 {
+            state,
+            pos: 0,
+            pos_begin: 0,
+            cur_flags: 0,
+        };
+        strobe.meta_ad(protocol_label, false);
+        strobe
+    }
+
+    fn meta_ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_M | FLAG_A, more);
+        self.absorb(data);
+    }
+
+    fn ad(&mut self, data: &[u8], more: bool) {
+        self.begin_op(FLAG_A, more);
+        self.absorb(data);
+    }
+
+    fn prf(&mut self, data: &mut [u8], more: bool) {
+        self.begin_op(FLAG_I | FLAG_A | FLAG_C, more);
+        self.squeeze(data);
+    }
+
+    fn run_f(&mut self) {
+        self.state[self.pos as usize] ^= self.pos_begin;
+        self.state[self.pos as usize + 1] ^= 0x04;
+        self.state[STROBE_R as usize + 1] ^= 0x80;
+        keccak_f(&mut self.state);
+        self.pos = 0;
+        self.pos_begin = 0;
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for byte in data {
+            self.state[self.pos as usize] ^= byte;
+            self.pos += 1;
+            if self.pos == STROBE_R {
+                self.run_f();
+            }
+        }
+    }
+
+    fn squeeze(&mut self, data: &mut [u8]) {
+        for byte in data {
+            *byte = self.state[self.pos as usize];
+            self.state[self.pos as usize] = 0;
+            self.pos += 1;
+            if self.pos == STROBE_R {
+                self.run_f();
+            }
+        }
+    }
+
+    fn begin_op(&mut self, flags: u8, more: bool) {
+        if more {
+            debug_assert_eq!(self.cur_flags, flags, "продолжение другой операции STROBE");
+            return;
+        }
+        let old_begin = self.pos_begin;
+        self.pos_begin = self.pos + 1;
+        self.cur_flags = flags;
+        self.absorb(&[old_begin, flags]);
+        // Операции с шифрованием (флаг C) начинаются с нового блока
+        if flags & FLAG_C != 0 && self.pos != 0 {
+            self.run_f();
+        }
+    }
+}
+
+fn keccak_f(state: &mut [u8; 200]) {
+    let mut lanes = [0u64; 25];
+    for (lane, chunk) in lanes.iter_mut().zip(state.chunks(8)) {
+        *lane = u64::from_le_bytes(chunk.try_into().expect("срез 8 байт"));
+    }
+    keccak::f1600(&mut lanes);
+    for (chunk, lane) in state.chunks_mut(8).zip(lanes) {
+        chunk.copy_from_slice(&lane.to_le_bytes());
+    }
+}
+
+/// Транскрипт Merlin: сообщения с метками и детерминированные вызовы из них.
+pub(crate) struct Transcript {
+    strobe: Strobe128,
+}
+
+impl Transcript {
+    pub(crate) fn new(label: &[u8]) -> Self {
+        let mut transcript = Transcript {
+            strobe: Strobe128::new(b"Merlin v1.0"),
+        };
+        transcript.append_message(b"dom-sep", label);
+        transcript
+    }
+
+    pub(crate) fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        let length = (message.len() as u32).to_le_bytes();
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&length, true);
+        self.strobe.ad(message, false);
+    }
+
+    pub(crate) fn challenge_bytes(&mut self, label: &[u8], dest: &mut [u8]) {
+        let length = (dest.len() as u32).to_le_bytes();
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&length, true);
+        self.strobe.prf(dest, false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Простой протокол из тестов merlin: одно сообщение и один вызов.
+    #[test]
+    fn simple_transcript() {
+        let mut transcript = Transcript::new(b"test protocol");
+        transcript.append_message(b"some label", b"some data");
+        let mut challenge = [0u8; 32];
+        transcript.challenge_bytes(b"challenge", &mut challenge);
+        assert_eq!(
+            hex::encode(challenge),
+            "d5a21972d0d5fe320c0d263fac7fffb8145aa640af6e9bca177c03c7efcf0615"
+        );
+    }
+
+    /// Сообщения длиннее скорости губки и чередование с вызовами.
+    #[test]
+    fn complex_transcript() {
+        let mut transcript = Transcript::new(b"test protocol");
+        transcript.append_message(b"step1", b"some data");
+        let data = [99u8; 1024];
+        let mut challenge = [0u8; 32];
+        for _ in 0..32 {
+            transcript.challenge_bytes(b"challenge", &mut challenge);
+            transcript.append_message(b"bigdata", &data);
+            transcript.append_message(b"challengedata", &challenge);
+        }
+        assert_eq!(
+            hex::encode(challenge),
+            "a8c933f54fae76e3f9bea93648c1308e7dfa2152dd51674ff3ca438351cf003c"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Модуль Python (функция `python`, сборка через maturin): `import mnem_to_entropy`.
+//! Пакетные функции отпускают GIL и считают на всех ядрах.
+
+// Обёртки #[pyfunction] из pyo3 0.22 приводят PyErr к самому себе через
+// `From`; в коде модуля таких преобразований нет
+#![allow(clippy::useless_conversion)]
+
+use crate::{
+    derive_addresses, language_from_name, language_name, mnemonic_to_seed, AddressType,
+    DerivationPath, Language, Network, Options, RootKeys,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// Результат `decode`.
+#[pyclass(module = "mnem_to_entropy", frozen)]
+pub struct Decoded {
+    entropy: Vec<u8>,
+    language: Option<Language>,
+}
+
+#[pymethods]
+impl Decoded {
+    /// Энтропия байтами (`bytes`).
+    #[getter]
+    fn entropy(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.entropy)
+    }
+
+    /// Энтропия в hex.
+    #[getter]
+    fn hex(&self) -> String {
+        hex::encode(&self.entropy)
+    }
+
+    /// Словарь, по которому распознана фраза.
+    #[getter]
+    fn language(&self) -> &'static str {
+        self.language.map(language_name).unwrap_or("custom")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Decoded(hex='{}', language='{}')", self.hex(), self.language())
+    }
+}
+
+/// Параметры деривации, общие для `derive` и `derive_batch`.
+struct DeriveParams {
+    path: DerivationPath,
+    address_type: AddressType,
+    network: Network,
+    start: u32,
+    count: u32,
+    passphrase: String,
+}
+
+impl DeriveParams {
+    fn parse(
+        path: &str,
+        address_type: Option<&str>,
+        network: &str,
+        start: u32,
+        count: u32,
+        passphrase: &str,
+    ) -> PyResult<Self> {
+        let path = DerivationPath::from_str(path)
+            .map_err(|e| PyValueError::new_err(format!("неверный путь: {}", e)))?;
+        let address_type = match address_type {
+            Some(name) => AddressType::from_str(name).map_err(PyValueError::new_err)?,
+            None => AddressType::from_path(&path).ok_or_else(|| {
+                PyValueError::new_err("не удалось определить тип адреса по пути, укажите address_type")
+            })?,
+        };
+        let network = Network::from_str(network)
+            .map_err(|e| PyValueError::new_err(format!("неверная сеть: {}", e)))?;
+        Ok(DeriveParams {
+            path,
+            address_type,
+            network,
+            start,
+            count,
+            passphrase: passphrase.to_string(),
+        })
+    }
+
+    /// Адреса `(путь, адрес)` для фразы; фраза предварительно проверяется.
+    fn derive(&self, mnemonic: &str, options: &Options) -> Result<Vec<(String, String)>, String> {
+        crate::decode(mnemonic, options).map_err(|e| e.to_string())?;
+        let seed = mnemonic_to_seed(mnemonic, &self.passphrase);
+        let root = RootKeys::from_seed(&seed, self.network);
+        let addresses = derive_addresses(
+            &root.xprv,
+            self.network,
+            &self.path,
+            self.address_type,
+            self.start,
+            self.count,
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(addresses
+            .into_iter()
+            .map(|derived| (derived.path.to_string(), derived.address))
+            .collect())
+    }
+}
+
+fn options(ignore_checksum: bool, language: Option<&str>) -> PyResult<Options> {
+    let language = match language {
+        Some(name) => Some(
+            language_from_name(name)
+                .ok_or_else(|| PyValueError::new_err(format!("неизвестный язык '{}'", name)))?,
+        ),
+        None => None,
+    };
+    Ok(Options {
+        ignore_checksum,
+        language,
+        ..Options::default()
+    })
+}
+
+fn decode_one(mnemonic: &str, options: &Options) -> Result<Decoded, String> {
+    crate::decode(mnemonic, options)
+        .map(|decoded| Decoded {
+            entropy: decoded.entropy.into_bytes(),
+            language: decoded.language,
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Мнемоника → энтропия; при ошибке бросает `ValueError`.
+#[pyfunction]
+#[pyo3(signature = (mnemonic, ignore_checksum = false, language = None))]
+fn decode(mnemonic: &str, ignore_checksum: bool, language: Option<&str>) -> PyResult<Decoded> {
+    let options = options(ignore_checksum, language)?;
+    decode_one(mnemonic, &options).map_err(PyValueError::new_err)
+}
+
+/// Энтропия (16–32 байта) → мнемоника.
+#[pyfunction]
+#[pyo3(signature = (entropy, language = "english"))]
+fn encode(entropy: &[u8], language: &str) -> PyResult<String> {
+    let language = language_from_name(language)
+        .ok_or_else(|| PyValueError::new_err(format!("неизвестный язык '{}'", language)))?;
+    crate::encode_in(language, entropy).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Адреса для фразы: список пар `(путь, адрес)`.
+#[pyfunction]
+#[pyo3(signature = (
+    mnemonic, path, count = 20, start = 0, passphrase = "", network = "bitcoin",
+    address_type = None, language = None
+))]
+#[allow(clippy::too_many_arguments)]
+fn derive(
+    py: Python<'_>,
+    mnemonic: &str,
+    path: &str,
+    count: u32,
+    start: u32,
+    passphrase: &str,
+    network: &str,
+    address_type: Option<&str>,
+    language: Option<&str>,
+) -> PyResult<Vec<(String, String)>> {
+    let params = DeriveParams::parse(path, address_type, network, start, count, passphrase)?;
+    let options = options(false, language)?;
+    py.allow_threads(|| params.derive(mnemonic, &options))
+        .map_err(PyValueError::new_err)
+}
+
+/// Пачка мнемоник → список пар `(Decoded | None, ошибка | None)` в том же порядке.
+#[pyfunction]
+#[pyo3(signature = (mnemonics, ignore_checksum = false, language = None))]
+fn decode_batch(
+    py: Python<'_>,
+    mnemonics: Vec<String>,
+    ignore_checksum: bool,
+    language: Option<&str>,
+) -> PyResult<Vec<(Option<Decoded>, Option<String>)>> {
+    let options = options(ignore_checksum, language)?;
+    Ok(py.allow_threads(|| {
+        mnemonics
+            .par_iter()
+            .map(|mnemonic| match decode_one(mnemonic, &options) {
+                Ok(decoded) => (Some(decoded), None),
+                Err(e) => (None, Some(e)),
+            })
+            .collect()
+    }))
+}
+
+/// Адреса для пачки фраз → список пар `(адреса | None, ошибка | None)`.
+#[pyfunction]
+#[pyo3(signature = (
+    mnemonics, path, count = 20, start = 0, passphrase = "", network = "bitcoin",
+    address_type = None, language = None
+))]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn derive_batch(
+    py: Python<'_>,
+    mnemonics: Vec<String>,
+    path: &str,
+    count: u32,
+    start: u32,
+    passphrase: &str,
+    network: &str,
+    address_type: Option<&str>,
+    language: Option<&str>,
+) -> PyResult<Vec<(Option<Vec<(String, String)>>, Option<String>)>> {
+    let params = DeriveParams::parse(path, address_type, network, start, count, passphrase)?;
+    let options = options(false, language)?;
+    Ok(py.allow_threads(|| {
+        mnemonics
+            .par_iter()
+            .map(|mnemonic| match params.derive(mnemonic, &options) {
+                Ok(addresses) => (Some(addresses), None),
+                Err(e) => (None, Some(e)),
+            })
+            .collect()
+    }))
+}
+
+#[pymodule]
+fn mnem_to_entropy(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Decoded>()?;
+    m.add_function(wrap_pyfunction!(decode, m)?)?;
+    m.add_function(wrap_pyfunction!(encode, m)?)?;
+    m.add_function(wrap_pyfunction!(derive, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_batch, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Проверка качества энтропии: признаки того, что фраза получена не из
+//! случайного источника — повторяющиеся байты, известные тестовые векторы,
+//! низкая энтропия Шеннона.
+
+use std::fmt;
+
+/// Энтропия официальных тестовых векторов BIP39 (Trezor), кроме
+/// однобайтовых повторов, которые распознаются отдельно. Такие фразы
+/// публикуются в документации, и средства на них давно похищены.
+pub const TEST_VECTORS: &[&str] = &[
+    "9e885d952ad362caeb4efe34a8e91bd2",
+    "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+    "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+    "c0ba5a8e914111210f2bd131f3d5e08d",
+    "6d9be1ee6ebd27a258115aad99b7317b9c8d28b6d76431c3",
+    "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863",
+    "23db8160a31d3e0dca3688ed941adbf3",
+    "8197a4a47f0425faeaa69deebc05ca29c0a5b5cc76ceacc0",
+    "066dca1a2bb7e8a1db2832148ce9933eee0f3ac9548d9384b7eccb8e6c5d63e5",
+    "f30f8c1da665478f49b001d94c5fc452",
+    "c10ec20dc3cd9f652c7fac2f1230f7a3c828389a14392f05",
+    "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+];
+
+/// Наибольший период повторяющегося шаблона, который считается подозрительным.
+pub const MAX_PATTERN_PERIOD: usize = 4;
+
+/// Доля от максимально возможной энтропии Шеннона (в битах на байт для
+/// данной длины), ниже которой энтропия считается низкой. У 16 случайных
+/// байт она почти всегда выше 0,9.
+pub const LOW_SHANNON_RATIO: f64 = 0.75;
+
+/// Признак неслучайной энтропии.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntropyWarning {
+    /// Энтропия — повтор шаблона из `period` байт (1 — все байты одинаковы,
+    /// в том числе нулевая энтропия фразы «abandon … about»).
+    RepeatingPattern { period: usize },
+    /// Энтропия совпадает с опубликованным тестовым вектором BIP39.
+    TestVector,
+    /// Энтропия Шеннона байтов ниже [`LOW_SHANNON_RATIO`] от возможной.
+    LowShannon { bits_per_byte: f64, max: f64 },
+}
+
+impl fmt::Display for EntropyWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntropyWarning::RepeatingPattern { period: 1 } => {
+                write!(f, "все байты энтропии одинаковы")
+            }
+            EntropyWarning::RepeatingPattern { period } => {
+                write!(f, "энтропия — повтор шаблона из {} байт", period)
+            }
+            EntropyWarning::TestVector => write!(f, "энтропия из тестовых векторов BIP39"),
+            EntropyWarning::LowShannon { bits_per_byte, max } => write!(
+                f,
+                "низкая энтропия Шеннона: {:.2} бит на байт из {:.2} возможных",
+                bits_per_byte, max
+            ),
+        }
+    }
+}
+
+/// Энтропия Шеннона распределения байтов, в битах на байт.
+pub fn shannon_entropy(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return 0.0;
+    }
+    let mut counts = [0usize; 256];
+    for &byte in bytes {
+        counts[byte as usize] += 1;
+    }
+    let total = bytes.len() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Признаки неслучайной энтропии; пустой список, если ничего не найдено.
+/// Повторяющийся шаблон исключает проверку Шеннона — она сработала бы всегда.
+pub fn check_entropy(entropy: &[u8]) -> Vec<EntropyWarning> {
+    let mut warnings = Vec::new();
+
+    let period = (1..=MAX_PATTERN_PERIOD)
+        .filter(|&period| period < entropy.len())
+        .find(|&period| entropy.iter().skip(period).zip(entropy).all(|(a, b)| a == b));
+    if let Some(period) = period {
+        warnings.push(EntropyWarning::RepeatingPattern { period });
+    }
+
+    if TEST_VECTORS.contains(&hex::encode(entropy).as_str()) {
+        warnings.push(EntropyWarning::TestVector);
+    }
+
+    if period.is_none() && !entropy.is_empty() {
+        let bits_per_byte = shannon_entropy(entropy);
+        let max = (entropy.len().min(256) as f64).log2();
+        if bits_per_byte < max * LOW_SHANNON_RATIO {
+            warnings.push(EntropyWarning::LowShannon { bits_per_byte, max });
+        }
+    }
+
+    warnings
+}
@@ -0,0 +1,776 @@
+//! Восстановление повреждённых мнемоник.
+//!
+//! Неизвестное слово обозначается `?` или `_`. Перебираются все 2048 слов
+//! словаря на его месте; контрольную сумму BIP39 проходит примерно
+//! 2048 / 2^CS кандидатов (128 для 12 слов, 8 для 24). Двух неизвестных
+//! слов — 2048² вариантов, из которых проходят около 262 тысяч для 12 слов
+//! и 16 тысяч для 24.
+//!
+//! Если неизвестно и место выпавшего слова, оно вставляется в каждую
+//! из `n + 1` позиций фразы из `n` слов.
+//!
+//! Начало фразы дополняется всеми продолжениями с верной контрольной
+//! суммой: их 2^(ENT − 11·K) для K известных слов.
+//!
+//! Перепутанные местами слова ищутся перебором перестановок пар
+//! (соседних или любых) у фразы, не прошедшей только проверку контрольной суммы.
+//!
+//! Опечатки исправляются по расстоянию Левенштейна: слово заменяется
+//! единственным ближайшим словом словаря, если фраза после замены
+//! проходит проверку контрольной суммы.
+
+use crate::{
+    analyze_mnemonic, checked_entropy, encode_in, indices_to_bytes, normalize_mnemonic, DecodeError,
+    Entropy, Language, Options, Wordlist, VALID_WORD_COUNTS, WORDLIST_SIZE,
+};
+use bitcoin_hashes::{sha256, Hash};
+use std::fmt;
+use std::sync::Arc;
+
+/// Обозначения неизвестного слова.
+pub const PLACEHOLDERS: [&str; 2] = ["?", "_"];
+
+/// Наибольшее число неизвестных слов, которые можно перебрать.
+pub const MAX_PLACEHOLDERS: usize = 2;
+
+/// Наибольшее расстояние Левенштейна, при котором слово считается опечаткой.
+pub const MAX_TYPO_DISTANCE: usize = 2;
+
+/// Мнемоника с известными позициями пропущенных слов в одном словаре.
+#[derive(Debug, Clone)]
+pub struct MissingWord {
+    indices: Vec<u16>,
+    positions: Vec<usize>,
+    dictionary: Dictionary,
+}
+
+/// Словарь BIP39 или пользовательский.
+#[derive(Debug, Clone)]
+pub(crate) enum Dictionary {
+    Language(Language),
+    Custom(Arc<Wordlist>),
+}
+
+impl Dictionary {
+    pub(crate) fn find_word(&self, word: &str) -> Option<u16> {
+        match self {
+            Dictionary::Language(language) => language.find_word(word),
+            Dictionary::Custom(wordlist) => wordlist.find_word(word),
+        }
+    }
+
+    /// Кандидат по индексам слов; `None`, если контрольная сумма не сходится.
+    fn candidate(&self, indices: &[u16]) -> Option<Candidate> {
+        let entropy = checked_entropy(indices)?;
+        let mnemonic = match self {
+            Dictionary::Language(language) => {
+                encode_in(*language, &entropy).expect("длина энтропии допустима")
+            }
+            Dictionary::Custom(wordlist) => indices
+                .iter()
+                .map(|&index| wordlist.word(index))
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        Some(Candidate {
+            mnemonic,
+            entropy: Entropy(entropy),
+            language: self.language(),
+        })
+    }
+
+    pub(crate) fn language(&self) -> Option<Language> {
+        match self {
+            Dictionary::Language(language) => Some(*language),
+            Dictionary::Custom(_) => None,
+        }
+    }
+
+    pub(crate) fn word(&self, index: u16) -> &str {
+        match self {
+            Dictionary::Language(language) => language.word_list()[index as usize],
+            Dictionary::Custom(wordlist) => wordlist.word(index),
+        }
+    }
+}
+
+/// Словари, разрешённые параметрами декодирования.
+pub(crate) fn dictionaries(options: &Options) -> Vec<Dictionary> {
+    match &options.wordlist {
+        Some(wordlist) => vec![Dictionary::Custom(Arc::clone(wordlist))],
+        None => options.languages().iter().copied().map(Dictionary::Language).collect(),
+    }
+}
+
+/// Словарь, в котором нашлось больше всего слов фразы.
+pub(crate) fn best_dictionary(words: &[&str], options: &Options) -> Dictionary {
+    dictionaries(options)
+        .into_iter()
+        .min_by_key(|dictionary| {
+            words
+                .iter()
+                .filter(|word| dictionary.find_word(word).is_none())
+                .count()
+        })
+        .expect("список словарей не пуст")
+}
+
+/// Кандидат с верной контрольной суммой.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// Полная мнемоника с подставленным словом.
+    pub mnemonic: String,
+    /// Энтропия кандидата.
+    pub entropy: Entropy,
+    /// Язык словаря; `None` для пользовательского словаря.
+    pub language: Option<Language>,
+}
+
+/// Причина, по которой фразу нельзя перебрать.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoverError {
+    /// Во фразе нет `?` или `_`.
+    NoPlaceholder,
+    /// Неизвестных слов больше [`MAX_PLACEHOLDERS`].
+    TooManyPlaceholders(usize),
+    /// Количество слов (вместе с пропущенным) не входит в [`VALID_WORD_COUNTS`].
+    BadWordCount(usize),
+    /// Известные слова не нашлись ни в одном допустимом словаре.
+    InvalidWords(Vec<String>),
+    /// Контрольная сумма фразы и так верна — переставлять нечего.
+    AlreadyValid,
+    /// Известных слов не меньше, чем слов во фразе, — дополнять нечего.
+    NothingToComplete { known: usize, word_count: usize },
+}
+
+impl fmt::Display for RecoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoverError::NoPlaceholder => {
+                write!(f, "Не указано неизвестное слово: замените его на '?' или '_'")
+            }
+            RecoverError::TooManyPlaceholders(count) => write!(
+                f,
+                "Неизвестных слов: {}; перебор поддерживает не больше {}",
+                count, MAX_PLACEHOLDERS
+            ),
+            RecoverError::BadWordCount(count) => write!(
+                f,
+                "Неверное количество слов: {} (BIP39 требует 12/15/18/21/24 слов)",
+                count
+            ),
+            RecoverError::InvalidWords(words) => write!(
+                f,
+                "Неверные слова (нет в словарях BIP39): {:?}",
+                words.iter().take(3).collect::<Vec<_>>()
+            ),
+            RecoverError::AlreadyValid => write!(f, "Контрольная сумма фразы уже верна"),
+            RecoverError::NothingToComplete { known, word_count } => write!(
+                f,
+                "Известно слов: {}, а во фразе должно быть {} — дополнять нечего",
+                known, word_count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecoverError {}
+
+/// Разбирает фразу с одним или двумя неизвестными словами.
+///
+/// Возвращает по шаблону на каждый словарь (из разрешённых в `options`),
+/// в котором есть все известные слова.
+pub fn parse_missing_word(mnemonic: &str, options: &Options) -> Result<Vec<MissingWord>, RecoverError> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+
+    let placeholders: Vec<usize> = words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| PLACEHOLDERS.contains(word))
+        .map(|(position, _)| position)
+        .collect();
+    if placeholders.is_empty() {
+        return Err(RecoverError::NoPlaceholder);
+    }
+    if placeholders.len() > MAX_PLACEHOLDERS {
+        return Err(RecoverError::TooManyPlaceholders(placeholders.len()));
+    }
+
+    if !VALID_WORD_COUNTS.contains(&words.len()) {
+        return Err(RecoverError::BadWordCount(words.len()));
+    }
+
+    let known = |dictionary: &Dictionary, word: &str| {
+        PLACEHOLDERS.contains(&word) || dictionary.find_word(word).is_some()
+    };
+
+    let templates: Vec<MissingWord> = dictionaries(options)
+        .into_iter()
+        .filter(|dictionary| words.iter().all(|word| known(dictionary, word)))
+        .map(|dictionary| MissingWord {
+            indices: words
+                .iter()
+                .map(|word| dictionary.find_word(word).unwrap_or(0))
+                .collect(),
+            positions: placeholders.clone(),
+            dictionary,
+        })
+        .collect();
+
+    if templates.is_empty() {
+        let invalid_words = match analyze_mnemonic(&words.join(" "), options) {
+            DecodeError::InvalidWords(invalid) => invalid
+                .into_iter()
+                .filter(|word| !PLACEHOLDERS.contains(&word.as_str()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        return Err(RecoverError::InvalidWords(invalid_words));
+    }
+
+    Ok(templates)
+}
+
+impl MissingWord {
+    /// Позиция первого неизвестного слова (с нуля).
+    pub fn position(&self) -> usize {
+        self.positions[0]
+    }
+
+    /// Позиции всех неизвестных слов (с нуля) по возрастанию.
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+
+    /// Язык словаря; `None` для пользовательского словаря.
+    pub fn language(&self) -> Option<Language> {
+        self.dictionary.language()
+    }
+
+    /// Подставляет слово с индексом `index` на место первого неизвестного;
+    /// `None`, если контрольная сумма не сходится.
+    pub fn candidate(&self, index: u16) -> Option<Candidate> {
+        self.candidate_with(&[index])
+    }
+
+    /// Подставляет слова с индексами `words` на места неизвестных по порядку;
+    /// `None`, если контрольная сумма не сходится.
+    pub fn candidate_with(&self, words: &[u16]) -> Option<Candidate> {
+        debug_assert!(words.len() <= self.positions.len());
+        debug_assert!(words.iter().all(|&index| (index as usize) < WORDLIST_SIZE));
+        let mut indices = self.indices.clone();
+        for (&position, &index) in self.positions.iter().zip(words) {
+            indices[position] = index;
+        }
+        self.dictionary.candidate(&indices)
+    }
+}
+
+/// Фраза, ставшая верной после перестановки двух слов.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Swap {
+    /// Позиция первого из переставленных слов (с нуля).
+    pub first: usize,
+    /// Позиция второго слова, `second > first`.
+    pub second: usize,
+    /// Фраза после перестановки.
+    pub candidate: Candidate,
+}
+
+/// Фраза, у которой все слова из словаря, но не сходится контрольная сумма:
+/// индексы слов в каждом словаре, где нашлись все слова.
+fn checksum_failure(mnemonic: &str, options: &Options) -> Result<Vec<(Dictionary, Vec<u16>)>, RecoverError> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    if !VALID_WORD_COUNTS.contains(&words.len()) {
+        return Err(RecoverError::BadWordCount(words.len()));
+    }
+
+    let dictionaries: Vec<(Dictionary, Vec<u16>)> = dictionaries(options)
+        .into_iter()
+        .filter_map(|dictionary| {
+            let indices = words
+                .iter()
+                .map(|word| dictionary.find_word(word))
+                .collect::<Option<Vec<u16>>>()?;
+            Some((dictionary, indices))
+        })
+        .collect();
+    if dictionaries.is_empty() {
+        return match analyze_mnemonic(mnemonic, options) {
+            DecodeError::InvalidWords(invalid) => Err(RecoverError::InvalidWords(invalid)),
+            _ => Err(RecoverError::InvalidWords(Vec::new())),
+        };
+    }
+    if dictionaries
+        .iter()
+        .any(|(_, indices)| checked_entropy(indices).is_some())
+    {
+        return Err(RecoverError::AlreadyValid);
+    }
+    Ok(dictionaries)
+}
+
+/// Шаблоны для подбора последнего слова фразы, не прошедшей только
+/// проверку контрольной суммы: последнее слово чаще всего переписывают
+/// с ошибкой, а верных вариантов для него немного (8 для 24 слов).
+pub fn last_word_templates(mnemonic: &str, options: &Options) -> Result<Vec<MissingWord>, RecoverError> {
+    let templates = checksum_failure(mnemonic, options)?
+        .into_iter()
+        .map(|(dictionary, indices)| MissingWord {
+            positions: vec![indices.len() - 1],
+            indices,
+            dictionary,
+        })
+        .collect();
+    Ok(templates)
+}
+
+/// Индексы слов в каждом словаре, где нашлись все слова; если таких словарей
+/// нет — ошибка со словами, которых нет ни в одном.
+fn known_indices(words: &[&str], options: &Options) -> Result<Vec<(Dictionary, Vec<u16>)>, RecoverError> {
+    let complete: Vec<(Dictionary, Vec<u16>)> = dictionaries(options)
+        .into_iter()
+        .filter_map(|dictionary| {
+            let indices = words
+                .iter()
+                .map(|word| dictionary.find_word(word))
+                .collect::<Option<Vec<u16>>>()?;
+            Some((dictionary, indices))
+        })
+        .collect();
+    if complete.is_empty() {
+        let all = dictionaries(options);
+        let invalid_words = words
+            .iter()
+            .filter(|word| all.iter().all(|dictionary| dictionary.find_word(word).is_none()))
+            .map(|word| word.to_string())
+            .collect();
+        return Err(RecoverError::InvalidWords(invalid_words));
+    }
+    Ok(complete)
+}
+
+/// Шаблоны для фразы, из которой выпало одно слово на неизвестном месте:
+/// по шаблону на каждую позицию вставки (от первой до последней) в каждом
+/// словаре, где нашлись все слова фразы.
+pub fn insertion_templates(mnemonic: &str, options: &Options) -> Result<Vec<MissingWord>, RecoverError> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    if !VALID_WORD_COUNTS.contains(&(words.len() + 1)) {
+        return Err(RecoverError::BadWordCount(words.len() + 1));
+    }
+
+    let complete = known_indices(&words, options)?;
+    let templates = complete
+        .into_iter()
+        .flat_map(|(dictionary, indices)| {
+            (0..=indices.len()).map(move |position| {
+                let mut indices = indices.clone();
+                indices.insert(position, 0);
+                MissingWord {
+                    indices,
+                    positions: vec![position],
+                    dictionary: dictionary.clone(),
+                }
+            })
+        })
+        .collect();
+    Ok(templates)
+}
+
+/// Продолжения начала фразы в одном словаре: все фразы заданной длины
+/// с верной контрольной суммой, начинающиеся с известных слов, в порядке
+/// индексов слов.
+///
+/// Известные слова задают старшие биты энтропии, остальные биты перебираются
+/// подряд, а последнее слово каждый раз дополняется контрольной суммой —
+/// поэтому неверных кандидатов итератор не порождает.
+#[derive(Debug, Clone)]
+pub struct Completions {
+    dictionary: Dictionary,
+    /// Энтропия следующего продолжения; `None`, когда перебор окончен.
+    entropy: Option<Vec<u8>>,
+    /// Число старших бит энтропии, заданных известными словами.
+    fixed_bits: usize,
+    /// Длина энтропии в битах.
+    entropy_bits: usize,
+}
+
+impl Completions {
+    /// Язык словаря; `None` для пользовательского словаря.
+    pub fn language(&self) -> Option<Language> {
+        self.dictionary.language()
+    }
+
+    /// Число бит энтропии, не заданных известными словами: продолжений 2^free_bits.
+    pub fn free_bits(&self) -> usize {
+        self.entropy_bits - self.fixed_bits
+    }
+
+    /// Общее число продолжений; `None`, если оно не меньше 2^128.
+    pub fn total(&self) -> Option<u128> {
+        1u128.checked_shl(self.free_bits() as u32)
+    }
+}
+
+impl Iterator for Completions {
+    type Item = Candidate;
+
+    fn next(&mut self) -> Option<Candidate> {
+        let entropy = self.entropy.take()?;
+        let candidate = self
+            .dictionary
+            .candidate(&entropy_indices(&entropy))
+            .expect("контрольная сумма вычислена по энтропии");
+
+        // Следующее значение свободных бит; переполнение в известные биты —
+        // конец перебора
+        let mut next = entropy;
+        for byte in next.iter_mut().rev() {
+            let (sum, carry) = byte.overflowing_add(1);
+            *byte = sum;
+            if !carry {
+                break;
+            }
+        }
+        let first = self.fixed_bits / 8;
+        let wrapped = next[first] & (0xFF >> (self.fixed_bits % 8)) == 0
+            && next[first + 1..].iter().all(|&byte| byte == 0);
+        if !wrapped {
+            self.entropy = Some(next);
+        }
+        Some(candidate)
+    }
+}
+
+/// Индексы слов фразы для энтропии: энтропия и контрольная сумма по 11 бит.
+fn entropy_indices(entropy: &[u8]) -> Vec<u16> {
+    let entropy_bits = entropy.len() * 8;
+    let word_count = (entropy_bits + entropy_bits / 32) / 11;
+    let mut bytes = entropy.to_vec();
+    bytes.push(sha256::Hash::hash(entropy).to_byte_array()[0]);
+    (0..word_count)
+        .map(|word| {
+            (0..11).fold(0u16, |index, j| {
+                let bit = word * 11 + j;
+                index << 1 | u16::from(bytes[bit / 8] >> (7 - bit % 8) & 1)
+            })
+        })
+        .collect()
+}
+
+/// Продолжения начала фразы `prefix` до `word_count` слов: по итератору
+/// на каждый словарь, в котором есть все известные слова.
+pub fn completions(prefix: &str, word_count: usize, options: &Options) -> Result<Vec<Completions>, RecoverError> {
+    let prefix = &*normalize_mnemonic(prefix);
+    let words: Vec<&str> = prefix.split_whitespace().collect();
+    if !VALID_WORD_COUNTS.contains(&word_count) {
+        return Err(RecoverError::BadWordCount(word_count));
+    }
+    if words.len() >= word_count {
+        return Err(RecoverError::NothingToComplete {
+            known: words.len(),
+            word_count,
+        });
+    }
+
+    let entropy_bytes = word_count * 11 * 32 / 33 / 8;
+    let completions = known_indices(&words, options)?
+        .into_iter()
+        .map(|(dictionary, indices)| {
+            let mut entropy = indices_to_bytes(&indices);
+            entropy.resize(entropy_bytes, 0);
+            Completions {
+                dictionary,
+                entropy: Some(entropy),
+                fixed_bits: indices.len() * 11,
+                entropy_bits: entropy_bytes * 8,
+            }
+        })
+        .collect();
+    Ok(completions)
+}
+
+/// Перебирает перестановки двух слов фразы, не прошедшей проверку
+/// контрольной суммы: только соседних или, с `all_pairs`, любых.
+/// Возвращает перестановки, после которых контрольная сумма сходится.
+pub fn try_swaps(mnemonic: &str, options: &Options, all_pairs: bool) -> Result<Vec<Swap>, RecoverError> {
+    let dictionaries = checksum_failure(mnemonic, options)?;
+
+    let mut swaps = Vec::new();
+    for (dictionary, indices) in dictionaries {
+        for first in 0..indices.len() {
+            let last = if all_pairs { indices.len() } else { (first + 2).min(indices.len()) };
+            for second in first + 1..last {
+                if indices[first] == indices[second] {
+                    continue;
+                }
+                let mut swapped = indices.clone();
+                swapped.swap(first, second);
+                if let Some(candidate) = dictionary.candidate(&swapped) {
+                    swaps.push(Swap {
+                        first,
+                        second,
+                        candidate,
+                    });
+                }
+            }
+        }
+    }
+    Ok(swaps)
+}
+
+/// Замена слова с опечаткой.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordCorrection {
+    /// Позиция слова во фразе (с нуля).
+    pub position: usize,
+    /// Слово из исходной фразы.
+    pub original: String,
+    /// Слово словаря, которым оно заменено.
+    pub replacement: String,
+}
+
+impl fmt::Display for WordCorrection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} → {}", self.original, self.replacement)
+    }
+}
+
+/// Исправленная фраза.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Autocorrected {
+    /// Фраза после замены слов.
+    pub mnemonic: String,
+    /// Применённые замены.
+    pub corrections: Vec<WordCorrection>,
+}
+
+/// Исправляет опечатки: каждое слово, которого нет в словаре, заменяется
+/// единственным ближайшим словом (не дальше [`MAX_TYPO_DISTANCE`]).
+///
+/// Словарь выбирается тот, в котором нашлось больше всего слов фразы.
+/// `None`, если исправлять нечего, у какого-то слова нет единственного
+/// ближайшего соседа или исправленная фраза не проходит проверку
+/// контрольной суммы.
+pub fn autocorrect(mnemonic: &str, options: &Options) -> Option<Autocorrected> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    if !VALID_WORD_COUNTS.contains(&words.len()) {
+        return None;
+    }
+
+    let dictionary = best_dictionary(&words, options);
+
+    let mut indices = Vec::with_capacity(words.len());
+    let mut corrections = Vec::new();
+    for (position, word) in words.iter().enumerate() {
+        let index = match dictionary.find_word(word) {
+            Some(index) => index,
+            None => {
+                let index = closest_word(&dictionary, word)?;
+                corrections.push(WordCorrection {
+                    position,
+                    original: word.to_string(),
+                    replacement: dictionary.word(index).to_string(),
+                });
+                index
+            }
+        };
+        indices.push(index);
+    }
+
+    if corrections.is_empty() {
+        return None;
+    }
+    checked_entropy(&indices)?;
+
+    let mnemonic = indices
+        .iter()
+        .map(|&index| dictionary.word(index))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(Autocorrected {
+        mnemonic,
+        corrections,
+    })
+}
+
+/// Наименьшая длина сокращения, которое раскрывает [`expand_prefixes`].
+pub const MIN_PREFIX_LENGTH: usize = 3;
+
+/// Раскрывает сокращённые слова: слово не из словаря, с которого начинается
+/// ровно одно слово словаря, заменяется этим словом (`aban` → `abandon`).
+/// В английском словаре слова различаются уже по первым четырём буквам,
+/// поэтому резервные копии часто записывают только их.
+///
+/// Словарь выбирается тот, в котором нашлось больше всего слов фразы.
+/// Контрольная сумма не проверяется. `None`, если раскрывать нечего.
+pub fn expand_prefixes(mnemonic: &str, options: &Options) -> Option<Autocorrected> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    let dictionary = best_dictionary(&words, options);
+
+    let mut corrections = Vec::new();
+    let expanded: Vec<&str> = words
+        .iter()
+        .enumerate()
+        .map(|(position, &word)| {
+            if word.chars().count() < MIN_PREFIX_LENGTH || dictionary.find_word(word).is_some() {
+                return word;
+            }
+            let mut matches = (0..WORDLIST_SIZE as u16)
+                .map(|index| dictionary.word(index))
+                .filter(|candidate| candidate.starts_with(word));
+            match (matches.next(), matches.next()) {
+                (Some(replacement), None) => {
+                    corrections.push(WordCorrection {
+                        position,
+                        original: word.to_string(),
+                        replacement: replacement.to_string(),
+                    });
+                    replacement
+                }
+                _ => word,
+            }
+        })
+        .collect();
+
+    (!corrections.is_empty()).then(|| Autocorrected {
+        mnemonic: expanded.join(" "),
+        corrections,
+    })
+}
+
+/// Индекс единственного ближайшего к `word` слова словаря.
+fn closest_word(dictionary: &Dictionary, word: &str) -> Option<u16> {
+    let mut best_distance = MAX_TYPO_DISTANCE + 1;
+    let mut best = None;
+    for index in 0..WORDLIST_SIZE as u16 {
+        let distance = levenshtein(word, dictionary.word(index));
+        if distance < best_distance {
+            best_distance = distance;
+            best = Some(index);
+        } else if distance == best_distance {
+            best = None;
+        }
+    }
+    best
+}
+
+/// Сколько кандидатов [`suggest_words`] предлагает для слова по умолчанию.
+pub const SUGGESTION_LIMIT: usize = 5;
+
+/// Наибольшая цена [`typo_cost`] кандидата: три обычные правки.
+const MAX_SUGGESTION_COST: usize = 6;
+
+/// Кандидаты для слова не из словаря.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// Позиция слова во фразе (с нуля).
+    pub position: usize,
+    /// Слово из фразы.
+    pub word: String,
+    /// Слова словаря от самого вероятного; может быть пустым.
+    pub candidates: Vec<String>,
+}
+
+/// Кандидаты для каждого слова фразы, которого нет в словаре: не больше
+/// `limit` слов, упорядоченных по [`typo_cost`]. Словарь — тот, в котором
+/// нашлось больше всего слов фразы.
+pub fn suggest_words(mnemonic: &str, options: &Options, limit: usize) -> Vec<Suggestion> {
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    let dictionary = best_dictionary(&words, options);
+    words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| dictionary.find_word(word).is_none())
+        .map(|(position, word)| {
+            let mut ranked: Vec<(usize, u16)> = (0..WORDLIST_SIZE as u16)
+                .map(|index| (typo_cost(word, dictionary.word(index)), index))
+                .filter(|&(cost, _)| cost <= MAX_SUGGESTION_COST)
+                .collect();
+            ranked.sort_unstable();
+            Suggestion {
+                position,
+                word: word.to_string(),
+                candidates: ranked
+                    .iter()
+                    .take(limit)
+                    .map(|&(_, index)| dictionary.word(index).to_string())
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Цена опечатки при наборе `typed` вместо `word` в половинах правки:
+/// вставка, удаление и замена стоят 2, а замена соседней клавишей QWERTY
+/// и перестановка двух соседних букв — 1, как самые частые ошибки набора.
+pub fn typo_cost(typed: &str, word: &str) -> usize {
+    let a: Vec<char> = typed.chars().collect();
+    let b: Vec<char> = word.chars().collect();
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in table.iter_mut().enumerate() {
+        row[0] = 2 * i;
+    }
+    for (j, cell) in table[0].iter_mut().enumerate() {
+        *cell = 2 * j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = match (a[i - 1], b[j - 1]) {
+                (x, y) if x == y => 0,
+                (x, y) if adjacent_keys(x, y) => 1,
+                _ => 2,
+            };
+            let mut cost = (table[i - 1][j - 1] + substitution)
+                .min(table[i - 1][j] + 2)
+                .min(table[i][j - 1] + 2);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cost = cost.min(table[i - 2][j - 2] + 1);
+            }
+            table[i][j] = cost;
+        }
+    }
+    table[a.len()][b.len()]
+}
+
+/// Соседние клавиши латинской раскладки QWERTY (в ряду и в соседних рядах).
+fn adjacent_keys(a: char, b: char) -> bool {
+    const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+    let position = |c: char| {
+        ROWS.iter()
+            .enumerate()
+            .find_map(|(row, keys)| keys.find(c).map(|column| (row as isize, column as isize)))
+    };
+    match (position(a), position(b)) {
+        // Ряды сдвинуты на полклавиши: соседи снизу — та же и левая колонка
+        (Some((ra, ca)), Some((rb, cb))) => match ra - rb {
+            0 => (ca - cb).abs() == 1,
+            1 => cb == ca || cb == ca + 1,
+            -1 => ca == cb || ca == cb + 1,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Расстояние Левенштейна между строками (по символам).
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,157 @@
+//! Очистка фраз, скопированных из Word, Заметок и мессенджеров: BOM,
+//! невидимые символы, «умные» кавычки, возвраты каретки, знаки препинания
+//! после слов и заглавные буквы. Без очистки такие строки не проходят
+//! декодирование.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Этап очистки.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cleanup {
+    /// Метка порядка байтов U+FEFF (в начале файла и внутри строки).
+    Bom,
+    /// Символы нулевой ширины: U+200B–U+200D, U+2060, мягкий перенос U+00AD.
+    ZeroWidth,
+    /// Кавычки, прямые и типографские: `" ' “ ” „ ‘ ’ « »`.
+    Quotes,
+    /// Возврат каретки `\r` (строки CRLF и CR) заменяется пробелом.
+    LineBreaks,
+    /// Знаки препинания в конце слов: `abandon,` `about.`
+    Punctuation,
+    /// Заглавные буквы приводятся к строчным.
+    Lowercase,
+}
+
+impl Cleanup {
+    /// Все этапы в порядке применения.
+    pub const ALL: [Cleanup; 6] = [
+        Cleanup::Bom,
+        Cleanup::ZeroWidth,
+        Cleanup::Quotes,
+        Cleanup::LineBreaks,
+        Cleanup::Punctuation,
+        Cleanup::Lowercase,
+    ];
+
+    /// Имя этапа в `--sanitize`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Cleanup::Bom => "bom",
+            Cleanup::ZeroWidth => "zero-width",
+            Cleanup::Quotes => "quotes",
+            Cleanup::LineBreaks => "crlf",
+            Cleanup::Punctuation => "punctuation",
+            Cleanup::Lowercase => "lowercase",
+        }
+    }
+
+    fn apply(self, text: &str) -> String {
+        match self {
+            Cleanup::Bom => text.replace('\u{feff}', ""),
+            Cleanup::ZeroWidth => text
+                .chars()
+                .filter(|c| !matches!(c, '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{00ad}'))
+                .collect(),
+            Cleanup::Quotes => text
+                .chars()
+                .filter(|c| !matches!(c, '"' | '\'' | '“' | '”' | '„' | '‘' | '’' | '«' | '»'))
+                .collect(),
+            Cleanup::LineBreaks => text.replace('\r', " "),
+            Cleanup::Punctuation => text
+                .split_whitespace()
+                .map(|word| {
+                    word.trim_end_matches(|c: char| {
+                        c.is_ascii_punctuation() || matches!(c, '。' | '，' | '、')
+                    })
+                })
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+            Cleanup::Lowercase => text.to_lowercase(),
+        }
+    }
+}
+
+impl fmt::Display for Cleanup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Cleanup::Bom => "удалён BOM",
+            Cleanup::ZeroWidth => "удалены символы нулевой ширины",
+            Cleanup::Quotes => "удалены кавычки",
+            Cleanup::LineBreaks => "заменены возвраты каретки",
+            Cleanup::Punctuation => "удалены знаки препинания",
+            Cleanup::Lowercase => "буквы приведены к строчным",
+        })
+    }
+}
+
+/// Набор этапов очистки: `all` или их имена через запятую
+/// (`bom,zero-width,quotes,crlf,punctuation,lowercase`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sanitize(Vec<Cleanup>);
+
+impl Sanitize {
+    /// Все этапы.
+    pub fn all() -> Self {
+        Sanitize(Cleanup::ALL.to_vec())
+    }
+
+    /// Этапы в порядке применения.
+    pub fn steps(&self) -> &[Cleanup] {
+        &self.0
+    }
+}
+
+impl FromStr for Sanitize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(Sanitize::all());
+        }
+        let mut steps = Vec::new();
+        for name in s.split(',').map(str::trim) {
+            let step = Cleanup::ALL
+                .into_iter()
+                .find(|step| step.name().eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    format!(
+                        "неизвестный этап очистки '{}'; допустимые: all, bom, zero-width, quotes, \
+                         crlf, punctuation, lowercase",
+                        name
+                    )
+                })?;
+            if !steps.contains(&step) {
+                steps.push(step);
+            }
+        }
+        // Этапы применяются в фиксированном порядке, независимо от порядка в списке
+        steps.sort_by_key(|step| Cleanup::ALL.iter().position(|other| other == step));
+        Ok(Sanitize(steps))
+    }
+}
+
+/// Очищенная строка и этапы, которые что-то изменили.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sanitized {
+    pub text: String,
+    pub applied: Vec<Cleanup>,
+}
+
+/// Применяет к строке этапы очистки `sanitize` по порядку.
+pub fn sanitize_mnemonic(text: &str, sanitize: &Sanitize) -> Sanitized {
+    let mut text = text.to_string();
+    let mut applied = Vec::new();
+    for &step in sanitize.steps() {
+        let cleaned = step.apply(&text);
+        if cleaned != text {
+            applied.push(step);
+            text = cleaned;
+        }
+    }
+    Sanitized {
+        text: text.trim().to_string(),
+        applied,
+    }
+}
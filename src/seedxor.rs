@@ -0,0 +1,103 @@
+//! SeedXOR, совместимый с Coldcard: энтропия делится на N долей одинаковой
+//! длины, XOR которых даёт исходную. Каждая доля — обычная мнемоника BIP39
+//! со своей контрольной суммой, и по отдельности ничего не раскрывает.
+//! В отличие от SLIP-39, для восстановления нужны все доли.
+
+use crate::VALID_WORD_COUNTS;
+use std::fmt;
+
+/// Наибольшее число долей.
+pub const MAX_SEEDXOR_PARTS: usize = 16;
+
+/// Ошибка разделения или сборки SeedXOR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedXorError {
+    /// Число долей вне 2..=[`MAX_SEEDXOR_PARTS`].
+    BadPartCount(usize),
+    /// Длина энтропии (в битах) не из 128/160/192/224/256.
+    BadLength(usize),
+    /// Доли разной длины (в битах): первая и отличающаяся от неё.
+    LengthMismatch { expected: usize, actual: usize },
+    /// Системный генератор случайных чисел недоступен.
+    Random(String),
+}
+
+impl fmt::Display for SeedXorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeedXorError::BadPartCount(count) => write!(
+                f,
+                "Неверное число долей SeedXOR: {} (допустимо 2–{})",
+                count, MAX_SEEDXOR_PARTS
+            ),
+            SeedXorError::BadLength(bits) => write!(
+                f,
+                "Неверная длина энтропии: {} бит (BIP39 требует 128/160/192/224/256 бит)",
+                bits
+            ),
+            SeedXorError::LengthMismatch { expected, actual } => write!(
+                f,
+                "Доли SeedXOR разной длины: {} и {} бит",
+                expected, actual
+            ),
+            SeedXorError::Random(e) => write!(f, "Генератор случайных чисел недоступен: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SeedXorError {}
+
+fn check_length(entropy: &[u8]) -> Result<(), SeedXorError> {
+    let bits = entropy.len() * 8;
+    if VALID_WORD_COUNTS.iter().any(|words| words * 32 / 3 == bits) {
+        Ok(())
+    } else {
+        Err(SeedXorError::BadLength(bits))
+    }
+}
+
+/// Делит энтропию на `parts` долей: все, кроме последней, случайные,
+/// последняя — XOR энтропии с ними.
+pub fn seedxor_split(entropy: &[u8], parts: usize) -> Result<Vec<Vec<u8>>, SeedXorError> {
+    if !(2..=MAX_SEEDXOR_PARTS).contains(&parts) {
+        return Err(SeedXorError::BadPartCount(parts));
+    }
+    check_length(entropy)?;
+
+    let mut last = entropy.to_vec();
+    let mut shares = Vec::with_capacity(parts);
+    for _ in 1..parts {
+        let mut share = vec![0u8; entropy.len()];
+        getrandom::getrandom(&mut share).map_err(|e| SeedXorError::Random(e.to_string()))?;
+        xor_into(&mut last, &share);
+        shares.push(share);
+    }
+    shares.push(last);
+    Ok(shares)
+}
+
+/// Собирает энтропию из всех долей: XOR их энтропий.
+pub fn seedxor_combine(shares: &[&[u8]]) -> Result<Vec<u8>, SeedXorError> {
+    if !(2..=MAX_SEEDXOR_PARTS).contains(&shares.len()) {
+        return Err(SeedXorError::BadPartCount(shares.len()));
+    }
+    check_length(shares[0])?;
+
+    let mut entropy = shares[0].to_vec();
+    for share in &shares[1..] {
+        if share.len() != entropy.len() {
+            return Err(SeedXorError::LengthMismatch {
+                expected: entropy.len() * 8,
+                actual: share.len() * 8,
+            });
+        }
+        xor_into(&mut entropy, share);
+    }
+    Ok(entropy)
+}
+
+fn xor_into(target: &mut [u8], share: &[u8]) {
+    for (byte, other) in target.iter_mut().zip(share) {
+        *byte ^= other;
+    }
+}
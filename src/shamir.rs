@@ -0,0 +1,240 @@
+//! Простая схема Шамира над байтами энтропии — пороговая резервная копия
+//! без формата слов SLIP-39. Доля записывается в hex: порог, номер доли
+//! и значение многочлена. К секрету перед разделением дописываются
+//! 4 байта SHA-256 от него, поэтому сборка из долей разных наборов или
+//! из повреждённых долей обнаруживается.
+
+use crate::gf256::interpolate;
+use crate::VALID_WORD_COUNTS;
+use bitcoin_hashes::{sha256, Hash};
+use std::fmt;
+use std::str::FromStr;
+
+/// Наибольшее число долей: номера долей — ненулевые байты.
+pub const MAX_SHAMIR_SHARES: usize = 255;
+
+/// Длина контрольной суммы секрета в долях (байт).
+const DIGEST_LENGTH: usize = 4;
+
+/// Доля схемы Шамира.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShamirShare {
+    /// Сколько долей нужно для сборки.
+    pub threshold: u8,
+    /// Номер доли (точка многочлена), с единицы.
+    pub index: u8,
+    /// Значение многочлена: байты секрета и контрольной суммы.
+    pub value: Vec<u8>,
+}
+
+impl ShamirShare {
+    /// Доля в hex: порог, номер и значение.
+    pub fn to_hex(&self) -> String {
+        let mut bytes = vec![self.threshold, self.index];
+        bytes.extend_from_slice(&self.value);
+        hex::encode(bytes)
+    }
+}
+
+impl FromStr for ShamirShare {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s.trim()).map_err(|e| ShamirError::BadShare(e.to_string()))?;
+        match bytes.as_slice() {
+            [threshold, index, value @ ..] if *threshold > 0 && *index > 0 && !value.is_empty() => {
+                Ok(ShamirShare {
+                    threshold: *threshold,
+                    index: *index,
+                    value: value.to_vec(),
+                })
+            }
+            _ => Err(ShamirError::BadShare("слишком короткая доля или нулевой порог/номер".into())),
+        }
+    }
+}
+
+/// Ошибка разделения или сборки секрета.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShamirError {
+    /// Порог и число долей: нужно 2 <= порог <= долей <= [`MAX_SHAMIR_SHARES`].
+    BadParameters { threshold: usize, shares: usize },
+    /// Длина энтропии (в битах) не из 128/160/192/224/256.
+    BadLength(usize),
+    /// Доля не разбирается: не hex или слишком короткая.
+    BadShare(String),
+    /// Долей меньше порога.
+    NotEnoughShares { threshold: u8, count: usize },
+    /// У долей разный порог или разная длина — они из разных наборов.
+    Inconsistent,
+    /// Один номер доли встречается дважды.
+    DuplicateIndex(u8),
+    /// Контрольная сумма собранного секрета не совпала.
+    DigestMismatch,
+    /// Системный генератор случайных чисел недоступен.
+    Random(String),
+}
+
+impl fmt::Display for ShamirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShamirError::BadParameters { threshold, shares } => write!(
+                f,
+                "Неверные параметры: порог {} из {} долей (нужно 2 ≤ порог ≤ долей ≤ {})",
+                threshold, shares, MAX_SHAMIR_SHARES
+            ),
+            ShamirError::BadLength(bits) => write!(
+                f,
+                "Неверная длина энтропии: {} бит (BIP39 требует 128/160/192/224/256 бит)",
+                bits
+            ),
+            ShamirError::BadShare(e) => write!(f, "Неверная доля: {}", e),
+            ShamirError::NotEnoughShares { threshold, count } => {
+                write!(f, "Недостаточно долей: {} из {}", count, threshold)
+            }
+            ShamirError::Inconsistent => write!(f, "Доли из разных наборов: различаются порог или длина"),
+            ShamirError::DuplicateIndex(index) => write!(f, "Доля {} указана дважды", index),
+            ShamirError::DigestMismatch => write!(
+                f,
+                "Контрольная сумма секрета не совпала: доли повреждены или из разных наборов"
+            ),
+            ShamirError::Random(e) => write!(f, "Генератор случайных чисел недоступен: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ShamirError {}
+
+fn digest(secret: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut digest = [0u8; DIGEST_LENGTH];
+    digest.copy_from_slice(&sha256::Hash::hash(secret).to_byte_array()[..DIGEST_LENGTH]);
+    digest
+}
+
+/// Делит энтропию на `count` долей, любые `threshold` из которых
+/// восстанавливают её.
+pub fn shamir_split(entropy: &[u8], threshold: usize, count: usize) -> Result<Vec<ShamirShare>, ShamirError> {
+    if threshold < 2 || threshold > count || count > MAX_SHAMIR_SHARES {
+        return Err(ShamirError::BadParameters { threshold, shares: count });
+    }
+    let bits = entropy.len() * 8;
+    if !VALID_WORD_COUNTS.iter().any(|words| words * 32 / 3 == bits) {
+        return Err(ShamirError::BadLength(bits));
+    }
+
+    // Многочлен степени threshold - 1: значение в нуле — секрет
+    // с контрольной суммой, в точках 1..threshold - 1 — случайные байты.
+    let secret = [entropy, &digest(entropy)[..]].concat();
+    let mut random = vec![0u8; secret.len() * (threshold - 1)];
+    getrandom::getrandom(&mut random).map_err(|e| ShamirError::Random(e.to_string()))?;
+    let mut base: Vec<(u8, &[u8])> = vec![(0, &secret)];
+    base.extend(random.chunks(secret.len()).zip(1..).map(|(value, index)| (index, value)));
+
+    Ok((1..=count as u8)
+        .map(|index| ShamirShare {
+            threshold: threshold as u8,
+            index,
+            value: interpolate(&base, index),
+        })
+        .collect())
+}
+
+/// Собирает энтропию из долей (не меньше порога) и проверяет её
+/// контрольную сумму.
+pub fn shamir_combine(shares: &[ShamirShare]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares { threshold: 2, count: 0 })?;
+    if shares
+        .iter()
+        .any(|share| share.threshold != first.threshold || share.value.len() != first.value.len())
+    {
+        return Err(ShamirError::Inconsistent);
+    }
+    if shares.len() < first.threshold as usize {
+        return Err(ShamirError::NotEnoughShares {
+            threshold: first.threshold,
+            count: shares.len(),
+        });
+    }
+    for (position, share) in shares.iter().enumerate() {
+        if shares[..position].iter().any(|other| other.index == share.index) {
+            return Err(ShamirError::DuplicateIndex(share.index));
+        }
+    }
+    if first.value.len() <= DIGEST_LENGTH {
+        return Err(ShamirError::BadShare("нет байт секрета".into()));
+    }
+
+    let points: Vec<(u8, &[u8])> = shares
+        .iter()
+        .take(first.threshold as usize)
+        .map(|share| (share.index, share.value.as_slice()))
+        .collect();
+    let mut secret = interpolate(&points, 0);
+    let checksum = secret.split_off(secret.len() - DIGEST_LENGTH);
+    if checksum != digest(&secret) {
+        return Err(ShamirError::DigestMismatch);
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTROPY: [u8; 16] = [
+        0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80,
+    ];
+
+    #[test]
+    fn any_threshold_subset_recombines() {
+        let shares = shamir_split(&ENTROPY, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        for a in 0..5 {
+            for b in a + 1..5 {
+                for c in b + 1..5 {
+                    let subset = [shares[c].clone(), shares[a].clone(), shares[b].clone()];
+                    assert_eq!(shamir_combine(&subset).unwrap(), ENTROPY, "доли {} {} {}", a, b, c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_through_hex() {
+        let shares = shamir_split(&[0xab; 32], 2, 2).unwrap();
+        let parsed: Vec<ShamirShare> = shares.iter().map(|share| share.to_hex().parse().unwrap()).collect();
+        assert_eq!(shamir_combine(&parsed).unwrap(), vec![0xab; 32]);
+    }
+
+    #[test]
+    fn below_threshold_is_rejected() {
+        let shares = shamir_split(&ENTROPY, 3, 5).unwrap();
+        assert_eq!(
+            shamir_combine(&shares[..2]),
+            Err(ShamirError::NotEnoughShares { threshold: 3, count: 2 })
+        );
+    }
+
+    #[test]
+    fn duplicate_index_is_rejected() {
+        let shares = shamir_split(&ENTROPY, 2, 3).unwrap();
+        let duplicated = [shares[1].clone(), shares[1].clone()];
+        assert_eq!(shamir_combine(&duplicated), Err(ShamirError::DuplicateIndex(2)));
+    }
+
+    #[test]
+    fn tampered_share_fails_digest() {
+        let mut shares = shamir_split(&ENTROPY, 2, 3).unwrap();
+        shares[0].value[0] ^= 0x01;
+        assert_eq!(shamir_combine(&shares[..2]), Err(ShamirError::DigestMismatch));
+    }
+
+    #[test]
+    fn bad_parameters_are_rejected() {
+        assert_eq!(
+            shamir_split(&ENTROPY, 1, 3),
+            Err(ShamirError::BadParameters { threshold: 1, shares: 3 })
+        );
+        assert_eq!(shamir_split(&[0; 15], 2, 3), Err(ShamirError::BadLength(120)));
+    }
+}
@@ -0,0 +1,677 @@
+//! SLIP-39: разделение мастер-секрета на доли Шамира и его восстановление.
+//!
+//! Доли объединяются в два уровня: сначала из долей участников
+//! восстанавливается секрет каждой группы, затем из секретов групп —
+//! зашифрованный мастер-секрет, который расшифровывается паролем.
+//! Разделение выполняет те же шаги в обратном порядке.
+
+mod wordlist;
+
+pub use wordlist::WORDLIST;
+
+use crate::gf256::interpolate;
+use bitcoin_hashes::hmac::{Hmac, HmacEngine};
+use bitcoin_hashes::{sha256, Hash, HashEngine};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Минимальная длина мастер-секрета в битах.
+const MIN_STRENGTH_BITS: usize = 128;
+/// Служебные слова: идентификатор (2), параметры групп (2), чексум (3).
+const METADATA_WORDS: usize = 7;
+const CHECKSUM_WORDS: usize = 3;
+const MAX_SHARE_COUNT: u8 = 16;
+const MIN_MNEMONIC_WORDS: usize = METADATA_WORDS + MIN_STRENGTH_BITS.div_ceil(10);
+const SECRET_INDEX: u8 = 255;
+const DIGEST_INDEX: u8 = 254;
+const DIGEST_LENGTH: usize = 4;
+const ROUND_COUNT: u8 = 4;
+const BASE_ITERATION_COUNT: u32 = 10000;
+const CUSTOMIZATION: &[u8] = b"shamir";
+const CUSTOMIZATION_EXTENDABLE: &[u8] = b"shamir_extendable";
+
+/// Ошибка разбора или объединения долей SLIP-39.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Slip39Error {
+    /// Слово не из словаря SLIP-39.
+    UnknownWord(String),
+    /// Слишком мало слов в доле.
+    TooShort(usize),
+    /// Неверное выравнивание значения доли.
+    InvalidPadding,
+    /// Контрольная сумма RS1024 не сходится.
+    InvalidChecksum,
+    /// Порог групп больше количества групп.
+    InvalidGroupThreshold,
+    /// Доли не передано ни одной.
+    NoShares,
+    /// Доли относятся к разным наборам (идентификатор или параметры не совпадают).
+    MismatchedShares,
+    /// Одна и та же доля участника передана с разными значениями.
+    ConflictingShares { group: u8, member: u8 },
+    /// Недостаточно полных групп; `incomplete` — (группа, есть долей, нужно долей).
+    NotEnoughGroups {
+        have: usize,
+        need: u8,
+        incomplete: Vec<(u8, usize, u8)>,
+    },
+    /// Дайджест восстановленного секрета не сходится: доли повреждены или из разных наборов.
+    DigestMismatch,
+    /// Длина секрета для разделения: нужно не меньше 16 байт и чётное число байт.
+    InvalidSecretLength(usize),
+    /// Недопустимые порог и количество долей (группы или участников).
+    InvalidThreshold { threshold: u8, count: u8 },
+}
+
+impl fmt::Display for Slip39Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Slip39Error::UnknownWord(word) => write!(f, "Слово не из словаря SLIP-39: {}", word),
+            Slip39Error::TooShort(count) => write!(
+                f,
+                "Слишком короткая доля: {} слов (минимум {})",
+                count, MIN_MNEMONIC_WORDS
+            ),
+            Slip39Error::InvalidPadding => write!(f, "Неверное выравнивание значения доли"),
+            Slip39Error::InvalidChecksum => write!(f, "Неверная контрольная сумма доли SLIP-39"),
+            Slip39Error::InvalidGroupThreshold => {
+                write!(f, "Порог групп больше количества групп")
+            }
+            Slip39Error::NoShares => write!(f, "Не передано ни одной доли"),
+            Slip39Error::MismatchedShares => {
+                write!(f, "Доли относятся к разным наборам (идентификатор или параметры не совпадают)")
+            }
+            Slip39Error::ConflictingShares { group, member } => write!(
+                f,
+                "Доля участника {} группы {} передана с разными значениями",
+                member + 1,
+                group + 1
+            ),
+            Slip39Error::NotEnoughGroups {
+                have,
+                need,
+                incomplete,
+            } => {
+                write!(f, "Недостаточно групп: собрано {} из {}", have, need)?;
+                for (group, count, threshold) in incomplete {
+                    write!(f, "; группа {}: {} из {} долей", group + 1, count, threshold)?;
+                }
+                Ok(())
+            }
+            Slip39Error::DigestMismatch => write!(
+                f,
+                "Дайджест секрета не сходится: доли повреждены или из разных наборов"
+            ),
+            Slip39Error::InvalidSecretLength(len) => write!(
+                f,
+                "Неверная длина секрета: {} байт (нужно чётное число, не меньше {})",
+                len,
+                MIN_STRENGTH_BITS / 8
+            ),
+            Slip39Error::InvalidThreshold { threshold, count } => write!(
+                f,
+                "Недопустимая схема {} из {}: порог от 1 до количества долей (не больше {}), \
+                 порог 1 допустим только для единственной доли",
+                threshold, count, MAX_SHARE_COUNT
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Slip39Error {}
+
+/// Разобранная доля SLIP-39.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    /// Случайный идентификатор набора долей (15 бит).
+    pub identifier: u16,
+    /// Флаг расширяемой резервной копии.
+    pub extendable: bool,
+    /// Показатель числа итераций PBKDF2: 10000 << e.
+    pub iteration_exponent: u8,
+    /// Индекс группы (с нуля).
+    pub group_index: u8,
+    /// Сколько групп нужно для восстановления.
+    pub group_threshold: u8,
+    /// Общее количество групп.
+    pub group_count: u8,
+    /// Индекс участника в группе (с нуля).
+    pub member_index: u8,
+    /// Сколько долей группы нужно для восстановления её секрета.
+    pub member_threshold: u8,
+    /// Значение доли.
+    pub value: Vec<u8>,
+}
+
+impl Share {
+    /// Разбирает мнемонику доли, проверяя контрольную сумму RS1024.
+    pub fn parse(mnemonic: &str) -> Result<Share, Slip39Error> {
+        let indices = mnemonic
+            .split_whitespace()
+            .map(|word| {
+                wordlist::find_word(&word.to_lowercase())
+                    .ok_or_else(|| Slip39Error::UnknownWord(word.to_string()))
+            })
+            .collect::<Result<Vec<u16>, _>>()?;
+
+        if indices.len() < MIN_MNEMONIC_WORDS {
+            return Err(Slip39Error::TooShort(indices.len()));
+        }
+
+        let value_words = &indices[4..indices.len() - CHECKSUM_WORDS];
+        let padding_bits = value_words.len() * 10 % 16;
+        if padding_bits > 8 {
+            return Err(Slip39Error::InvalidPadding);
+        }
+
+        let id_exp = (indices[0] as u32) << 10 | indices[1] as u32;
+        let extendable = (id_exp >> 4) & 1 == 1;
+        if rs1024_polymod(customization(extendable), &indices) != 1 {
+            return Err(Slip39Error::InvalidChecksum);
+        }
+
+        let params = (indices[2] as u32) << 10 | indices[3] as u32;
+        let group_threshold = ((params >> 12) & 0xf) as u8 + 1;
+        let group_count = ((params >> 8) & 0xf) as u8 + 1;
+        if group_threshold > group_count {
+            return Err(Slip39Error::InvalidGroupThreshold);
+        }
+
+        Ok(Share {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 0xf) as u8,
+            group_index: (params >> 16) as u8,
+            group_threshold,
+            group_count,
+            member_index: ((params >> 4) & 0xf) as u8,
+            member_threshold: (params & 0xf) as u8 + 1,
+            value: words_to_value(value_words, padding_bits)?,
+        })
+    }
+
+    /// Кодирует долю в мнемонику с контрольной суммой RS1024.
+    pub fn to_mnemonic(&self) -> String {
+        let id_exp = (self.identifier as u32) << 5
+            | (self.extendable as u32) << 4
+            | self.iteration_exponent as u32;
+        let params = (self.group_index as u32) << 16
+            | (self.group_threshold as u32 - 1) << 12
+            | (self.group_count as u32 - 1) << 8
+            | (self.member_index as u32) << 4
+            | (self.member_threshold as u32 - 1);
+
+        let mut indices = vec![
+            (id_exp >> 10) as u16,
+            (id_exp & 0x3ff) as u16,
+            (params >> 10) as u16,
+            (params & 0x3ff) as u16,
+        ];
+        indices.extend(value_to_words(&self.value));
+
+        let mut padded = indices.clone();
+        padded.extend([0; CHECKSUM_WORDS]);
+        let checksum = rs1024_polymod(customization(self.extendable), &padded) ^ 1;
+        indices.extend((0..CHECKSUM_WORDS).rev().map(|i| ((checksum >> (10 * i)) & 0x3ff) as u16));
+
+        indices
+            .iter()
+            .map(|&index| WORDLIST[index as usize])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Восстанавливает мастер-секрет из мнемоник долей.
+pub fn combine(mnemonics: &[&str], passphrase: &str) -> Result<Vec<u8>, Slip39Error> {
+    let shares = mnemonics
+        .iter()
+        .map(|mnemonic| Share::parse(mnemonic))
+        .collect::<Result<Vec<_>, _>>()?;
+    combine_shares(&shares, passphrase)
+}
+
+/// Восстанавливает мастер-секрет из разобранных долей.
+///
+/// Лишние доли допускаются: из каждой полной группы берётся ровно
+/// `member_threshold` долей, из групп — ровно `group_threshold`.
+pub fn combine_shares(shares: &[Share], passphrase: &str) -> Result<Vec<u8>, Slip39Error> {
+    let first = shares.first().ok_or(Slip39Error::NoShares)?;
+    let consistent = shares.iter().all(|share| {
+        share.identifier == first.identifier
+            && share.extendable == first.extendable
+            && share.iteration_exponent == first.iteration_exponent
+            && share.group_threshold == first.group_threshold
+            && share.group_count == first.group_count
+            && share.value.len() == first.value.len()
+    });
+    if !consistent {
+        return Err(Slip39Error::MismatchedShares);
+    }
+
+    // Группа → (участник → доля)
+    let mut groups: BTreeMap<u8, BTreeMap<u8, &Share>> = BTreeMap::new();
+    for share in shares {
+        let members = groups.entry(share.group_index).or_default();
+        if let Some(existing) = members.get(&share.member_index) {
+            if existing.value != share.value {
+                return Err(Slip39Error::ConflictingShares {
+                    group: share.group_index,
+                    member: share.member_index,
+                });
+            }
+        }
+        members.insert(share.member_index, share);
+    }
+
+    let mut group_shares = Vec::new();
+    let mut incomplete = Vec::new();
+    for (&group_index, members) in &groups {
+        let threshold = members.values().next().map(|s| s.member_threshold).unwrap_or(1);
+        if members.values().any(|share| share.member_threshold != threshold) {
+            return Err(Slip39Error::MismatchedShares);
+        }
+        if members.len() < threshold as usize {
+            incomplete.push((group_index, members.len(), threshold));
+            continue;
+        }
+
+        let points: Vec<(u8, &[u8])> = members
+            .values()
+            .take(threshold as usize)
+            .map(|share| (share.member_index, share.value.as_slice()))
+            .collect();
+        group_shares.push((group_index, recover_secret(threshold, &points)?));
+    }
+
+    if group_shares.len() < first.group_threshold as usize {
+        return Err(Slip39Error::NotEnoughGroups {
+            have: group_shares.len(),
+            need: first.group_threshold,
+            incomplete,
+        });
+    }
+
+    let points: Vec<(u8, &[u8])> = group_shares
+        .iter()
+        .take(first.group_threshold as usize)
+        .map(|(index, value)| (*index, value.as_slice()))
+        .collect();
+    let encrypted = recover_secret(first.group_threshold, &points)?;
+
+    Ok(decrypt(
+        &encrypted,
+        passphrase.as_bytes(),
+        first.identifier,
+        first.iteration_exponent,
+        first.extendable,
+    ))
+}
+
+/// Параметры одной группы при разделении: сколько долей нужно из скольких.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupSpec {
+    pub threshold: u8,
+    pub count: u8,
+}
+
+/// Разделяет мастер-секрет на доли SLIP-39.
+///
+/// Возвращает мнемоники долей по группам в порядке `groups`. Для
+/// восстановления нужно `group_threshold` групп, в каждой — порог её долей.
+/// Число итераций PBKDF2 при шифровании равно 10000 << `iteration_exponent`.
+pub fn split(
+    secret: &[u8],
+    passphrase: &str,
+    group_threshold: u8,
+    groups: &[GroupSpec],
+    extendable: bool,
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<String>>, Slip39Error> {
+    if secret.len() * 8 < MIN_STRENGTH_BITS || !secret.len().is_multiple_of(2) {
+        return Err(Slip39Error::InvalidSecretLength(secret.len()));
+    }
+    check_threshold(group_threshold, groups.len().min(u8::MAX as usize) as u8, true)?;
+    for group in groups {
+        check_threshold(group.threshold, group.count, false)?;
+    }
+
+    let mut id_bytes = [0u8; 2];
+    random_bytes(&mut id_bytes);
+    let identifier = u16::from_be_bytes(id_bytes) & 0x7fff;
+
+    let encrypted = encrypt(secret, passphrase.as_bytes(), identifier, iteration_exponent, extendable);
+    let group_shares = split_secret(group_threshold, groups.len() as u8, &encrypted);
+
+    Ok(groups
+        .iter()
+        .zip(group_shares)
+        .map(|(group, (group_index, group_secret))| {
+            split_secret(group.threshold, group.count, &group_secret)
+                .into_iter()
+                .map(|(member_index, value)| {
+                    Share {
+                        identifier,
+                        extendable,
+                        iteration_exponent,
+                        group_index,
+                        group_threshold,
+                        group_count: groups.len() as u8,
+                        member_index,
+                        member_threshold: group.threshold,
+                        value,
+                    }
+                    .to_mnemonic()
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Проверяет схему «threshold из count». Порог 1 при нескольких долях
+/// запрещён стандартом для участников, но допустим для групп.
+fn check_threshold(threshold: u8, count: u8, allow_single: bool) -> Result<(), Slip39Error> {
+    let valid = threshold >= 1
+        && threshold <= count
+        && count <= MAX_SHARE_COUNT
+        && (allow_single || threshold > 1 || count == 1);
+    if valid {
+        Ok(())
+    } else {
+        Err(Slip39Error::InvalidThreshold { threshold, count })
+    }
+}
+
+/// Разделяет секрет уровня на `count` долей с порогом `threshold`.
+///
+/// Первые `threshold - 2` долей случайны, многочлен дополнительно проходит
+/// через точку дайджеста (254) и сам секрет (255).
+fn split_secret(threshold: u8, count: u8, secret: &[u8]) -> Vec<(u8, Vec<u8>)> {
+    if threshold == 1 {
+        return (0..count).map(|index| (index, secret.to_vec())).collect();
+    }
+
+    let random_count = threshold - 2;
+    let mut shares: Vec<(u8, Vec<u8>)> = (0..random_count)
+        .map(|index| {
+            let mut value = vec![0u8; secret.len()];
+            random_bytes(&mut value);
+            (index, value)
+        })
+        .collect();
+
+    let mut random_part = vec![0u8; secret.len() - DIGEST_LENGTH];
+    random_bytes(&mut random_part);
+    let digest_share = [&share_digest(&random_part, secret)[..], &random_part].concat();
+
+    let mut base: Vec<(u8, &[u8])> = shares
+        .iter()
+        .map(|(index, value)| (*index, value.as_slice()))
+        .collect();
+    base.push((DIGEST_INDEX, &digest_share));
+    base.push((SECRET_INDEX, secret));
+
+    let rest: Vec<(u8, Vec<u8>)> = (random_count..count)
+        .map(|index| (index, interpolate(&base, index)))
+        .collect();
+    shares.extend(rest);
+    shares
+}
+
+fn random_bytes(buf: &mut [u8]) {
+    getrandom::getrandom(buf).expect("системный генератор случайных чисел недоступен");
+}
+
+/// Восстанавливает секрет уровня (группы или мастер) и проверяет его дайджест.
+fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> Result<Vec<u8>, Slip39Error> {
+    if threshold == 1 {
+        return Ok(shares[0].1.to_vec());
+    }
+
+    let secret = interpolate(shares, SECRET_INDEX);
+    let digest_share = interpolate(shares, DIGEST_INDEX);
+    let (digest, random_part) = digest_share.split_at(DIGEST_LENGTH);
+
+    if share_digest(random_part, &secret)[..] != digest[..] {
+        return Err(Slip39Error::DigestMismatch);
+    }
+    Ok(secret)
+}
+
+/// Первые 4 байта HMAC-SHA256(random_part, secret).
+fn share_digest(random_part: &[u8], secret: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut engine = HmacEngine::<sha256::Hash>::new(random_part);
+    engine.input(secret);
+    let mac = Hmac::<sha256::Hash>::from_engine(engine).to_byte_array();
+
+    let mut digest = [0u8; DIGEST_LENGTH];
+    digest.copy_from_slice(&mac[..DIGEST_LENGTH]);
+    digest
+}
+
+/// Шифрует мастер-секрет четырёхраундовой сетью Фейстеля
+/// с PBKDF2-HMAC-SHA256 в качестве раундовой функции.
+fn encrypt(
+    secret: &[u8],
+    passphrase: &[u8],
+    identifier: u16,
+    iteration_exponent: u8,
+    extendable: bool,
+) -> Vec<u8> {
+    let salt = feistel_salt(identifier, extendable);
+    feistel(secret, passphrase, iteration_exponent, &salt, 0..ROUND_COUNT)
+}
+
+/// Расшифровывает мастер-секрет: те же раунды в обратном порядке.
+fn decrypt(
+    encrypted: &[u8],
+    passphrase: &[u8],
+    identifier: u16,
+    iteration_exponent: u8,
+    extendable: bool,
+) -> Vec<u8> {
+    let salt = feistel_salt(identifier, extendable);
+    feistel(encrypted, passphrase, iteration_exponent, &salt, (0..ROUND_COUNT).rev())
+}
+
+fn feistel(
+    data: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    salt: &[u8],
+    rounds: impl Iterator<Item = u8>,
+) -> Vec<u8> {
+    let half = data.len() / 2;
+    let mut left = data[..half].to_vec();
+    let mut right = data[half..].to_vec();
+
+    for round in rounds {
+        let f = round_function(round, passphrase, iteration_exponent, salt, &right);
+        let next_right: Vec<u8> = left.iter().zip(&f).map(|(a, b)| a ^ b).collect();
+        left = std::mem::replace(&mut right, next_right);
+    }
+
+    [right, left].concat()
+}
+
+fn round_function(round: u8, passphrase: &[u8], exponent: u8, salt: &[u8], data: &[u8]) -> Vec<u8> {
+    let password = [&[round][..], passphrase].concat();
+    let salt = [salt, data].concat();
+    let iterations = (BASE_ITERATION_COUNT << exponent) / ROUND_COUNT as u32;
+
+    let mut out = vec![0u8; data.len()];
+    pbkdf2::pbkdf2_hmac::<Sha256>(&password, &salt, iterations, &mut out);
+    out
+}
+
+/// Соль сети Фейстеля: "shamir" + идентификатор, для расширяемых копий — пустая.
+fn feistel_salt(identifier: u16, extendable: bool) -> Vec<u8> {
+    if extendable {
+        Vec::new()
+    } else {
+        [CUSTOMIZATION, &identifier.to_be_bytes()[..]].concat()
+    }
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        CUSTOMIZATION_EXTENDABLE
+    } else {
+        CUSTOMIZATION
+    }
+}
+
+/// Контрольная сумма Рида — Соломона над GF(1024).
+fn rs1024_polymod(customization: &[u8], values: &[u16]) -> u32 {
+    const GEN: [u32; 10] = [
+        0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48,
+        0x21B1F890, 0x3F3F120,
+    ];
+
+    let mut chk: u32 = 1;
+    for value in customization.iter().map(|&b| b as u32).chain(values.iter().map(|&v| v as u32)) {
+        let b = chk >> 20;
+        chk = ((chk & 0xFFFFF) << 10) ^ value;
+        for (i, generator) in GEN.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+/// Раскладывает значение доли на 10-битные слова, дополняя его нулями в начале.
+fn value_to_words(value: &[u8]) -> Vec<u16> {
+    let word_count = (value.len() * 8).div_ceil(10);
+    let padding_bits = word_count * 10 - value.len() * 8;
+    let bits: Vec<bool> = std::iter::repeat_n(false, padding_bits)
+        .chain(value.iter().flat_map(|&byte| (0..8).rev().map(move |bit| (byte >> bit) & 1 == 1)))
+        .collect();
+
+    bits.chunks(10)
+        .map(|word| word.iter().fold(0u16, |acc, &bit| (acc << 1) | bit as u16))
+        .collect()
+}
+
+/// Собирает значение доли из 10-битных слов, отбрасывая нулевое выравнивание в начале.
+fn words_to_value(words: &[u16], padding_bits: usize) -> Result<Vec<u8>, Slip39Error> {
+    let bits: Vec<bool> = words
+        .iter()
+        .flat_map(|&word| (0..10).rev().map(move |bit| (word >> bit) & 1 == 1))
+        .collect();
+
+    if bits[..padding_bits].iter().any(|&bit| bit) {
+        return Err(Slip39Error::InvalidPadding);
+    }
+
+    Ok(bits[padding_bits..]
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, &bit| (acc << 1) | bit as u8))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Векторы из vectors.json SLIP-0039 (пароль "TREZOR")
+    const PASSPHRASE: &str = "TREZOR";
+
+    const SINGLE: &str = "duckling enlarge academic academic agency result length solution fridge kidney \
+                          coal piece deal husband erode duke ajar critical decision keyboard";
+    const TWO_OF_THREE: [&str; 2] = [
+        "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist \
+         rescue view short owner flip making coding armed",
+        "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip \
+         twice unkind craft early superior advocate guest smoking",
+    ];
+
+    #[test]
+    fn vector_1_single_share() {
+        assert_eq!(
+            hex::encode(combine(&[SINGLE], PASSPHRASE).unwrap()),
+            "bb54aac4b89dc868ba37d9cc21b2cece"
+        );
+    }
+
+    #[test]
+    fn vector_2_invalid_checksum() {
+        let mnemonic = SINGLE.replace("keyboard", "kidney");
+        assert_eq!(combine(&[&mnemonic], PASSPHRASE), Err(Slip39Error::InvalidChecksum));
+    }
+
+    #[test]
+    fn vector_4_two_of_three() {
+        assert_eq!(
+            hex::encode(combine(&TWO_OF_THREE, PASSPHRASE).unwrap()),
+            "b43ceb7e57a0ea8766221624d01b0864"
+        );
+    }
+
+    #[test]
+    fn vector_5_not_enough_shares() {
+        assert!(matches!(
+            combine(&TWO_OF_THREE[..1], PASSPHRASE),
+            Err(Slip39Error::NotEnoughGroups { have: 0, need: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn vector_6_different_identifiers() {
+        let shares = [
+            "adequate smoking academic acid debut wine petition glen cluster slow rhyme slow simple \
+             epidemic rumor junk tracks treat olympic tolerate",
+            "adequate stay academic agency agency formal party ting frequent learn upstairs remember \
+             smear leaf damage anatomy ladle market hush corner",
+        ];
+        assert_eq!(combine(&shares, PASSPHRASE), Err(Slip39Error::MismatchedShares));
+    }
+
+    #[test]
+    fn vector_7_different_iteration_exponents() {
+        let shares = [
+            "peasant leaves academic acid desert exact olympic math alive axle trial tackle drug deny \
+             decent smear dominant desert bucket remind",
+            "peasant leader academic agency cultural blessing percent network envelope medal junk \
+             primary human pumps jacket fragment payroll ticket evoke voice",
+        ];
+        assert_eq!(combine(&shares, PASSPHRASE), Err(Slip39Error::MismatchedShares));
+    }
+
+    #[test]
+    fn mismatched_group_threshold_is_rejected() {
+        let mut other = Share::parse(TWO_OF_THREE[1]).unwrap();
+        other.group_count = 2;
+        other.group_threshold = 2;
+        let shares = [Share::parse(TWO_OF_THREE[0]).unwrap(), other];
+        assert_eq!(combine_shares(&shares, PASSPHRASE), Err(Slip39Error::MismatchedShares));
+    }
+
+    #[test]
+    fn tampered_share_fails_digest() {
+        let mut tampered = Share::parse(TWO_OF_THREE[0]).unwrap();
+        tampered.value[0] ^= 0x01;
+        // Контрольная сумма RS1024 пересчитывается, поэтому ошибку ловит только дайджест
+        let tampered = tampered.to_mnemonic();
+        assert_eq!(
+            combine(&[&tampered, TWO_OF_THREE[1]], PASSPHRASE),
+            Err(Slip39Error::DigestMismatch)
+        );
+    }
+
+    #[test]
+    fn split_round_trips() {
+        let secret = hex::decode("b43ceb7e57a0ea8766221624d01b0864").unwrap();
+        let specs = [
+            GroupSpec { threshold: 2, count: 3 },
+            GroupSpec { threshold: 1, count: 1 },
+            GroupSpec { threshold: 3, count: 5 },
+        ];
+        let groups = split(&secret, PASSPHRASE, 2, &specs, false, 0).unwrap();
+        let mnemonics = [&groups[0][2], &groups[0][0], &groups[2][4], &groups[2][1], &groups[2][3]];
+        let mnemonics: Vec<&str> = mnemonics.iter().map(|mnemonic| mnemonic.as_str()).collect();
+        assert_eq!(combine(&mnemonics, PASSPHRASE).unwrap(), secret);
+    }
+}
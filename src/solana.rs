@@ -0,0 +1,154 @@
+//! Адреса Solana: ключи Ed25519 по SLIP-0010 на пути `m/44'/501'/i'/0'`.
+
+use crate::derive::{DeriveError, DerivedAddress};
+use crate::ed25519;
+use bitcoin::bip32::{ChildNumber, DerivationPath};
+use bitcoin_hashes::hmac::{Hmac, HmacEngine};
+use bitcoin_hashes::{sha512, Hash, HashEngine};
+
+/// Тип монеты Solana по SLIP-44.
+pub const SOLANA_COIN_TYPE: u32 = 501;
+
+/// Закрытый ключ и код цепочки SLIP-0010.
+struct Node {
+    key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl Node {
+    /// Корень: HMAC-SHA512 с ключом "ed25519 seed" от BIP39 seed.
+    fn master(seed: &[u8]) -> Node {
+        Node::from_hmac(b"ed25519 seed", &[seed])
+    }
+
+    /// Дочерний ключ; для Ed25519 SLIP-0010 допускает только hardened-индексы.
+    fn child(&self, index: ChildNumber) -> Result<Node, DeriveError> {
+        match index {
+            ChildNumber::Hardened { .. } => {
+                let index = u32::from(index).to_be_bytes();
+                Ok(Node::from_hmac(&self.chain_code, &[&[0], &self.key, &index]))
+            }
+            ChildNumber::Normal { .. } => Err(DeriveError::NonHardened(index)),
+        }
+    }
+
+    fn from_hmac(key: &[u8], data: &[&[u8]]) -> Node {
+        let mut engine = HmacEngine::<sha512::Hash>::new(key);
+        for part in data {
+            engine.input(part);
+        }
+        let output = Hmac::<sha512::Hash>::from_engine(engine).to_byte_array();
+        Node {
+            key: output[..32].try_into().expect("половина HMAC-SHA512 — 32 байта"),
+            chain_code: output[32..].try_into().expect("половина HMAC-SHA512 — 32 байта"),
+        }
+    }
+}
+
+/// Путь счёта Solana `m/44'/501'/account'/0'` (как в Phantom и Solflare).
+pub fn solana_path(account: u32) -> Result<DerivationPath, DeriveError> {
+    Ok(DerivationPath::from(vec![
+        ChildNumber::from_hardened_idx(44)?,
+        ChildNumber::from_hardened_idx(SOLANA_COIN_TYPE)?,
+        ChildNumber::from_hardened_idx(account)?,
+        ChildNumber::from_hardened_idx(0)?,
+    ]))
+}
+
+/// Открытый ключ Ed25519 по пути `path` от BIP39 seed (SLIP-0010).
+pub fn slip10_ed25519_public_key(seed: &[u8], path: &DerivationPath) -> Result<[u8; 32], DeriveError> {
+    let node = path
+        .into_iter()
+        .try_fold(Node::master(seed), |node, &index| node.child(index))?;
+    Ok(ed25519::public_key(&node.key))
+}
+
+/// Выводит `count` адресов Solana (открытые ключи в Base58) для счетов
+/// `start .. start+count-1` по пути `m/44'/501'/i'/0'`.
+pub fn derive_solana_addresses(seed: &[u8], start: u32, count: u32) -> Result<Vec<DerivedAddress>, DeriveError> {
+    (start..start.saturating_add(count))
+        .map(|account| {
+            let path = solana_path(account)?;
+            let public_key = slip10_ed25519_public_key(seed, &path)?;
+            Ok(DerivedAddress {
+                path,
+                address: bitcoin::base58::encode(&public_key),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// SLIP-0010, Test vector 1 for ed25519: путь, код цепочки, закрытый
+    /// и открытый ключ.
+    const VECTOR_1: [(&str, &str, &str, &str); 6] = [
+        (
+            "m",
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+        ),
+        (
+            "m/0'",
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+        ),
+        (
+            "m/0'/1'",
+            "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+        ),
+        (
+            "m/0'/1'/2'",
+            "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+            "ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+        ),
+        (
+            "m/0'/1'/2'/2'",
+            "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+            "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+            "8abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c429c",
+        ),
+        (
+            "m/0'/1'/2'/2'/1000000000'",
+            "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+            "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a",
+        ),
+    ];
+
+    #[test]
+    fn slip10_vector_1() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        for (path, chain_code, key, public_key) in VECTOR_1 {
+            let path = DerivationPath::from_str(path).unwrap();
+            let node = path
+                .into_iter()
+                .try_fold(Node::master(&seed), |node, &index| node.child(index))
+                .unwrap();
+            assert_eq!(hex::encode(node.chain_code), chain_code, "{path}");
+            assert_eq!(hex::encode(node.key), key, "{path}");
+            assert_eq!(
+                hex::encode(slip10_ed25519_public_key(&seed, &path).unwrap()),
+                public_key,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn normal_index_is_rejected() {
+        let path = DerivationPath::from_str("m/0'/1").unwrap();
+        assert!(matches!(
+            slip10_ed25519_public_key(&[0; 16], &path),
+            Err(DeriveError::NonHardened(_))
+        ));
+    }
+}
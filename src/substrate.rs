@@ -0,0 +1,337 @@
+//! Ключи и адреса Substrate (Polkadot, Kusama и парачейны): мини-секрет из
+//! энтропии фразы, деривация sr25519/ed25519 по соединениям `//hard/soft`
+//! и адреса SS58.
+
+use crate::ed25519::{self, Point};
+use crate::merlin::Transcript;
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+use sha2::{Digest, Sha512};
+use std::fmt;
+use std::str::FromStr;
+
+/// Тип монеты Polkadot по SLIP-44.
+pub const POLKADOT_COIN_TYPE: u32 = 354;
+
+/// Наибольший префикс сети SS58 (14 бит).
+pub const MAX_SS58_PREFIX: u16 = 0x3fff;
+
+/// Схема подписи ключей Substrate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubstrateCrypto {
+    /// Schnorr на Ristretto255 (schnorrkel), по умолчанию в Polkadot.js.
+    Sr25519,
+    /// Ed25519: допускает только жёсткие соединения.
+    Ed25519,
+}
+
+impl FromStr for SubstrateCrypto {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sr25519" | "sr" => Ok(SubstrateCrypto::Sr25519),
+            "ed25519" | "ed" => Ok(SubstrateCrypto::Ed25519),
+            _ => Err(format!("неизвестная схема '{}'; допустимые: sr25519, ed25519", s)),
+        }
+    }
+}
+
+/// Соединение пути деривации: `//name` — жёсткое, `/name` — мягкое.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Junction {
+    pub hard: bool,
+    /// Код цепочки: число — u64 little-endian, иначе строка в кодировке SCALE;
+    /// дополняется нулями до 32 байт, длинные значения хэшируются BLAKE2b-256.
+    pub chain_code: [u8; 32],
+}
+
+impl Junction {
+    fn new(name: &str, hard: bool) -> Self {
+        let encoded = match name.parse::<u64>() {
+            Ok(number) => number.to_le_bytes().to_vec(),
+            Err(_) => {
+                let mut encoded = compact_length(name.len());
+                encoded.extend_from_slice(name.as_bytes());
+                encoded
+            }
+        };
+        let mut chain_code = [0u8; 32];
+        if encoded.len() > 32 {
+            chain_code = blake2b::<32>(&[&encoded]);
+        } else {
+            chain_code[..encoded.len()].copy_from_slice(&encoded);
+        }
+        Junction { hard, chain_code }
+    }
+}
+
+/// Ошибка деривации Substrate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubstrateError {
+    /// Путь не разбирается на соединения `/name` и `//name`.
+    InvalidPath(String),
+    /// Мягкое соединение для ключа ed25519.
+    SoftEd25519,
+    /// Длина энтропии (в байтах) вне 16..=32 или не кратна 4.
+    BadEntropy(usize),
+    /// Префикс SS58 больше [`MAX_SS58_PREFIX`].
+    BadPrefix(u16),
+}
+
+impl fmt::Display for SubstrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubstrateError::InvalidPath(path) => write!(
+                f,
+                "Неверный путь Substrate '{}': ожидаются соединения вида //hard и /soft",
+                path
+            ),
+            SubstrateError::SoftEd25519 => {
+                write!(f, "Ключи ed25519 допускают только жёсткие соединения (//name)")
+            }
+            SubstrateError::BadEntropy(length) => write!(
+                f,
+                "Неверная длина энтропии для Substrate: {} байт (нужно 16–32, кратно 4)",
+                length
+            ),
+            SubstrateError::BadPrefix(prefix) => write!(
+                f,
+                "Префикс SS58 {} вне диапазона 0–{}",
+                prefix, MAX_SS58_PREFIX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SubstrateError {}
+
+/// Разбирает путь вида `//polkadot//0/1` на соединения; пустая строка — корень.
+pub fn parse_junctions(path: &str) -> Result<Vec<Junction>, SubstrateError> {
+    let invalid = || SubstrateError::InvalidPath(path.to_string());
+    let mut junctions = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        rest = rest.strip_prefix('/').ok_or_else(invalid)?;
+        let hard = rest.starts_with('/');
+        if hard {
+            rest = &rest[1..];
+        }
+        let end = rest.find('/').unwrap_or(rest.len());
+        if end == 0 {
+            return Err(invalid());
+        }
+        junctions.push(Junction::new(&rest[..end], hard));
+        rest = &rest[end..];
+    }
+    Ok(junctions)
+}
+
+/// Мини-секрет Substrate (substrate-bip39): первые 32 байта
+/// PBKDF2-HMAC-SHA512(энтропия, соль = "mnemonic" + пароль, 2048 итераций).
+/// В отличие от BIP39 seed, вход — энтропия фразы, а не её слова.
+pub fn mini_secret_from_entropy(entropy: &[u8], password: &str) -> Result<[u8; 32], SubstrateError> {
+    if !(16..=32).contains(&entropy.len()) || !entropy.len().is_multiple_of(4) {
+        return Err(SubstrateError::BadEntropy(entropy.len()));
+    }
+    let salt = [b"mnemonic".as_slice(), password.as_bytes()].concat();
+    let mut seed = [0u8; 64];
+    pbkdf2::pbkdf2_hmac::<Sha512>(entropy, &salt, 2048, &mut seed);
+    Ok(seed[..32].try_into().expect("срез 32 байта"))
+}
+
+/// Открытый ключ (32 байта) по энтропии фразы, паролю и соединениям пути,
+/// как у `subkey inspect "<фраза>//hard/soft" --password ...`.
+pub fn substrate_public_key(
+    entropy: &[u8],
+    password: &str,
+    crypto: SubstrateCrypto,
+    junctions: &[Junction],
+) -> Result<[u8; 32], SubstrateError> {
+    let mini_secret = mini_secret_from_entropy(entropy, password)?;
+    match crypto {
+        SubstrateCrypto::Sr25519 => {
+            let key = junctions
+                .iter()
+                .fold(Sr25519Key::expand(&mini_secret), |key, junction| key.derive(junction));
+            Ok(key.public_key())
+        }
+        SubstrateCrypto::Ed25519 => {
+            let seed = junctions.iter().try_fold(mini_secret, |seed, junction| {
+                if !junction.hard {
+                    return Err(SubstrateError::SoftEd25519);
+                }
+                // SCALE-кортеж ("Ed25519HDKD", seed, chain_code)
+                let mut label = compact_length(11);
+                label.extend_from_slice(b"Ed25519HDKD");
+                Ok(blake2b::<32>(&[&label, &seed, &junction.chain_code]))
+            })?;
+            Ok(ed25519::public_key(&seed))
+        }
+    }
+}
+
+/// Адрес SS58: префикс сети, открытый ключ и 2 байта контрольной суммы
+/// BLAKE2b-512("SS58PRE" || данные) в Base58. Префикс 0 — Polkadot,
+/// 2 — Kusama, 42 — общий формат Substrate.
+pub fn ss58_encode(public_key: &[u8; 32], prefix: u16) -> Result<String, SubstrateError> {
+    let mut data = match prefix {
+        0..=63 => vec![prefix as u8],
+        64..=MAX_SS58_PREFIX => vec![
+            ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+            (prefix >> 8) as u8 | ((prefix & 0b11) as u8) << 6,
+        ],
+        _ => return Err(SubstrateError::BadPrefix(prefix)),
+    };
+    data.extend_from_slice(public_key);
+    let checksum = blake2b::<64>(&[b"SS58PRE", &data]);
+    data.extend_from_slice(&checksum[..2]);
+    Ok(bitcoin::base58::encode(&data))
+}
+
+/// Закрытый ключ sr25519: скаляр по модулю ℓ (без nonce — для вывода
+/// открытых ключей он не нужен).
+struct Sr25519Key {
+    scalar: [u8; 32],
+}
+
+impl Sr25519Key {
+    /// Развёртка мини-секрета в режиме Ed25519 (как в substrate):
+    /// SHA-512, «зажатие» битов и деление на кофактор 8.
+    fn expand(mini_secret: &[u8; 32]) -> Self {
+        let hash = Sha512::digest(mini_secret);
+        let mut key: [u8; 32] = hash[..32].try_into().expect("срез 32 байта");
+        key[0] &= 248;
+        key[31] &= 63;
+        key[31] |= 64;
+        let mut scalar = [0u8; 32];
+        for i in 0..32 {
+            let high = key.get(i + 1).map_or(0, |byte| byte << 5);
+            scalar[i] = key[i] >> 3 | high;
+        }
+        Sr25519Key { scalar }
+    }
+
+    fn point(&self) -> Point {
+        Point::BASE.mul(&self.scalar)
+    }
+
+    fn public_key(&self) -> [u8; 32] {
+        self.point().compress_ristretto()
+    }
+
+    /// Шаг HDKD schnorrkel: жёсткий — новый мини-секрет из транскрипта с
+    /// закрытым ключом, мягкий — ключ плюс скаляр из транскрипта с открытым.
+    fn derive(&self, junction: &Junction) -> Self {
+        let mut transcript = Transcript::new(b"SchnorrRistrettoHDKD");
+        transcript.append_message(b"sign-bytes", b"");
+        transcript.append_message(b"chain-code", &junction.chain_code);
+        if junction.hard {
+            transcript.append_message(b"secret-key", &self.scalar);
+            let mut mini_secret = [0u8; 32];
+            transcript.challenge_bytes(b"HDKD-hard", &mut mini_secret);
+            Sr25519Key::expand(&mini_secret)
+        } else {
+            transcript.append_message(b"public-key", &self.public_key());
+            let mut wide = [0u8; 64];
+            transcript.challenge_bytes(b"HDKD-scalar", &mut wide);
+            let offset = ed25519::scalar_reduce(&wide);
+            Sr25519Key {
+                scalar: ed25519::scalar_add(&ed25519::scalar_reduce(&self.scalar), &offset),
+            }
+        }
+    }
+}
+
+/// Префикс длины SCALE (compact) для строки из `length` байт.
+fn compact_length(length: usize) -> Vec<u8> {
+    match length {
+        0..=0x3f => vec![(length << 2) as u8],
+        0x40..=0x3fff => ((length << 2 | 0b01) as u16).to_le_bytes().to_vec(),
+        _ => ((length << 2 | 0b10) as u32).to_le_bytes().to_vec(),
+    }
+}
+
+fn blake2b<const N: usize>(data: &[&[u8]]) -> [u8; N] {
+    let mut hasher = Blake2bVar::new(N).expect("длина BLAKE2b допустима");
+    for part in data {
+        hasher.update(part);
+    }
+    let mut hash = [0u8; N];
+    hasher.finalize_variable(&mut hash).expect("буфер нужной длины");
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, Options};
+
+    /// Фраза ключей разработчика Substrate (DEV_PHRASE в sp-core).
+    const DEV_PHRASE: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+    fn public_key(path: &str, crypto: SubstrateCrypto) -> [u8; 32] {
+        let decoded = decode(DEV_PHRASE, &Options::default()).unwrap();
+        let junctions = parse_junctions(path).unwrap();
+        substrate_public_key(decoded.entropy.as_bytes(), "", crypto, &junctions).unwrap()
+    }
+
+    #[test]
+    fn dev_phrase_sr25519() {
+        let root = public_key("", SubstrateCrypto::Sr25519);
+        assert_eq!(
+            ss58_encode(&root, 42).unwrap(),
+            "5DfhGyQdFobKM8NsWvEeAKk5EQQgYe9AydgJ7rMB6E1EqRzV"
+        );
+
+        let alice = public_key("//Alice", SubstrateCrypto::Sr25519);
+        assert_eq!(
+            hex::encode(alice),
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+        assert_eq!(
+            ss58_encode(&alice, 42).unwrap(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert_eq!(
+            ss58_encode(&alice, 0).unwrap(),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+
+        assert_eq!(
+            hex::encode(public_key("/Alice", SubstrateCrypto::Sr25519)),
+            "d6c71059dbbe9ad2b0ed3f289738b800836eb425544ce694825285b958ca755e"
+        );
+    }
+
+    #[test]
+    fn dev_phrase_ed25519() {
+        let alice = public_key("//Alice", SubstrateCrypto::Ed25519);
+        assert_eq!(
+            hex::encode(alice),
+            "88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee"
+        );
+        assert_eq!(
+            ss58_encode(&alice, 42).unwrap(),
+            "5FA9nQDVg267DEd8m1ZypXLBnvN7SFxYwV7ndqSYGiN9TTpu"
+        );
+
+        let decoded = decode(DEV_PHRASE, &Options::default()).unwrap();
+        let junctions = parse_junctions("//Alice/0").unwrap();
+        assert_eq!(
+            substrate_public_key(decoded.entropy.as_bytes(), "", SubstrateCrypto::Ed25519, &junctions),
+            Err(SubstrateError::SoftEd25519)
+        );
+    }
+
+    #[test]
+    fn junctions_and_prefixes() {
+        let junctions = parse_junctions("//polkadot/7").unwrap();
+        assert!(junctions[0].hard && !junctions[1].hard);
+        assert_eq!(&junctions[0].chain_code[..9], b"\x20polkadot");
+        assert_eq!(junctions[1].chain_code[..8], 7u64.to_le_bytes());
+        assert!(matches!(parse_junctions("Alice"), Err(SubstrateError::InvalidPath(_))));
+        assert!(matches!(parse_junctions("///x"), Err(SubstrateError::InvalidPath(_))));
+        assert_eq!(ss58_encode(&[0; 32], MAX_SS58_PREFIX + 1), Err(SubstrateError::BadPrefix(0x4000)));
+    }
+}
@@ -0,0 +1,116 @@
+//! Обратная проверка декодирования: энтропия снова кодируется во фразу
+//! и сравнивается с исходной.
+
+use crate::{normalize_mnemonic, Decoded, Options, VALID_WORD_COUNTS};
+use bitcoin_hashes::{sha256, Hash};
+use std::fmt;
+
+/// Причина, по которой энтропия не воспроизводит исходную фразу.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// Длина энтропии не соответствует ни фразе BIP39, ни всем битам фразы
+    /// (`ignore_checksum`).
+    BadEntropyLength(usize),
+    /// Число слов фразы из энтропии отличается от исходного.
+    WordCount { expected: usize, actual: usize },
+    /// Слово на позиции `position` (с нуля) отличается от исходного.
+    Mismatch {
+        position: usize,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::BadEntropyLength(len) => write!(
+                f,
+                "Обратная проверка: энтропию из {} байт нельзя закодировать во фразу",
+                len
+            ),
+            VerifyError::WordCount { expected, actual } => write!(
+                f,
+                "Обратная проверка: из энтропии получилось {} слов вместо {}",
+                actual, expected
+            ),
+            VerifyError::Mismatch {
+                position,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Обратная проверка: слово {} — {} вместо {}",
+                position + 1,
+                actual,
+                expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Кодирует энтропию `decoded` обратно словарём, по которому она получена,
+/// и сравнивает с фразой `mnemonic` пословно (пробелы не учитываются).
+///
+/// Энтропия длины BIP39 дополняется контрольной суммой; энтропия из всех
+/// 11*N бит фразы (`ignore_checksum`) разбирается на слова как есть.
+pub fn verify_round_trip(mnemonic: &str, decoded: &Decoded, options: &Options) -> Result<(), VerifyError> {
+    let entropy = decoded.entropy.as_bytes();
+    let indices = entropy_indices(entropy).ok_or(VerifyError::BadEntropyLength(entropy.len()))?;
+    let encoded: Vec<&str> = match (&options.wordlist, decoded.language) {
+        (Some(wordlist), _) => indices.iter().map(|&index| wordlist.word(index)).collect(),
+        (None, Some(language)) => {
+            let words = language.word_list();
+            indices.iter().map(|&index| words[index as usize]).collect()
+        }
+        (None, None) => return Err(VerifyError::BadEntropyLength(entropy.len())),
+    };
+
+    let mnemonic = &*normalize_mnemonic(mnemonic);
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    if words.len() != encoded.len() {
+        return Err(VerifyError::WordCount {
+            expected: words.len(),
+            actual: encoded.len(),
+        });
+    }
+    match words.iter().zip(&encoded).position(|(word, encoded)| word != encoded) {
+        Some(position) => Err(VerifyError::Mismatch {
+            position,
+            expected: words[position].to_string(),
+            actual: encoded[position].to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Индексы слов для энтропии: по стандарту BIP39 (энтропия и ENT/32 бит
+/// SHA-256) или, если длина равна ⌈11*N/8⌉ байт, — первые 11*N бит как есть.
+fn entropy_indices(entropy: &[u8]) -> Option<Vec<u16>> {
+    let (bits, word_count) = if let Some(&count) = VALID_WORD_COUNTS
+        .iter()
+        .find(|&&count| count * 11 * 32 / 33 == entropy.len() * 8)
+    {
+        let mut bits = entropy.to_vec();
+        bits.push(sha256::Hash::hash(entropy).to_byte_array()[0]);
+        (bits, count)
+    } else {
+        let count = *VALID_WORD_COUNTS
+            .iter()
+            .find(|&&count| (count * 11).div_ceil(8) == entropy.len())?;
+        (entropy.to_vec(), count)
+    };
+
+    let indices = (0..word_count)
+        .map(|i| {
+            (0..11).fold(0u16, |index, j| {
+                let bit_pos = i * 11 + j;
+                let bit = bits[bit_pos / 8] >> (7 - bit_pos % 8) & 1;
+                index << 1 | bit as u16
+            })
+        })
+        .collect();
+    Some(indices)
+}
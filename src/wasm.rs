@@ -0,0 +1,91 @@
+//! Привязки JavaScript (функция `wasm`): та же логика декодирования,
+//! что и в утилите, для браузерных инструментов восстановления.
+
+use crate::{language_from_name, language_name, Language, Options, Wordlist};
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+/// Параметры декодирования: `new DecodeOptions()`, затем сеттеры.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct DecodeOptions {
+    ignore_checksum: bool,
+    language: Option<Language>,
+    wordlist: Option<Arc<Wordlist>>,
+}
+
+#[wasm_bindgen]
+impl DecodeOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> DecodeOptions {
+        DecodeOptions::default()
+    }
+
+    /// Декодировать фразу с неверной контрольной суммой.
+    #[wasm_bindgen(setter = ignoreChecksum)]
+    pub fn set_ignore_checksum(&mut self, ignore_checksum: bool) {
+        self.ignore_checksum = ignore_checksum;
+    }
+
+    /// Использовать только словарь указанного языка (english, japanese, ...).
+    #[wasm_bindgen(js_name = setLanguage)]
+    pub fn set_language(&mut self, name: &str) -> Result<(), JsError> {
+        let language = language_from_name(name)
+            .ok_or_else(|| JsError::new(&format!("неизвестный язык '{}'", name)))?;
+        self.language = Some(language);
+        self.wordlist = None;
+        Ok(())
+    }
+
+    /// Пользовательский словарь: 2048 слов, по одному на строку.
+    #[wasm_bindgen(js_name = setWordlist)]
+    pub fn set_wordlist(&mut self, text: &str) -> Result<(), JsError> {
+        let wordlist = Wordlist::parse(text).map_err(|e| JsError::new(&e.to_string()))?;
+        self.wordlist = Some(Arc::new(wordlist));
+        self.language = None;
+        Ok(())
+    }
+}
+
+/// Результат декодирования.
+#[wasm_bindgen]
+pub struct DecodeResult {
+    entropy: Vec<u8>,
+    language: Option<Language>,
+}
+
+#[wasm_bindgen]
+impl DecodeResult {
+    /// Энтропия в hex.
+    #[wasm_bindgen(getter)]
+    pub fn entropy(&self) -> String {
+        hex::encode(&self.entropy)
+    }
+
+    /// Энтропия байтами (`Uint8Array`).
+    #[wasm_bindgen(getter)]
+    pub fn bytes(&self) -> Vec<u8> {
+        self.entropy.clone()
+    }
+
+    /// Словарь, по которому распознана фраза; "custom" для пользовательского.
+    #[wasm_bindgen(getter)]
+    pub fn language(&self) -> String {
+        self.language.map(language_name).unwrap_or("custom").to_string()
+    }
+}
+
+/// Мнемоника → энтропия; при ошибке бросает исключение с её описанием.
+#[wasm_bindgen]
+pub fn decode(mnemonic: &str, options: &DecodeOptions) -> Result<DecodeResult, JsError> {
+    let options = Options {
+        ignore_checksum: options.ignore_checksum,
+        language: options.language,
+        wordlist: options.wordlist.clone(),
+    };
+    let decoded = crate::decode(mnemonic, &options).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(DecodeResult {
+        entropy: decoded.entropy.as_bytes().to_vec(),
+        language: decoded.language,
+    })
+}
@@ -0,0 +1,82 @@
+//! Пользовательские словари из 2048 слов.
+
+use std::collections::HashMap;
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+
+/// Размер словаря BIP39: каждое слово кодирует 11 бит.
+pub const WORDLIST_SIZE: usize = 2048;
+
+/// Нестандартный словарь из 2048 слов (списки сообществ, старые кошельки).
+#[derive(Debug, Clone)]
+pub struct Wordlist {
+    words: Vec<String>,
+    index: HashMap<String, u16>,
+}
+
+/// Ошибка загрузки пользовательского словаря.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordlistError {
+    /// В словаре не 2048 слов.
+    BadLength(usize),
+    /// Слово встречается в словаре больше одного раза.
+    DuplicateWord(String),
+}
+
+impl fmt::Display for WordlistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordlistError::BadLength(len) => write!(
+                f,
+                "Словарь должен содержать {} слов, найдено: {}",
+                WORDLIST_SIZE, len
+            ),
+            WordlistError::DuplicateWord(word) => {
+                write!(f, "Слово встречается в словаре дважды: {}", word)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordlistError {}
+
+impl Wordlist {
+    /// Строит словарь из списка слов; порядок слов задаёт их индексы.
+    /// Слова приводятся к NFKD, как и проверяемые фразы.
+    pub fn from_words(words: Vec<String>) -> Result<Self, WordlistError> {
+        let words: Vec<String> = words.into_iter().map(|word| word.nfkd().collect()).collect();
+        if words.len() != WORDLIST_SIZE {
+            return Err(WordlistError::BadLength(words.len()));
+        }
+
+        let mut index = HashMap::with_capacity(WORDLIST_SIZE);
+        for (i, word) in words.iter().enumerate() {
+            if index.insert(word.clone(), i as u16).is_some() {
+                return Err(WordlistError::DuplicateWord(word.clone()));
+            }
+        }
+
+        Ok(Wordlist { words, index })
+    }
+
+    /// Разбирает текст словаря: одно слово на строку, пустые строки пропускаются.
+    pub fn parse(text: &str) -> Result<Self, WordlistError> {
+        let words = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+        Self::from_words(words)
+    }
+
+    /// Индекс слова в словаре.
+    pub fn find_word(&self, word: &str) -> Option<u16> {
+        self.index.get(word).copied()
+    }
+
+    /// Слово по индексу.
+    pub fn word(&self, index: u16) -> &str {
+        &self.words[index as usize]
+    }
+}